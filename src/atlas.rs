@@ -0,0 +1,558 @@
+use std::{
+    fs,
+    io
+};
+
+use std::convert::{
+    AsRef
+};
+
+use std::path::{
+    Path
+};
+
+use std::vec::{
+    Vec
+};
+
+use byteorder::{
+    LittleEndian,
+    ReadBytesExt,
+    WriteBytesExt
+};
+
+use crate::geom::d2::contour;
+
+use crate::geom::d2::prim::vect::{
+    Vect as Vect2
+};
+
+use crate::geom::d3::mesh::ind::{
+    IndSegMesh
+};
+
+use crate::geom::d3::prim::vect::{
+    Vector as Vector3
+};
+
+/// magic bytes at start of binary atlas file
+const MAGIC : &[u8; 8] = b"TGATLAS\0";
+
+/// version of binary atlas file
+const VERSION : u16 = 1;
+
+/// relative positions in each grid cell where the error bounds are estimated (center, edge midpoints and quarter points)
+const ERROR_SAMPLES : [(f64, f64); 9] = [
+    (0.5f64, 0.5f64),
+    (0.5f64, 0f64), (0f64, 0.5f64), (1f64, 0.5f64), (0.5f64, 1f64),
+    (0.25f64, 0.25f64), (0.25f64, 0.75f64), (0.75f64, 0.25f64), (0.75f64, 0.75f64)
+];
+
+/// regular grid of angles from start to end (inclusive) with count angles
+#[derive(Clone, Copy, Debug)]
+pub struct AngleGrid {
+    start : f64,
+    end   : f64,
+    count : usize
+}
+
+/// how an atlas answers queries for angles between grid angles
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AtlasQuery {
+    /// contour of the nearest grid angles
+    Nearest,
+    /// bilinear blend of the contours of the 4 surrounding grid angles
+    Blend
+}
+
+/// error bounds of a grid cell estimated against freshly computed contours at several positions in the cell
+///
+/// infinite if one of these contours could not be computed
+#[derive(Clone, Copy, Debug)]
+pub struct AtlasError {
+    nearest : f64,
+    blend   : f64
+}
+
+/// contour answered by atlas with the error bound of the grid cell it lies in
+pub struct AtlasContour {
+    vertices : Vec<Vect2<f64>>,
+    error    : f64
+}
+
+/// precomputed contours of a mesh on a grid of inclination and anteversion angles
+///
+/// every contour is resampled to the same number of counterclockwise vertices with corresponding vertices
+/// of neighbouring grid angles aligned, so contours can be blended vertex by vertex
+pub struct Atlas {
+    inclination : AngleGrid,
+    anteversion : AngleGrid,
+    samples     : usize,
+    contours    : Vec<Option<Vec<Vect2<f64>>>>,
+    errors      : Vec<AtlasError>
+}
+
+impl AngleGrid {
+    /// create new AngleGrid from start to end with count angles (at least 1)
+    pub fn new(start : f64, end : f64, count : usize) -> AngleGrid {
+        AngleGrid{start : start, end : end, count : count.max(1)}
+    }
+
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    pub fn end(&self) -> f64 {
+        self.end
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// number of cells between grid angles
+    pub fn cells(&self) -> usize {
+        (self.count - 1).max(1)
+    }
+
+    /// distance between two neighbouring grid angles
+    pub fn step(&self) -> f64 {
+        match self.count > 1 {
+            false => 0f64,
+            true  => (self.end - self.start) / (self.count - 1) as f64
+        }
+    }
+
+    /// angle at index
+    pub fn angle(&self, index : usize) -> f64 {
+        self.start + self.step() * index as f64
+    }
+
+    /// center angle of cell at index
+    pub fn center(&self, cell : usize) -> f64 {
+        self.angle(cell) + self.step() / 2f64
+    }
+
+    /// cell index and relative position in cell in \[0, 1\] of angle clamped to grid
+    pub fn locate(&self, angle : f64) -> (usize, f64) {
+        let step = self.step();
+
+        if step == 0f64 {
+            return (0, 0f64)
+        }
+
+        let pos  = ((angle - self.start) / step).max(0f64).min((self.count - 1) as f64);
+        let cell = (pos.floor() as usize).min(self.cells() - 1);
+
+        (cell, pos - cell as f64)
+    }
+
+    /// index of grid angle nearest to angle
+    pub fn nearest(&self, angle : f64) -> usize {
+        let (cell, r) = self.locate(angle);
+
+        match r < 0.5f64 {
+            false => (cell + 1).min(self.count - 1),
+            true  => cell
+        }
+    }
+}
+
+impl AtlasError {
+    /// error bound of nearest queries
+    pub fn nearest(&self) -> f64 {
+        self.nearest
+    }
+
+    /// error bound of blend queries
+    pub fn blend(&self) -> f64 {
+        self.blend
+    }
+
+    /// error bound of query
+    pub fn query(&self, query : AtlasQuery) -> f64 {
+        match query {
+            AtlasQuery::Nearest => self.nearest,
+            AtlasQuery::Blend   => self.blend
+        }
+    }
+}
+
+impl AtlasContour {
+    pub fn vertices(&self) -> &Vec<Vect2<f64>> {
+        &self.vertices
+    }
+
+    /// estimated maximal hausdorff distance to freshly computed contour
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    pub fn into_vertices(self) -> Vec<Vect2<f64>> {
+        self.vertices
+    }
+}
+
+impl Atlas {
+    // General methods
+
+    /// precompute contours of mesh (see IndSegMesh::template_contour) on grid of inclination and anteversion angles
+    ///
+    /// each contour is resampled to samples vertices. max limits the vertices of each computed contour.
+    /// every contour is aligned to all already aligned neighbouring contours.
+    /// computes additional contours at several positions per grid cell to estimate the error bounds of the cell
+    pub fn generate<V : Vector3<Val = f64>>(mesh : &IndSegMesh<V>, inclination : AngleGrid, anteversion : AngleGrid, samples : usize, max : usize) -> Atlas {
+        let mut contours : Vec<Option<Vec<Vect2<f64>>>> = Vec::with_capacity(inclination.count() * anteversion.count());
+
+        for i in 0..inclination.count() {
+            for j in 0..anteversion.count() {
+                let contour = mesh.template_contour(inclination.angle(i), anteversion.angle(j), max);
+                let contour = contour.and_then(|contour| Atlas::normalize(&contour, samples));
+
+                let contour = contour.map(|contour| {
+                    let references = Atlas::references(&contours, &anteversion, i, j);
+
+                    match references.is_empty() {
+                        true  => contour,
+                        false => contour::align_all(&references, &contour)
+                    }
+                });
+
+                contours.push(contour);
+            }
+        }
+
+        let mut atlas = Atlas{
+            inclination : inclination,
+            anteversion : anteversion,
+            samples     : samples,
+            contours    : contours,
+            errors      : Vec::new()
+        };
+
+        let infinite = AtlasError{nearest : f64::INFINITY, blend : f64::INFINITY};
+        let mut errors = Vec::with_capacity(inclination.cells() * anteversion.cells());
+
+        for i in 0..inclination.cells() {
+            for j in 0..anteversion.cells() {
+                let mut error = AtlasError{nearest : 0f64, blend : 0f64};
+
+                for (r, s) in ERROR_SAMPLES {
+                    let incl = inclination.angle(i) + inclination.step() * r;
+                    let ante = anteversion.angle(j) + anteversion.step() * s;
+
+                    let Some(fresh) = mesh.template_contour(incl, ante, max) else {
+                        error = infinite;
+                        break;
+                    };
+
+                    let blend = atlas.blend(incl, ante).and_then(|blend| contour::hausdorff(&blend, &fresh));
+
+                    // at half steps several corners are equally near so any of them can be answered
+                    let mut nearest = Some(0f64);
+
+                    for (ci, cj) in Atlas::nearest_corners(&inclination, &anteversion, i, j, r, s) {
+                        let corner = atlas.node(ci, cj).and_then(|corner| contour::hausdorff(corner, &fresh));
+
+                        nearest = match (nearest, corner) {
+                            (Some(nearest), Some(corner)) => Some(nearest.max(corner)),
+                            _ => None
+                        };
+                    }
+
+                    error.nearest = error.nearest.max(nearest.unwrap_or(f64::INFINITY));
+                    error.blend   = error.blend.max(blend.unwrap_or(f64::INFINITY));
+                }
+
+                errors.push(error);
+            }
+        }
+
+        atlas.errors = errors;
+        atlas
+    }
+
+    pub fn inclination(&self) -> &AngleGrid {
+        &self.inclination
+    }
+
+    pub fn anteversion(&self) -> &AngleGrid {
+        &self.anteversion
+    }
+
+    /// number of vertices of each contour
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// contour at grid angles with indices i (inclination) and j (anteversion)
+    pub fn node(&self, i : usize, j : usize) -> Option<&Vec<Vect2<f64>>> {
+        self.contours.get(i * self.anteversion.count() + j)?.as_ref()
+    }
+
+    /// error bounds of grid cell with indices i (inclination) and j (anteversion)
+    pub fn cell_error(&self, i : usize, j : usize) -> Option<&AtlasError> {
+        self.errors.get(i * self.anteversion.cells() + j)
+    }
+
+    // Query methods
+
+    /// contour of grid angles nearest to inclination and anteversion
+    pub fn nearest(&self, inclination : f64, anteversion : f64) -> Option<Vec<Vect2<f64>>> {
+        let i = self.inclination.nearest(inclination);
+        let j = self.anteversion.nearest(anteversion);
+
+        self.node(i, j).cloned()
+    }
+
+    /// bilinear blend of the contours of the 4 grid angles surrounding inclination and anteversion
+    pub fn blend(&self, inclination : f64, anteversion : f64) -> Option<Vec<Vect2<f64>>> {
+        let (i, r) = self.inclination.locate(inclination);
+        let (j, s) = self.anteversion.locate(anteversion);
+
+        let [c00, c01, c10, c11] = Atlas::corners(&self.inclination, &self.anteversion, i, j);
+
+        let c00 = self.node(c00.0, c00.1)?;
+        let c01 = self.node(c01.0, c01.1)?;
+        let c10 = self.node(c10.0, c10.1)?;
+        let c11 = self.node(c11.0, c11.1)?;
+
+        let w00 = (1f64 - r) * (1f64 - s);
+        let w01 = (1f64 - r) * s;
+        let w10 = r * (1f64 - s);
+        let w11 = r * s;
+
+        let blend = (0..self.samples)
+            .map(|index| {
+                let x = w00 * c00[index].0 + w01 * c01[index].0 + w10 * c10[index].0 + w11 * c11[index].0;
+                let y = w00 * c00[index].1 + w01 * c01[index].1 + w10 * c10[index].1 + w11 * c11[index].1;
+
+                (x, y)
+            })
+            .collect();
+
+        Some(blend)
+    }
+
+    /// contour for inclination and anteversion answered by query with error bound of the grid cell
+    pub fn contour(&self, inclination : f64, anteversion : f64, query : AtlasQuery) -> Option<AtlasContour> {
+        let vertices = match query {
+            AtlasQuery::Nearest => self.nearest(inclination, anteversion)?,
+            AtlasQuery::Blend   => self.blend(inclination, anteversion)?
+        };
+
+        let (i, _) = self.inclination.locate(inclination);
+        let (j, _) = self.anteversion.locate(anteversion);
+
+        let error = self.cell_error(i, j).map_or(f64::INFINITY, |error| error.query(query));
+
+        Some(AtlasContour{vertices : vertices, error : error})
+    }
+
+    /// hausdorff distance between contour answered by query and contour freshly computed of mesh
+    pub fn verify<V : Vector3<Val = f64>>(&self, mesh : &IndSegMesh<V>, inclination : f64, anteversion : f64, query : AtlasQuery, max : usize) -> Option<f64> {
+        let contour = self.contour(inclination, anteversion, query)?;
+        let fresh   = mesh.template_contour(inclination, anteversion, max)?;
+
+        contour::hausdorff(contour.vertices(), &fresh)
+    }
+
+    // File methods
+
+    /// read atlas from binary file written by Atlas::write_binary
+    pub fn read_binary<A : AsRef<Path>>(path : A) -> io::Result<Atlas> {
+        let bytes = fs::read(path)?;
+        let mut bytes = bytes.as_slice();
+
+        if bytes.len() < MAGIC.len() || &bytes[0..MAGIC.len()] != MAGIC {
+            let err = io::Error::new(io::ErrorKind::InvalidData, "tg.atlas.Atlas.read_binary: invalid magic bytes");
+            return Err(err)
+        }
+
+        bytes = &bytes[MAGIC.len()..];
+
+        if bytes.read_u16::<LittleEndian>()? != VERSION {
+            let err = io::Error::new(io::ErrorKind::InvalidData, "tg.atlas.Atlas.read_binary: unsupported version");
+            return Err(err)
+        }
+
+        let inclination = Atlas::read_grid(&mut bytes)?;
+        let anteversion = Atlas::read_grid(&mut bytes)?;
+        let samples     = bytes.read_u32::<LittleEndian>()? as usize;
+
+        let node_count = inclination.count().checked_mul(anteversion.count());
+        let cell_count = inclination.cells().checked_mul(anteversion.cells());
+
+        // every node needs at least its flag byte and every cell 2 errors
+        let (node_count, cell_count) = match (node_count, cell_count) {
+            (Some(node_count), Some(cell_count)) if cell_count.checked_mul(8)
+                .and_then(|size| size.checked_add(node_count))
+                .is_some_and(|size| size <= bytes.len()) => (node_count, cell_count),
+            _ => {
+                let err = io::Error::new(io::ErrorKind::InvalidData, "tg.atlas.Atlas.read_binary: invalid file size");
+                return Err(err)
+            }
+        };
+
+        let mut contours = Vec::with_capacity(node_count);
+
+        for _ in 0..node_count {
+            let contour = match bytes.read_u8()? {
+                0 => None,
+                _ => {
+                    let mut contour = Vec::with_capacity(samples.min(bytes.len() / 8));
+
+                    for _ in 0..samples {
+                        let x = bytes.read_f32::<LittleEndian>()? as f64;
+                        let y = bytes.read_f32::<LittleEndian>()? as f64;
+
+                        contour.push((x, y));
+                    }
+
+                    Some(contour)
+                }
+            };
+
+            contours.push(contour);
+        }
+
+        let mut errors = Vec::with_capacity(cell_count);
+
+        for _ in 0..cell_count {
+            let nearest = bytes.read_f32::<LittleEndian>()? as f64;
+            let blend   = bytes.read_f32::<LittleEndian>()? as f64;
+
+            errors.push(AtlasError{nearest : nearest, blend : blend});
+        }
+
+        let atlas = Atlas{
+            inclination : inclination,
+            anteversion : anteversion,
+            samples     : samples,
+            contours    : contours,
+            errors      : errors
+        };
+
+        Ok(atlas)
+    }
+
+    /// write atlas to compact binary file with vertices and errors stored as f32
+    pub fn write_binary<A : AsRef<Path>>(&self, path : A) -> io::Result<()> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.write_u16::<LittleEndian>(VERSION)?;
+
+        Atlas::write_grid(&mut bytes, &self.inclination)?;
+        Atlas::write_grid(&mut bytes, &self.anteversion)?;
+        bytes.write_u32::<LittleEndian>(self.samples as u32)?;
+
+        for contour in &self.contours {
+            match contour {
+                None => bytes.write_u8(0)?,
+                Some(contour) => {
+                    bytes.write_u8(1)?;
+
+                    for vertex in contour {
+                        bytes.write_f32::<LittleEndian>(vertex.0 as f32)?;
+                        bytes.write_f32::<LittleEndian>(vertex.1 as f32)?;
+                    }
+                }
+            }
+        }
+
+        for error in &self.errors {
+            bytes.write_f32::<LittleEndian>(error.nearest as f32)?;
+            bytes.write_f32::<LittleEndian>(error.blend as f32)?;
+        }
+
+        fs::write(path, bytes)
+    }
+
+    // Helper methods
+
+    /// resample contour and orient it counterclockwise
+    fn normalize(contour : &[Vect2<f64>], samples : usize) -> Option<Vec<Vect2<f64>>> {
+        let mut contour = contour::resample(contour, samples);
+
+        if contour.is_empty() {
+            return None
+        }
+
+        if contour::signed_area(&contour) < 0f64 {
+            contour[1..].reverse();
+        }
+
+        Some(contour)
+    }
+
+    /// already aligned contours of the grid angles before i j which neighbour it or else the nearest aligned contour
+    fn references<'a>(contours : &'a [Option<Vec<Vect2<f64>>>], anteversion : &AngleGrid, i : usize, j : usize) -> Vec<&'a [Vect2<f64>]> {
+        let count = anteversion.count();
+        let index = |ci : usize, cj : usize| ci * count + cj;
+
+        let mut neighbours = Vec::new();
+
+        if j > 0 {
+            neighbours.push(index(i, j - 1));
+        }
+
+        if i > 0 {
+            neighbours.extend((j.saturating_sub(1)..=(j + 1).min(count - 1)).map(|cj| index(i - 1, cj)));
+        }
+
+        let references = neighbours.into_iter().filter_map(|neighbour| contours[neighbour].as_deref()).collect::<Vec<_>>();
+
+        if !references.is_empty() {
+            return references
+        }
+
+        let dist = |neighbour : usize| (neighbour / count).abs_diff(i) + (neighbour % count).abs_diff(j);
+
+        (0..contours.len())
+            .filter(|neighbour| contours[*neighbour].is_some())
+            .min_by_key(|neighbour| dist(*neighbour))
+            .and_then(|neighbour| contours[neighbour].as_deref())
+            .into_iter()
+            .collect()
+    }
+
+    /// grid indices of the corners of cell with indices i j nearest to relative position r s in the cell
+    fn nearest_corners(inclination : &AngleGrid, anteversion : &AngleGrid, i : usize, j : usize, r : f64, s : f64) -> Vec<(usize, usize)> {
+        let near = |cell : usize, r : f64, count : usize| {
+            let next = (cell + 1).min(count - 1);
+
+            match r.partial_cmp(&0.5f64) {
+                Some(std::cmp::Ordering::Less)    => vec![cell],
+                Some(std::cmp::Ordering::Greater) => vec![next],
+                _ => vec![cell, next]
+            }
+        };
+
+        near(i, r, inclination.count()).into_iter()
+            .flat_map(|ci| near(j, s, anteversion.count()).into_iter().map(move |cj| (ci, cj)))
+            .collect()
+    }
+
+    /// grid indices of the 4 corners of cell with indices i j as \[(i, j), (i, j + 1), (i + 1, j), (i + 1, j + 1)\]
+    fn corners(inclination : &AngleGrid, anteversion : &AngleGrid, i : usize, j : usize) -> [(usize, usize); 4] {
+        let i1 = (i + 1).min(inclination.count() - 1);
+        let j1 = (j + 1).min(anteversion.count() - 1);
+
+        [(i, j), (i, j1), (i1, j), (i1, j1)]
+    }
+
+    fn read_grid(bytes : &mut &[u8]) -> io::Result<AngleGrid> {
+        let start = bytes.read_f64::<LittleEndian>()?;
+        let end   = bytes.read_f64::<LittleEndian>()?;
+        let count = bytes.read_u32::<LittleEndian>()? as usize;
+
+        Ok(AngleGrid::new(start, end, count))
+    }
+
+    fn write_grid(bytes : &mut Vec<u8>, grid : &AngleGrid) -> io::Result<()> {
+        bytes.write_f64::<LittleEndian>(grid.start())?;
+        bytes.write_f64::<LittleEndian>(grid.end())?;
+        bytes.write_u32::<LittleEndian>(grid.count() as u32)
+    }
+}
@@ -35,4 +35,10 @@ macro_rules! impl_cast {
 }
 
 impl_cast!(from f32, to u32);
-impl_cast!(from u32, to f32);
\ No newline at end of file
+impl_cast!(from u32, to f32);
+impl_cast!(from f32, to f64);
+impl_cast!(from f64, to f32);
+impl_cast!(from f64, to u32);
+impl_cast!(from u32, to f64);
+impl_cast!(from usize, to f32);
+impl_cast!(from usize, to f64);
@@ -1,2 +1,6 @@
+pub mod drr;
+pub mod overlay;
 pub mod rast;
-pub mod vect;
\ No newline at end of file
+pub mod render;
+pub mod vect;
+pub mod viewport;
\ No newline at end of file
@@ -0,0 +1,191 @@
+use image::{
+    ImageBuffer,
+    Luma
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::mesh::ind::{
+    IndTriMesh
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// simulated radiograph (digitally reconstructed radiograph) of closed triangle meshes
+///
+/// the point source lies on the negative y axis of the isocenter and the detector is parallel to the xz plane behind the isocenter,
+/// so that detector columns follow x and rows follow z (upwards) like the projection (x, z) of template contours
+#[derive(Clone, Copy, Debug)]
+pub struct Drr {
+    width             : u32,
+    height            : u32,
+    spacing           : f64,
+    center            : Vect<f64>,
+    source_distance   : f64,
+    detector_distance : f64,
+    attenuation       : f64
+}
+
+impl Drr {
+    /// detector with width x height pixels and spacing in millimetres per pixel
+    ///
+    /// defaults to source isocenter distance 1000 mm and isocenter detector distance 150 mm (magnification 1.15),
+    /// isocenter at origin and attenuation coefficient 0.05 per millimetre
+    pub fn new(width : u32, height : u32, spacing : f64) -> Drr {
+        Drr{
+            width             : width,
+            height            : height,
+            spacing           : spacing,
+            center            : (0f64, 0f64, 0f64),
+            source_distance   : 1000f64,
+            detector_distance : 150f64,
+            attenuation       : 0.05f64
+        }
+    }
+
+    /// isocenter which is projected to the center of the detector
+    pub fn center<V : Vector>(mut self, center : V) -> Drr
+    where V::Val : Cast<f64>
+    {
+        self.center = (center.x().cast(), center.y().cast(), center.z().cast());
+        self
+    }
+
+    /// distances of source to isocenter and isocenter to detector in millimetres
+    pub fn distances(mut self, source : f64, detector : f64) -> Drr {
+        self.source_distance   = source;
+        self.detector_distance = detector;
+        self
+    }
+
+    /// linear attenuation coefficient per millimetre
+    pub fn attenuation(mut self, attenuation : f64) -> Drr {
+        self.attenuation = attenuation;
+        self
+    }
+
+    /// magnification of objects at the isocenter on the detector
+    pub fn magnification(&self) -> f64 {
+        (self.source_distance + self.detector_distance) / self.source_distance
+    }
+
+    fn source(&self) -> Vect<f64> {
+        self.center.sub((0f64, self.source_distance, 0f64))
+    }
+
+    /// position of pixel coordinates on the detector
+    fn detector(&self, x : f64, y : f64) -> Vect<f64> {
+        let u = (x - self.width as f64 / 2f64) * self.spacing;
+        let v = (self.height as f64 / 2f64 - y) * self.spacing;
+
+        self.center.add((u, self.detector_distance, v))
+    }
+
+    /// pixel coordinates of central projection of point onto the detector or None if point is not in front of the source
+    fn project(&self, pnt : Vect<f64>) -> Option<(f64, f64)> {
+        let source = self.source();
+
+        if pnt.y() <= source.y() {
+            return None
+        }
+
+        let factor = (self.source_distance + self.detector_distance) / (pnt.y() - source.y());
+
+        let u = (pnt.x() - source.x()) * factor;
+        let v = (pnt.z() - source.z()) * factor;
+
+        Some((self.width as f64 / 2f64 + u / self.spacing, self.height as f64 / 2f64 - v / self.spacing))
+    }
+
+    /// length in millimetres of the ray through every pixel center inside the closed mesh in row major order
+    ///
+    /// every triangle adds its signed distance to the source along the rays it covers, so that exits minus entries sum up to the thickness
+    pub fn thickness<V : Vector>(&self, mesh : &IndTriMesh<V>) -> Vec<f64>
+    where V::Val : Cast<f64>
+    {
+        let mut thickness = vec![0f64; (self.width * self.height) as usize];
+
+        let source = self.source();
+
+        for tri in mesh.triangles() {
+            let pnts = tri.indices().map(|index| {
+                let vertex = &mesh.vertices()[index];
+                (vertex.x().cast(), vertex.y().cast(), vertex.z().cast())
+            });
+
+            let Some(projected) = pnts.iter().map(|pnt| self.project(*pnt)).collect::<Option<Vec<_>>>() else {
+                continue
+            };
+
+            let normal = pnts[1].sub(pnts[0]).cross(pnts[2].sub(pnts[0]));
+            let offset = normal.dot(pnts[0].sub(source));
+
+            // counterclockwise projected triangle so that shared edges are decided consistently
+            let (a, mut b, mut c) = (projected[0], projected[1], projected[2]);
+
+            let area = (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1);
+
+            if area == 0f64 {
+                continue;
+            }
+
+            if area < 0f64 {
+                std::mem::swap(&mut b, &mut c);
+            }
+
+            let min_x = a.0.min(b.0).min(c.0).floor().max(0f64) as u32;
+            let min_y = a.1.min(b.1).min(c.1).floor().max(0f64) as u32;
+            let max_x = a.0.max(b.0).max(c.0).ceil().clamp(0f64, self.width as f64) as u32;
+            let max_y = a.1.max(b.1).max(c.1).ceil().clamp(0f64, self.height as f64) as u32;
+
+            // pixel centers on an edge belong to the triangle on its left or upper side only
+            let inside = |p : (f64, f64), q : (f64, f64), x : f64, y : f64| {
+                let side = (q.0 - p.0) * (y - p.1) - (x - p.0) * (q.1 - p.1);
+                side > 0f64 || side == 0f64 && (q.1 < p.1 || q.1 == p.1 && q.0 > p.0)
+            };
+
+            for py in min_y..max_y {
+                for px in min_x..max_x {
+                    let x = px as f64 + 0.5f64;
+                    let y = py as f64 + 0.5f64;
+
+                    if !(inside(a, b, x, y) && inside(b, c, x, y) && inside(c, a, x, y)) {
+                        continue;
+                    }
+
+                    let ray   = self.detector(x, y).sub(source);
+                    let slope = normal.dot(ray);
+
+                    if slope == 0f64 {
+                        continue;
+                    }
+
+                    // distance from source to triangle plane along the ray with sign of exit (+) or entry (-)
+                    let dist = offset / slope * ray.len();
+
+                    thickness[(py * self.width + px) as usize] += dist * slope.signum();
+                }
+            }
+        }
+
+        // meshes with inward normals give negative thicknesses
+        thickness.iter().map(|val| val.abs()).collect()
+    }
+
+    /// 16 bit grayscale radiograph where attenuated pixels are bright (1 - exp(-attenuation * thickness))
+    pub fn render<V : Vector>(&self, mesh : &IndTriMesh<V>) -> ImageBuffer<Luma<u16>, Vec<u16>>
+    where V::Val : Cast<f64>
+    {
+        let thickness = self.thickness(mesh);
+
+        ImageBuffer::from_fn(self.width, self.height, |x, y| {
+            let val = 1f64 - (-self.attenuation * thickness[(y * self.width + x) as usize]).exp();
+            Luma([(val * u16::MAX as f64).round() as u16])
+        })
+    }
+}
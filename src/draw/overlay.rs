@@ -0,0 +1,223 @@
+use std::{
+    io
+};
+
+use std::convert::{
+    AsRef
+};
+
+use std::path::{
+    Path
+};
+
+use image::{
+    DynamicImage,
+    ImageBuffer,
+    Luma,
+    Rgb,
+    Rgba,
+    RgbaImage
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::draw::rast::{
+    Stroke,
+    draw_polyline_aa,
+    draw_polylines_aa,
+    fill_contours_aa
+};
+
+use crate::draw::vect::{
+    FillRule
+};
+
+use crate::draw::viewport::{
+    Viewport
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// placement of template coordinates (millimetres with y axis pointing upwards) on radiograph pixels
+#[derive(Clone, Copy, Debug)]
+pub struct Placement {
+    spacing  : f64,
+    origin   : Vect<f64>,
+    rotation : f64
+}
+
+/// color, opacity and stroke of overlay drawings
+#[derive(Clone, Copy, Debug)]
+pub struct OverlayStyle {
+    color   : Rgb<u8>,
+    opacity : f64,
+    stroke  : Stroke
+}
+
+/// shape of landmark markers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Cross,
+    Circle,
+    Dot
+}
+
+/// radiograph with template drawings composited on top
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    image : RgbaImage
+}
+
+impl Placement {
+    /// placement with spacing in millimetres per pixel (calibrated for magnification) and template origin at pixel origin
+    pub fn new<V : Vector>(spacing : f64, origin : V) -> Placement
+    where V::Val : Cast<f64>
+    {
+        Placement{spacing : spacing, origin : (origin.x().cast(), origin.y().cast()), rotation : 0f64}
+    }
+
+    /// counterclockwise rotation in radians of the template around its origin
+    pub fn rotation(mut self, rotation : f64) -> Placement {
+        self.rotation = rotation;
+        self
+    }
+
+    /// pixel position of template point
+    pub fn map<V : Vector>(&self, pnt : &V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        let (sin, cos) = self.rotation.sin_cos();
+
+        let x : f64 = pnt.x().cast();
+        let y : f64 = pnt.y().cast();
+
+        let x_rot = cos * x - sin * y;
+        let y_rot = sin * x + cos * y;
+
+        // pixel rows grow downwards
+        (self.origin.x() + x_rot / self.spacing, self.origin.y() - y_rot / self.spacing)
+    }
+}
+
+impl OverlayStyle {
+    /// opaque style with color and line width in pixels
+    pub fn new(color : Rgb<u8>, width : f64) -> OverlayStyle {
+        OverlayStyle{color : color, opacity : 1f64, stroke : Stroke::new(width)}
+    }
+
+    pub fn opacity(mut self, opacity : f64) -> OverlayStyle {
+        self.opacity = opacity.clamp(0f64, 1f64);
+        self
+    }
+
+    /// stroke with line width in pixels, caps and joins
+    pub fn stroke(mut self, stroke : Stroke) -> OverlayStyle {
+        self.stroke = stroke;
+        self
+    }
+
+    fn rgba(&self) -> Rgba<u8> {
+        let [r, g, b] = self.color.0;
+        Rgba([r, g, b, (self.opacity * 255f64).round() as u8])
+    }
+}
+
+impl Overlay {
+    /// open radiograph where 16 bit grayscale images are windowed to their minimal and maximal value
+    pub fn open<A : AsRef<Path>>(path : A) -> io::Result<Overlay> {
+        match image::open(path) {
+            Ok(image) => Ok(Overlay::from_image(&image)),
+            Err(err)  => Err(io::Error::new(io::ErrorKind::InvalidData, format!("tg.draw.overlay.Overlay.open: {}", err)))
+        }
+    }
+
+    /// overlay of radiograph where 16 bit grayscale images are windowed to their minimal and maximal value
+    pub fn from_image(image : &DynamicImage) -> Overlay {
+        match image {
+            DynamicImage::ImageLuma16(gray) => {
+                let low  = gray.pixels().map(|pixel| pixel[0]).min().unwrap_or(0);
+                let high = gray.pixels().map(|pixel| pixel[0]).max().unwrap_or(u16::MAX);
+
+                Overlay::from_gray16(gray, low, high)
+            },
+            _ => Overlay{image : image.to_rgba8()}
+        }
+    }
+
+    /// overlay of 16 bit grayscale radiograph where values from low to high are mapped to black to white
+    pub fn from_gray16(gray : &ImageBuffer<Luma<u16>, Vec<u16>>, low : u16, high : u16) -> Overlay {
+        let range = (high as f64 - low as f64).max(1f64);
+
+        let image = RgbaImage::from_fn(gray.width(), gray.height(), |x, y| {
+            let val = (gray.get_pixel(x, y)[0].clamp(low, high) - low) as f64 / range;
+            let val = (val * 255f64).round() as u8;
+
+            Rgba([val, val, val, 255u8])
+        });
+
+        Overlay{image : image}
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// draw open (closed = false) or closed template contour
+    pub fn contour<V : Vector>(mut self, contour : &[V], closed : bool, placement : &Placement, style : &OverlayStyle) -> Overlay
+    where V::Val : Cast<f64>
+    {
+        let vertices = contour.iter().map(|vertex| placement.map(vertex)).collect::<Vec<_>>();
+        let viewport = self.pixels();
+
+        draw_polyline_aa(&mut self.image, &viewport, &vertices, closed, style.stroke, style.rgba());
+        self
+    }
+
+    /// draw marker with size in pixels at template point
+    pub fn landmark<V : Vector>(mut self, pnt : &V, marker : Marker, size : f64, placement : &Placement, style : &OverlayStyle) -> Overlay
+    where V::Val : Cast<f64>
+    {
+        let (x, y)   = placement.map(pnt);
+        let half     = size / 2f64;
+        let viewport = self.pixels();
+
+        match marker {
+            Marker::Cross => {
+                let horizontal = [(x - half, y), (x + half, y)];
+                let vertical   = [(x, y - half), (x, y + half)];
+
+                draw_polylines_aa(&mut self.image, &viewport, &[(&horizontal, false), (&vertical, false)], style.stroke, style.rgba());
+            },
+            Marker::Circle => {
+                let circle = Stroke::circle((x, y), half);
+                draw_polyline_aa(&mut self.image, &viewport, &circle, true, style.stroke, style.rgba());
+            },
+            Marker::Dot => {
+                let circle = Stroke::circle((x, y), half);
+                fill_contours_aa(&mut self.image, &viewport, &[&circle], FillRule::NonZero, style.rgba());
+            }
+        }
+
+        self
+    }
+
+    /// viewport of placed pixel coordinates
+    fn pixels(&self) -> Viewport {
+        Viewport::pixels(self.image.width(), self.image.height())
+    }
+
+    /// write composited image where the format is chosen by the file extension
+    pub fn save<A : AsRef<Path>>(&self, path : A) -> io::Result<()> {
+        let image = DynamicImage::ImageRgba8(self.image.clone()).to_rgb8();
+
+        match image.save(path) {
+            Ok(())   => Ok(()),
+            Err(err) => Err(io::Error::other(format!("tg.draw.overlay.Overlay.save: {}", err)))
+        }
+    }
+}
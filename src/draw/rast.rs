@@ -1,29 +1,49 @@
 use image::{
+    ImageBuffer,
+    Pixel,
     Rgb,
-    RgbImage
+    RgbImage,
+    Rgba
 };
 
 use crate::conv::{
     Cast
 };
 
+use crate::draw::vect::{
+    FillRule
+};
+
+use crate::draw::viewport::{
+    Viewport
+};
+
+use crate::geom::d2::contour;
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
 use crate::geom::d2::prim::PRect;
 use crate::geom::d2::shape::d2::{
-    Bounds, 
     Shape
 };
 
+use crate::geom::d2::prim::ellipse::{
+    steps
+};
+
 use crate::geom::d2::prim::seg::{
     Segment 
 };
 
 use crate::geom::d2::prim::vect::{
+    Vect,
     Vector 
 };
 
 use crate::num::{
     Zero,
-    One,
     Two,
     Float
 };
@@ -46,22 +66,21 @@ pub fn horizontal_flip(image : &mut RgbImage) {
     }
 }
 
-pub fn fill_shape_float<Sh : Shape, Func : Fn(<Sh::Vect as Vector>::Own) -> Rgb<u8>>(image : &mut RgbImage, shape : Sh, color : Func) 
-where Sh::Val : One + Two + Float + Cast<u32>,
-      u32     : Cast<Sh::Val>
+/// fill shape in world coordinates of viewport by testing the pixel centers inside of its bounds
+pub fn fill_shape_float<Sh : Shape, Func : Fn(<Sh::Vect as Vector>::Own) -> Rgb<u8>>(image : &mut RgbImage, viewport : &Viewport, shape : Sh, color : Func) 
+where Sh::Val : Cast<f64>,
+      f64     : Cast<Sh::Val>
 {   
-    let half = Sh::Val::one() / Sh::Val::two();
-
-    let im_bounds = Bounds::with_zero_unchecked(image.dimensions());
-    let sh_bounds = shape.bounds().vmap::<(u32, u32), _>(|val| val.cast());
+    let (width, height) = image.dimensions();
 
-    let bounds = im_bounds.clamp(sh_bounds);
+    let bounds = viewport.pixel_bounds(shape.bounds());
     let start = bounds.start();
     let end   = bounds.end();
 
-    for x in start.x()..end.x() {
-        for y in start.y()..end.y() {
-            let point = <Sh::Vect as Vector>::of((u32::cast(x), u32::cast(y))).vadd(half);
+    for x in start.x()..Ord::min(end.x(), width) {
+        for y in start.y()..Ord::min(end.y(), height) {
+            let (world_x, world_y) = viewport.unmap((x as f64 + 0.5f64, y as f64 + 0.5f64));
+            let point = <Sh::Vect as Vector>::of((world_x.cast(), world_y.cast()));
 
             if shape.contains(&point) {
                 let pixel = color(point);
@@ -71,9 +90,10 @@ where Sh::Val : One + Two + Float + Cast<u32>,
     }
 }
 
-pub fn draw_seg_float<Seg : Segment, Func : Fn(<Seg::Vect as Vector>::Own) -> Rgb<u8>>(image : &mut RgbImage, seg : Seg, color : Func, width : Seg::Val)
-where Seg::Val  : Zero + One + Two + Float + Cast<u32> + HPOrd,
-      u32       : Cast<Seg::Val>
+/// draw segment in world coordinates of viewport as rectangle with width in world units
+pub fn draw_seg_float<Seg : Segment, Func : Fn(<Seg::Vect as Vector>::Own) -> Rgb<u8>>(image : &mut RgbImage, viewport : &Viewport, seg : Seg, color : Func, width : Seg::Val)
+where Seg::Val  : Zero + Two + Float + Cast<f64> + HPOrd,
+      f64       : Cast<Seg::Val>
 {
     let half_width = width / Seg::Val::two();
 
@@ -92,5 +112,523 @@ where Seg::Val  : Zero + One + Two + Float + Cast<u32> + HPOrd,
     let d = seg_a.add(&offset_r);
 
     let rect = PRect::new_unchecked(a, b, c, d);
-    fill_shape_float(image, rect, color);
-}
\ No newline at end of file
+    fill_shape_float(image, viewport, rect, color);
+}
+
+/// polygon edge prepared for scanline filling
+#[derive(Clone, Copy)]
+struct ScanEdge {
+    /// first row whose pixel centers are crossed by the edge
+    row_start : u32,
+    /// first row after the edge
+    row_end   : u32,
+    /// endpoint with smaller y coordinate
+    low       : Vect<f64>,
+    /// change of x per unit of y
+    slope     : f64,
+    /// +1 for downward edges and -1 for upward edges
+    winding   : i32
+}
+
+impl ScanEdge {
+    /// edge between a and b or None if it crosses no row of pixel centers below height
+    fn new(a : Vect<f64>, b : Vect<f64>, height : u32) -> Option<ScanEdge> {
+        if a.y() == b.y() {
+            return None
+        }
+
+        let (low, high, winding) = match a.y() < b.y() {
+            true  => (a, b, 1),
+            false => (b, a, -1)
+        };
+
+        // rows whose centers y + 0.5 lie in [low.y, high.y) (top-left convention)
+        let row_start = (low.y() - 0.5f64).ceil().max(0f64);
+        let row_end   = (high.y() - 0.5f64).ceil().clamp(0f64, height as f64);
+
+        if row_start >= row_end {
+            return None
+        }
+
+        let slope = (high.x() - low.x()) / (high.y() - low.y());
+
+        Some(ScanEdge{row_start : row_start as u32, row_end : row_end as u32, low : low, slope : slope, winding : winding})
+    }
+
+    /// x coordinate of edge at center of row
+    fn x(&self, row : u32) -> f64 {
+        self.low.x() + (row as f64 + 0.5f64 - self.low.y()) * self.slope
+    }
+}
+
+/// fill closed contours (e.g. outer contour and holes) with scanlines at pixel centers
+///
+/// pixels are filled if their center is inside where centers on left and top edges count as inside and centers on right and bottom edges not,
+/// so adjacent polygons neither overlap nor leave gaps
+pub fn fill_contours_float<V : Vector, Func : Fn(Vect<f64>) -> Rgb<u8>>(image : &mut RgbImage, viewport : &Viewport, contours : &[&[V]], rule : FillRule, color : Func)
+where V::Val : Cast<f64>
+{
+    let (width, height) = image.dimensions();
+
+    let mut edges = Vec::new();
+
+    for contour in contours {
+        let len = contour.len();
+
+        for index in 0..len {
+            let a = viewport.map(&contour[index]);
+            let b = viewport.map(&contour[(index + 1) % len]);
+
+            edges.extend(ScanEdge::new(a, b, height));
+        }
+    }
+
+    edges.sort_by_key(|edge| edge.row_start);
+
+    let mut active : Vec<ScanEdge> = Vec::new();
+    let mut next   = 0;
+
+    let mut crossings = Vec::new();
+
+    let row_start = edges.first().map_or(0, |edge| edge.row_start);
+
+    for row in row_start..height {
+        active.retain(|edge| edge.row_end > row);
+
+        while next < edges.len() && edges[next].row_start <= row {
+            active.push(edges[next]);
+            next += 1;
+        }
+
+        if active.is_empty() {
+            if next == edges.len() {
+                break;
+            }
+
+            continue;
+        }
+
+        crossings.clear();
+        crossings.extend(active.iter().map(|edge| (edge.x(row), edge.winding)));
+        crossings.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut winding = 0;
+
+        for index in 0..crossings.len() - 1 {
+            winding = match rule {
+                FillRule::NonZero => winding + crossings[index].1,
+                FillRule::EvenOdd => 1 - winding
+            };
+
+            if winding == 0 {
+                continue;
+            }
+
+            // pixels whose centers x + 0.5 lie in [left, right)
+            let start = (crossings[index].0 - 0.5f64).ceil().clamp(0f64, width as f64) as u32;
+            let end   = (crossings[index + 1].0 - 0.5f64).ceil().clamp(0f64, width as f64) as u32;
+
+            for x in start..end {
+                let pixel = color(viewport.unmap((x as f64 + 0.5f64, row as f64 + 0.5f64)));
+                image.put_pixel(x, row, pixel);
+            }
+        }
+    }
+}
+
+/// fill polygon with scanlines (see fill_contours_float)
+pub fn fill_poly_float<V : Vector, Func : Fn(Vect<f64>) -> Rgb<u8>>(image : &mut RgbImage, viewport : &Viewport, poly : &Poly<V>, rule : FillRule, color : Func)
+where V::Val : Cast<f64>
+{
+    fill_contours_float(image, viewport, &[poly.vertices()], rule, color)
+}
+
+/// fill polygon with holes with scanlines (see fill_contours_float)
+pub fn fill_poly_with_holes_float<V : Vector, Func : Fn(Vect<f64>) -> Rgb<u8>>(image : &mut RgbImage, viewport : &Viewport, outer : &[V], holes : &[&[V]], rule : FillRule, color : Func)
+where V::Val : Cast<f64>
+{
+    let mut contours = vec![outer];
+    contours.extend(holes.iter().copied());
+
+    fill_contours_float(image, viewport, &contours, rule, color)
+}
+
+/// number of sub scanlines per pixel row of anti-aliased filling
+const AA_SUBSAMPLES : u32 = 16;
+
+/// maximal deviation in pixels of tessellated round caps and joins
+const AA_ROUND_TOLERANCE : f64 = 0.05;
+
+/// shape of open polyline ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cap {
+    /// end exactly at the endpoints
+    Butt,
+    /// extend by half of stroke width with half circle
+    Round,
+    /// extend by half of stroke width with rectangle
+    Square
+}
+
+/// shape of corners between consecutive polyline segments
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Join {
+    /// extend outer edges until they meet unless the miter limit is exceeded (bevel otherwise)
+    Miter,
+    /// circle around corner
+    Round,
+    /// connect outer edges with straight line
+    Bevel
+}
+
+/// stroke parameters of anti-aliased lines in pixels
+#[derive(Clone, Copy, Debug)]
+pub struct Stroke {
+    width       : f64,
+    cap         : Cap,
+    join        : Join,
+    miter_limit : f64
+}
+
+/// pixel that can be blended with a color with coverage alpha
+pub trait Blend {
+    fn mix(&mut self, color : Rgba<u8>, alpha : f64);
+}
+
+impl Blend for Rgb<u8> {
+    fn mix(&mut self, color : Rgba<u8>, alpha : f64) {
+        let alpha = (alpha * color[3] as f64 / 255f64).clamp(0f64, 1f64);
+
+        for channel in 0..3 {
+            let val = self[channel] as f64 * (1f64 - alpha) + color[channel] as f64 * alpha;
+            self[channel] = val.round() as u8;
+        }
+    }
+}
+
+impl Blend for Rgba<u8> {
+    /// source over compositing with straight (not premultiplied) alpha
+    fn mix(&mut self, color : Rgba<u8>, alpha : f64) {
+        let src = (alpha * color[3] as f64 / 255f64).clamp(0f64, 1f64);
+        let dst = self[3] as f64 / 255f64;
+        let out = src + dst * (1f64 - src);
+
+        if out <= 0f64 {
+            return
+        }
+
+        for channel in 0..3 {
+            let val = (color[channel] as f64 * src + self[channel] as f64 * dst * (1f64 - src)) / out;
+            self[channel] = val.round() as u8;
+        }
+
+        self[3] = (out * 255f64).round() as u8;
+    }
+}
+
+impl Stroke {
+    /// stroke with butt caps and miter joins with miter limit 4
+    pub fn new(width : f64) -> Stroke {
+        Stroke{width : width, cap : Cap::Butt, join : Join::Miter, miter_limit : 4f64}
+    }
+
+    pub fn cap(mut self, cap : Cap) -> Stroke {
+        self.cap = cap;
+        self
+    }
+
+    pub fn join(mut self, join : Join) -> Stroke {
+        self.join = join;
+        self
+    }
+
+    /// maximal ratio of miter length to half stroke width before miter joins become bevel joins
+    pub fn miter_limit(mut self, miter_limit : f64) -> Stroke {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// counterclockwise polygons whose non-zero union is the stroke of polyline
+    fn outline(&self, vertices : &[Vect<f64>], closed : bool) -> Vec<Vec<Vect<f64>>> {
+        let half = self.width / 2f64;
+
+        let mut pieces = Vec::new();
+
+        // drop consecutive duplicates that have no direction
+        let mut pnts : Vec<Vect<f64>> = Vec::with_capacity(vertices.len());
+
+        for vertex in vertices {
+            if pnts.last().is_none_or(|last| !last.equal(vertex)) {
+                pnts.push(*vertex);
+            }
+        }
+
+        if closed && pnts.len() > 1 && pnts[0].equal(pnts[pnts.len() - 1]) {
+            pnts.pop();
+        }
+
+        if half <= 0f64 || pnts.is_empty() {
+            return pieces
+        }
+
+        if pnts.len() == 1 {
+            if self.cap == Cap::Round {
+                pieces.push(Stroke::circle(pnts[0], half));
+            }
+
+            if self.cap == Cap::Square {
+                let (x, y) = pnts[0];
+                pieces.push(vec![(x - half, y - half), (x + half, y - half), (x + half, y + half), (x - half, y + half)]);
+            }
+
+            return pieces
+        }
+
+        let count = if closed { pnts.len() } else { pnts.len() - 1 };
+
+        for index in 0..count {
+            let mut a = pnts[index];
+            let mut b = pnts[(index + 1) % pnts.len()];
+
+            let dir = b.sub(a).vdiv(b.sub(a).len());
+            let nrm = dir.orth_l().vmul(half);
+
+            if !closed && self.cap == Cap::Square {
+                if index == 0 {
+                    a = a.sub(dir.vmul(half));
+                }
+
+                if index == count - 1 {
+                    b = b.add(dir.vmul(half));
+                }
+            }
+
+            pieces.push(vec![a.add(nrm), b.add(nrm), b.sub(nrm), a.sub(nrm)]);
+        }
+
+        if !closed && self.cap == Cap::Round {
+            pieces.push(Stroke::circle(pnts[0], half));
+            pieces.push(Stroke::circle(pnts[pnts.len() - 1], half));
+        }
+
+        let corners = if closed { 0..pnts.len() } else { 1..pnts.len() - 1 };
+
+        for index in corners {
+            let prev = pnts[(index + pnts.len() - 1) % pnts.len()];
+            let pnt  = pnts[index];
+            let next = pnts[(index + 1) % pnts.len()];
+
+            pieces.extend(self.corner(prev, pnt, next, half));
+        }
+
+        for piece in pieces.iter_mut() {
+            if contour::signed_area(piece) < 0f64 {
+                piece.reverse();
+            }
+        }
+
+        pieces
+    }
+
+    /// join polygon at pnt between segments prev-pnt and pnt-next
+    fn corner(&self, prev : Vect<f64>, pnt : Vect<f64>, next : Vect<f64>, half : f64) -> Option<Vec<Vect<f64>>> {
+        if self.join == Join::Round {
+            return Some(Stroke::circle(pnt, half))
+        }
+
+        let dir_a = pnt.sub(prev).vdiv(pnt.sub(prev).len());
+        let dir_b = next.sub(pnt).vdiv(next.sub(pnt).len());
+
+        let cross = dir_a.det(dir_b);
+
+        if cross == 0f64 {
+            return None
+        }
+
+        // normals on outer side of corner
+        let (nrm_a, nrm_b) = match cross > 0f64 {
+            true  => (dir_a.orth_r(), dir_b.orth_r()),
+            false => (dir_a.orth_l(), dir_b.orth_l())
+        };
+
+        let a = pnt.add(nrm_a.vmul(half));
+        let b = pnt.add(nrm_b.vmul(half));
+
+        let sum = nrm_a.add(nrm_b);
+        let len = sum.len();
+
+        // ratio of miter length to half stroke width is 1 / cos(angle / 2) = 2 / |nrm_a + nrm_b|
+        if self.join == Join::Miter && len > 0f64 && 2f64 / len <= self.miter_limit {
+            let miter = pnt.add(sum.vmul(2f64 * half / (len * len)));
+            return Some(vec![pnt, a, miter, b])
+        }
+
+        Some(vec![pnt, a, b])
+    }
+
+    /// polygon of circle whose chords deviate at most AA_ROUND_TOLERANCE from the circle
+    pub(crate) fn circle(center : Vect<f64>, radius : f64) -> Vec<Vect<f64>> {
+        let steps = steps(radius, AA_ROUND_TOLERANCE, 2f64 * std::f64::consts::PI, 8);
+
+        (0..steps)
+            .map(|index| {
+                let angle = 2f64 * std::f64::consts::PI * index as f64 / steps as f64;
+                (center.x() + radius * angle.cos(), center.y() + radius * angle.sin())
+            })
+            .collect()
+    }
+}
+
+/// fill closed contours anti-aliased by blending color with the exact horizontal coverage of sub scanlines
+pub fn fill_contours_aa<V : Vector, P : Pixel<Subpixel = u8> + Blend>(image : &mut ImageBuffer<P, Vec<u8>>, viewport : &Viewport, contours : &[&[V]], rule : FillRule, color : Rgba<u8>)
+where V::Val : Cast<f64>
+{
+    let (width, height) = image.dimensions();
+
+    // (low, high, slope, winding) of non horizontal edges
+    let mut edges = Vec::new();
+
+    for contour in contours {
+        let len = contour.len();
+
+        for index in 0..len {
+            let a = viewport.map(&contour[index]);
+            let b = viewport.map(&contour[(index + 1) % len]);
+
+            if a.y() == b.y() {
+                continue;
+            }
+
+            let (low, high, winding) = match a.y() < b.y() {
+                true  => (a, b, 1),
+                false => (b, a, -1)
+            };
+
+            edges.push((low, high, (high.x() - low.x()) / (high.y() - low.y()), winding));
+        }
+    }
+
+    if edges.is_empty() {
+        return
+    }
+
+    let min_y = edges.iter().map(|(low, _, _, _)| low.y()).fold(f64::INFINITY, f64::min);
+    let max_y = edges.iter().map(|(_, high, _, _)| high.y()).fold(f64::NEG_INFINITY, f64::max);
+
+    let row_start = min_y.floor().clamp(0f64, height as f64) as u32;
+    let row_end   = max_y.ceil().clamp(0f64, height as f64) as u32;
+
+    let weight = 1f64 / AA_SUBSAMPLES as f64;
+
+    // partial coverage of pixels and differences of full coverage of following pixels
+    let mut coverage = vec![0f64; width as usize + 1];
+    let mut full     = vec![0f64; width as usize + 1];
+
+    let mut crossings = Vec::new();
+
+    for row in row_start..row_end {
+        let active = edges.iter()
+            .filter(|(low, high, _, _)| low.y() < (row + 1) as f64 && high.y() > row as f64)
+            .collect::<Vec<_>>();
+
+        for sub in 0..AA_SUBSAMPLES {
+            let y = row as f64 + (sub as f64 + 0.5f64) * weight;
+
+            crossings.clear();
+            crossings.extend(active.iter()
+                .filter(|(low, high, _, _)| low.y() <= y && y < high.y())
+                .map(|(low, _, slope, winding)| (low.x() + (y - low.y()) * slope, *winding)));
+            crossings.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            let mut winding = 0;
+
+            for index in 0..crossings.len().saturating_sub(1) {
+                winding = match rule {
+                    FillRule::NonZero => winding + crossings[index].1,
+                    FillRule::EvenOdd => 1 - winding
+                };
+
+                if winding == 0 {
+                    continue;
+                }
+
+                let left  = crossings[index].0.clamp(0f64, width as f64);
+                let right = crossings[index + 1].0.clamp(0f64, width as f64);
+
+                if left >= right {
+                    continue;
+                }
+
+                let left_pixel  = left.floor() as usize;
+                let right_pixel = right.floor() as usize;
+
+                if left_pixel == right_pixel {
+                    coverage[left_pixel] += (right - left) * weight;
+                    continue;
+                }
+
+                coverage[left_pixel]  += ((left_pixel + 1) as f64 - left) * weight;
+                coverage[right_pixel] += (right - right_pixel as f64) * weight;
+
+                full[left_pixel + 1] += weight;
+                full[right_pixel]    -= weight;
+            }
+        }
+
+        let mut sum = 0f64;
+
+        for x in 0..width as usize {
+            sum += full[x];
+
+            let alpha = (coverage[x] + sum).min(1f64);
+
+            if alpha > 0f64 {
+                image.get_pixel_mut(x as u32, row).mix(color, alpha);
+            }
+
+            coverage[x] = 0f64;
+            full[x]     = 0f64;
+        }
+
+        coverage[width as usize] = 0f64;
+        full[width as usize]     = 0f64;
+    }
+}
+
+/// fill polygon anti-aliased (see fill_contours_aa)
+pub fn fill_poly_aa<V : Vector, P : Pixel<Subpixel = u8> + Blend>(image : &mut ImageBuffer<P, Vec<u8>>, viewport : &Viewport, poly : &Poly<V>, rule : FillRule, color : Rgba<u8>)
+where V::Val : Cast<f64>
+{
+    fill_contours_aa(image, viewport, &[poly.vertices()], rule, color)
+}
+
+/// draw open (closed = false) or closed polylines anti-aliased with stroke width in pixels, caps and joins where overlaps are blended once
+pub fn draw_polylines_aa<V : Vector, P : Pixel<Subpixel = u8> + Blend>(image : &mut ImageBuffer<P, Vec<u8>>, viewport : &Viewport, polylines : &[(&[V], bool)], stroke : Stroke, color : Rgba<u8>)
+where V::Val : Cast<f64>
+{
+    let mut pieces = Vec::new();
+
+    for (vertices, closed) in polylines {
+        let vertices = vertices.iter().map(|vertex| viewport.map(vertex)).collect::<Vec<Vect<f64>>>();
+        pieces.extend(stroke.outline(&vertices, *closed));
+    }
+
+    let pieces = pieces.iter().map(|piece| piece.as_slice()).collect::<Vec<_>>();
+
+    // overlapping pieces of the same orientation are blended once with the non-zero rule
+    fill_contours_aa(image, &Viewport::pixels(image.width(), image.height()), &pieces, FillRule::NonZero, color)
+}
+
+/// draw open (closed = false) or closed polyline anti-aliased with stroke width in pixels, caps and joins
+pub fn draw_polyline_aa<V : Vector, P : Pixel<Subpixel = u8> + Blend>(image : &mut ImageBuffer<P, Vec<u8>>, viewport : &Viewport, vertices : &[V], closed : bool, stroke : Stroke, color : Rgba<u8>)
+where V::Val : Cast<f64>
+{
+    draw_polylines_aa(image, viewport, &[(vertices, closed)], stroke, color)
+}
+
+/// draw segment anti-aliased with stroke width in pixels and caps
+pub fn draw_seg_aa<Seg : Segment, P : Pixel<Subpixel = u8> + Blend>(image : &mut ImageBuffer<P, Vec<u8>>, viewport : &Viewport, seg : Seg, stroke : Stroke, color : Rgba<u8>)
+where Seg::Val : Cast<f64>
+{
+    draw_polyline_aa(image, viewport, &seg.pnts(), false, stroke, color)
+}
@@ -0,0 +1,430 @@
+use std::{
+    io
+};
+
+use std::convert::{
+    AsRef
+};
+
+use std::path::{
+    Path
+};
+
+use image::{
+    Rgb,
+    RgbImage
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::mesh::ind::{
+    IndSegMesh,
+    IndTriMesh
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// distance of the near plane of perspective cameras in world units
+const NEAR : f64 = 1e-3;
+
+/// up directions with sines of angles to the view direction below this are treated as parallel
+const PARALLEL : f64 = 1e-9;
+
+/// projection of camera space onto the image plane
+#[derive(Clone, Copy, Debug)]
+pub enum Projection {
+    /// parallel projection where height is the visible world height
+    Orthographic{height : f64},
+    /// central projection where fov is the vertical field of view in radians
+    Perspective{fov : f64}
+}
+
+/// interpolation of lambert shading over triangles
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shading {
+    /// one intensity per triangle from its normal
+    Flat,
+    /// intensities of vertex normals interpolated over triangle
+    Gouraud
+}
+
+/// camera looking from eye at target with up direction
+#[derive(Clone, Copy, Debug)]
+pub struct Camera {
+    eye        : Vect<f64>,
+    right      : Vect<f64>,
+    up         : Vect<f64>,
+    forward    : Vect<f64>,
+    projection : Projection
+}
+
+/// depth buffered rendering of 3D meshes into rgb image
+pub struct Render {
+    image   : RgbImage,
+    depth   : Vec<f64>,
+    camera  : Camera,
+    light   : Vect<f64>,
+    ambient : f64
+}
+
+/// vertex projected into pixel space with depth key that is linear in pixel space and smaller for nearer points
+#[derive(Clone, Copy)]
+struct Projected {
+    x   : f64,
+    y   : f64,
+    key : f64
+}
+
+impl Camera {
+    /// create new Camera where an up direction parallel to the view direction is replaced by a coordinate axis
+    pub fn new<V : Vector>(eye : V, target : V, up : V, projection : Projection) -> Camera
+    where V::Val : Cast<f64>
+    {
+        let eye    = Camera::vect(&eye);
+        let target = Camera::vect(&target);
+        let up     = Camera::vect(&up);
+
+        let forward = target.sub(eye).norm();
+        let right   = forward.cross(up);
+
+        // up parallel to view direction falls back to the axis least aligned with the view direction
+        let right = match right.len() > PARALLEL * up.len() {
+            true  => right.norm(),
+            false => {
+                let axis = match (forward.0.abs(), forward.1.abs(), forward.2.abs()) {
+                    (x, y, z) if x <= y && x <= z => (1f64, 0f64, 0f64),
+                    (_, y, z) if y <= z => (0f64, 1f64, 0f64),
+                    _ => (0f64, 0f64, 1f64)
+                };
+
+                forward.cross(axis).norm()
+            }
+        };
+
+        let up = right.cross(forward);
+
+        Camera{eye : eye, right : right, up : up, forward : forward, projection : projection}
+    }
+
+    pub fn orthographic<V : Vector>(eye : V, target : V, up : V, height : f64) -> Camera
+    where V::Val : Cast<f64>
+    {
+        Camera::new(eye, target, up, Projection::Orthographic{height : height})
+    }
+
+    pub fn perspective<V : Vector>(eye : V, target : V, up : V, fov : f64) -> Camera
+    where V::Val : Cast<f64>
+    {
+        Camera::new(eye, target, up, Projection::Perspective{fov : fov})
+    }
+
+    /// orthographic camera looking in direction at vertices so that they fill an image with aspect ratio width / height
+    ///
+    /// None if there are no vertices or direction has zero length
+    pub fn fit<V : Vector, D : Vector>(vertices : &[V], direction : D, up : D, aspect : f64, margin : f64) -> Option<Camera>
+    where V::Val : Cast<f64>,
+          D::Val : Cast<f64>
+    {
+        let direction = Camera::vect(&direction);
+        let up_vect   = Camera::vect(&up);
+
+        if direction.len() == 0f64 {
+            return None
+        }
+
+        let direction = direction.norm();
+
+        let first = Camera::vect(vertices.first()?);
+
+        let (min, max) = vertices.iter().map(Camera::vect).fold((first, first), |(min, max), vertex| {
+            ((min.0.min(vertex.0), min.1.min(vertex.1), min.2.min(vertex.2)), (max.0.max(vertex.0), max.1.max(vertex.1), max.2.max(vertex.2)))
+        });
+
+        let center = min.add(max).vmul(0.5f64);
+        let radius = max.sub(min).len() / 2f64;
+
+        let eye    = center.sub(direction.vmul(2f64 * radius + 1f64));
+        let camera = Camera::orthographic(eye, center, up_vect, 1f64);
+
+        // extent of vertices on image plane
+        let (width, height) = vertices.iter().fold((0f64, 0f64), |(width, height), vertex| {
+            let offset = Camera::vect(vertex).sub(center);
+            (width.max(2f64 * offset.dot(camera.right).abs()), height.max(2f64 * offset.dot(camera.up).abs()))
+        });
+
+        let height = height.max(width / aspect) * (1f64 + 2f64 * margin);
+
+        Some(Camera{projection : Projection::Orthographic{height : height.max(f64::EPSILON)}, ..camera})
+    }
+
+    /// direction from point towards camera
+    fn towards(&self, pnt : Vect<f64>) -> Vect<f64> {
+        match self.projection {
+            Projection::Orthographic{..} => self.forward.vmul(-1f64),
+            Projection::Perspective{..}  => self.eye.sub(pnt).norm()
+        }
+    }
+
+    /// clip polygon of points with interpolated values against the near plane of a perspective camera
+    fn clip_near(&self, polygon : &[(Vect<f64>, f64)]) -> Vec<(Vect<f64>, f64)> {
+        if let Projection::Orthographic{..} = self.projection {
+            return polygon.to_vec()
+        }
+
+        let depth = |pnt : Vect<f64>| pnt.sub(self.eye).dot(self.forward) - NEAR;
+
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+        for (index, (p, p_val)) in polygon.iter().enumerate() {
+            let (q, q_val) = polygon[(index + 1) % polygon.len()];
+            let (p_depth, q_depth) = (depth(*p), depth(q));
+
+            if p_depth >= 0f64 {
+                clipped.push((*p, *p_val));
+            }
+
+            if (p_depth >= 0f64) != (q_depth >= 0f64) {
+                let t = p_depth / (p_depth - q_depth);
+                clipped.push((p.add(q.sub(*p).vmul(t)), p_val + (q_val - p_val) * t));
+            }
+        }
+
+        clipped
+    }
+
+    /// pixel position and depth key of point or None if point is behind perspective camera
+    fn project(&self, pnt : Vect<f64>, width : u32, height : u32) -> Option<Projected> {
+        let rel = pnt.sub(self.eye);
+
+        let x = rel.dot(self.right);
+        let y = rel.dot(self.up);
+        let z = rel.dot(self.forward);
+
+        let (scale, key) = match self.projection {
+            Projection::Orthographic{height : world} => (height as f64 / world, z),
+            Projection::Perspective{fov} => {
+                if z <= f64::EPSILON {
+                    return None
+                }
+
+                (height as f64 / 2f64 / (fov / 2f64).tan() / z, -1f64 / z)
+            }
+        };
+
+        Some(Projected{x : width as f64 / 2f64 + x * scale, y : height as f64 / 2f64 - y * scale, key : key})
+    }
+
+    fn vect<V : Vector>(vect : &V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        (vect.x().cast(), vect.y().cast(), vect.z().cast())
+    }
+}
+
+impl Render {
+    /// empty render with background color and light from direction (pointing towards the light)
+    pub fn new<V : Vector>(width : u32, height : u32, camera : Camera, light : V, background : Rgb<u8>) -> Render
+    where V::Val : Cast<f64>
+    {
+        let image = RgbImage::from_pixel(width, height, background);
+        let depth = vec![f64::INFINITY; (width * height) as usize];
+
+        Render{image : image, depth : depth, camera : camera, light : Camera::vect(&light).norm(), ambient : 0.2f64}
+    }
+
+    /// intensity of unlit surfaces between 0 and 1
+    pub fn ambient(mut self, ambient : f64) -> Render {
+        self.ambient = ambient.clamp(0f64, 1f64);
+        self
+    }
+
+    pub fn image(&self) -> &RgbImage {
+        &self.image
+    }
+
+    pub fn into_image(self) -> RgbImage {
+        self.image
+    }
+
+    pub fn save<A : AsRef<Path>>(&self, path : A) -> io::Result<()> {
+        match self.image.save(path) {
+            Ok(())   => Ok(()),
+            Err(err) => Err(io::Error::other(format!("tg.draw.render.Render.save: {}", err)))
+        }
+    }
+
+    /// render triangles with two sided lambert shading and depth test
+    pub fn mesh<V : Vector>(mut self, mesh : &IndTriMesh<V>, color : Rgb<u8>, shading : Shading) -> Render
+    where V::Val : Cast<f64>
+    {
+        let (width, height) = self.image.dimensions();
+
+        let vertices = mesh.vertices().iter().map(Camera::vect).collect::<Vec<_>>();
+        let mesh     = IndTriMesh::new_unchecked(vertices, mesh.triangles().clone());
+
+        let vertex_normals = match shading {
+            Shading::Flat    => Vec::new(),
+            Shading::Gouraud => mesh.vertex_normals()
+        };
+
+        for tri in mesh.triangles() {
+            let pnts = mesh.triangle(tri);
+
+            let intensities = match shading {
+                Shading::Flat => {
+                    let normal = mesh.area_normal(tri);
+
+                    if normal.len() == 0f64 {
+                        continue;
+                    }
+
+                    let center    = pnts[0].add(pnts[1]).add(pnts[2]).vmul(1f64 / 3f64);
+                    let intensity = self.intensity(normal.norm(), center);
+
+                    [intensity; 3]
+                },
+                Shading::Gouraud => {
+                    let [a, b, c] = tri.indices();
+
+                    [
+                        self.intensity(vertex_normals[a], pnts[0]),
+                        self.intensity(vertex_normals[b], pnts[1]),
+                        self.intensity(vertex_normals[c], pnts[2])
+                    ]
+                }
+            };
+
+            // triangles crossing the near plane become quads
+            let polygon = self.camera.clip_near(&[(pnts[0], intensities[0]), (pnts[1], intensities[1]), (pnts[2], intensities[2])]);
+
+            let Some(projected) = polygon.iter().map(|(pnt, _)| self.camera.project(*pnt, width, height)).collect::<Option<Vec<_>>>() else {
+                continue
+            };
+
+            for index in 2..projected.len() {
+                let intensities = [polygon[0].1, polygon[index - 1].1, polygon[index].1];
+                self.fill_triangle([projected[0], projected[index - 1], projected[index]], intensities, color);
+            }
+        }
+
+        self
+    }
+
+    /// draw segments that are not hidden by rendered triangles
+    pub fn wireframe<V : Vector>(mut self, mesh : &IndSegMesh<V>, color : Rgb<u8>) -> Render
+    where V::Val : Cast<f64>
+    {
+        let (width, height) = self.image.dimensions();
+
+        for segment in mesh.segments() {
+            let a = Camera::vect(&mesh.vertices()[segment.a()]);
+            let b = Camera::vect(&mesh.vertices()[segment.b()]);
+
+            // a segment is a degenerate polygon whose clipped points with the smallest and largest parameter bound its visible part
+            let clipped = self.camera.clip_near(&[(a, 0f64), (b, 1f64)]);
+
+            let first = clipped.iter().min_by(|p, q| p.1.total_cmp(&q.1));
+            let last  = clipped.iter().max_by(|p, q| p.1.total_cmp(&q.1));
+
+            let (Some((a, _)), Some((b, _))) = (first, last) else {
+                continue
+            };
+
+            let a = self.camera.project(*a, width, height);
+            let b = self.camera.project(*b, width, height);
+
+            let (Some(a), Some(b)) = (a, b) else {
+                continue
+            };
+
+            let steps = (b.x - a.x).abs().max((b.y - a.y).abs()).ceil().max(1f64) as usize;
+
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+
+                let x   = a.x + (b.x - a.x) * t;
+                let y   = a.y + (b.y - a.y) * t;
+                let key = a.key + (b.key - a.key) * t;
+
+                if x < 0f64 || y < 0f64 || x >= width as f64 || y >= height as f64 {
+                    continue;
+                }
+
+                let (px, py) = (x as u32, y as u32);
+                let index    = (py * width + px) as usize;
+
+                // edges lie on the surface of their triangles
+                let bias = 1e-3f64 * key.abs();
+
+                if key <= self.depth[index] + bias {
+                    self.image.put_pixel(px, py, color);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// lambert intensity of surface with normal at pnt that is turned towards the camera
+    fn intensity(&self, normal : Vect<f64>, pnt : Vect<f64>) -> f64 {
+        let normal = match normal.dot(self.camera.towards(pnt)) < 0f64 {
+            false => normal,
+            true  => normal.vmul(-1f64)
+        };
+
+        self.ambient + (1f64 - self.ambient) * normal.dot(self.light).max(0f64)
+    }
+
+    /// rasterize triangle at pixel centers with interpolated depth key and intensity
+    fn fill_triangle(&mut self, [a, b, c] : [Projected; 3], intensities : [f64; 3], color : Rgb<u8>) {
+        let (width, height) = self.image.dimensions();
+
+        let area = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+
+        if area == 0f64 {
+            return
+        }
+
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0f64) as u32;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0f64) as u32;
+        let max_x = a.x.max(b.x).max(c.x).ceil().clamp(0f64, width as f64) as u32;
+        let max_y = a.y.max(b.y).max(c.y).ceil().clamp(0f64, height as f64) as u32;
+
+        let edge = |p : &Projected, q : &Projected, x : f64, y : f64| ((q.x - p.x) * (y - p.y) - (x - p.x) * (q.y - p.y)) / area;
+
+        for py in min_y..max_y {
+            for px in min_x..max_x {
+                let x = px as f64 + 0.5f64;
+                let y = py as f64 + 0.5f64;
+
+                // barycentric coordinates
+                let wa = edge(&b, &c, x, y);
+                let wb = edge(&c, &a, x, y);
+                let wc = edge(&a, &b, x, y);
+
+                if wa < 0f64 || wb < 0f64 || wc < 0f64 {
+                    continue;
+                }
+
+                let key   = wa * a.key + wb * b.key + wc * c.key;
+                let index = (py * width + px) as usize;
+
+                if key >= self.depth[index] {
+                    continue;
+                }
+
+                self.depth[index] = key;
+
+                let intensity = wa * intensities[0] + wb * intensities[1] + wc * intensities[2];
+                let pixel = color.0.map(|channel| (channel as f64 * intensity).round().clamp(0f64, 255f64) as u8);
+
+                self.image.put_pixel(px, py, Rgb(pixel));
+            }
+        }
+    }
+}
@@ -1,13 +1,26 @@
 use svg;
 
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
 use crate::geom::d2::prim::seg::{
     Segment
 };
 
 use crate::geom::d2::prim::vect::{
+    Vect,
     Vector 
 };
 
+use crate::geom::d2::shape::d2::{
+    Bounds
+};
+
 pub fn draw_segment_to_path<Seg : Segment>(segment : Seg) -> svg::node::element::Path 
 where Seg::Val : Into<svg::node::element::path::Parameters>
 {
@@ -63,4 +76,843 @@ where Iter::Item                   : Segment,
     }
 
     paths
-}
\ No newline at end of file
+}
+
+/// font size of svg text without font-size attribute
+const FONT_SIZE : f64 = 16f64;
+
+/// average width of a character relative to the font size used to estimate text extent
+const TEXT_WIDTH : f64 = 0.6f64;
+
+/// unit of the width and height of an svg document
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    /// user units without physical size
+    Px,
+    /// millimetres for printing at true scale
+    Mm,
+    /// inches for printing at true scale
+    In
+}
+
+/// rule which decides what is inside of a path with several subpaths (polygons with holes)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd
+}
+
+/// horizontal alignment of text relative to its position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    Start,
+    Middle,
+    End
+}
+
+/// presentation attributes of svg elements where None attributes are inherited
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    stroke       : Option<String>,
+    stroke_width : Option<f64>,
+    fill         : Option<String>,
+    fill_rule    : Option<FillRule>,
+    opacity      : Option<f64>,
+    dash         : Option<Vec<f64>>,
+    font_size    : Option<f64>,
+    font_family  : Option<String>
+}
+
+/// element of svg scene in scene coordinates
+#[derive(Clone, Debug)]
+enum Item {
+    Path{subpaths : Vec<(Vec<Vect<f64>>, bool)>, style : Style},
+    Polygon{vertices : Vec<Vect<f64>>, style : Style},
+    Polyline{vertices : Vec<Vect<f64>>, style : Style},
+    Text{position : Vect<f64>, text : String, anchor : Anchor, style : Style},
+    Group(Group)
+}
+
+/// group of svg elements with common style, a layer if it has a name
+#[derive(Clone, Debug, Default)]
+pub struct Group {
+    id    : Option<String>,
+    layer : Option<String>,
+    style : Style,
+    items : Vec<Item>
+}
+
+/// svg document of layers in scene coordinates (y axis upwards) which are fitted into the viewBox
+///
+/// with Unit::Mm one scene unit is printed as scale millimetres
+#[derive(Clone, Debug)]
+pub struct Scene {
+    bounds : Option<Bounds<Vect<f64>>>,
+    margin : f64,
+    unit   : Unit,
+    scale  : f64,
+    flip_y : bool,
+    layers : Vec<Group>
+}
+
+/// rows of key value pairs printed in the title block of a template
+#[derive(Clone, Debug, Default)]
+pub struct TitleBlock {
+    title   : String,
+    entries : Vec<(String, String)>
+}
+
+impl Unit {
+    /// svg suffix of unit
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Px => "",
+            Unit::Mm => "mm",
+            Unit::In => "in"
+        }
+    }
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// style of unfilled lines
+    pub fn stroke(color : &str, width : f64) -> Style {
+        Style::new().stroke_color(color).stroke_width(width).fill_color("none")
+    }
+
+    /// style of filled areas without lines
+    pub fn fill(color : &str) -> Style {
+        Style::new().fill_color(color).stroke_color("none")
+    }
+
+    pub fn stroke_color(mut self, color : &str) -> Style {
+        self.stroke = Some(color.to_string());
+        self
+    }
+
+    pub fn stroke_width(mut self, width : f64) -> Style {
+        self.stroke_width = Some(width);
+        self
+    }
+
+    pub fn fill_color(mut self, color : &str) -> Style {
+        self.fill = Some(color.to_string());
+        self
+    }
+
+    pub fn fill_rule(mut self, rule : FillRule) -> Style {
+        self.fill_rule = Some(rule);
+        self
+    }
+
+    pub fn opacity(mut self, opacity : f64) -> Style {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// dash pattern of alternating dash and gap lengths
+    pub fn dash(mut self, dash : Vec<f64>) -> Style {
+        self.dash = Some(dash);
+        self
+    }
+
+    pub fn font_size(mut self, size : f64) -> Style {
+        self.font_size = Some(size);
+        self
+    }
+
+    pub fn font_family(mut self, family : &str) -> Style {
+        self.font_family = Some(family.to_string());
+        self
+    }
+
+    /// assign the attributes of style to node
+    pub fn apply<N : svg::Node>(&self, mut node : N) -> N {
+        if let Some(stroke) = &self.stroke {
+            node.assign("stroke", stroke.as_str());
+        }
+
+        if let Some(stroke_width) = self.stroke_width {
+            node.assign("stroke-width", stroke_width);
+        }
+
+        if let Some(fill) = &self.fill {
+            node.assign("fill", fill.as_str());
+        }
+
+        if let Some(fill_rule) = self.fill_rule {
+            let rule = match fill_rule {
+                FillRule::NonZero => "nonzero",
+                FillRule::EvenOdd => "evenodd"
+            };
+
+            node.assign("fill-rule", rule);
+        }
+
+        if let Some(opacity) = self.opacity {
+            node.assign("opacity", opacity);
+        }
+
+        if let Some(dash) = &self.dash {
+            let dash = dash.iter().map(|val| val.to_string()).collect::<Vec<String>>().join(" ");
+            node.assign("stroke-dasharray", dash);
+        }
+
+        if let Some(font_size) = self.font_size {
+            node.assign("font-size", font_size);
+        }
+
+        if let Some(font_family) = &self.font_family {
+            node.assign("font-family", font_family.as_str());
+        }
+
+        node
+    }
+}
+
+impl Item {
+    /// componentwise min and max of all points of item with text extent estimated from the inherited font size
+    fn bounds(&self, font_size : f64) -> Option<(Vect<f64>, Vect<f64>)> {
+        let mut bounds : Option<(Vect<f64>, Vect<f64>)> = None;
+
+        let mut extend = |vertex : &Vect<f64>| {
+            bounds = match bounds {
+                None => Some((*vertex, *vertex)),
+                Some((min, max)) => Some((min.min(vertex), max.max(vertex)))
+            }
+        };
+
+        match self {
+            Item::Path{subpaths, ..} => subpaths.iter().flat_map(|(vertices, _)| vertices).for_each(&mut extend),
+            Item::Polygon{vertices, ..} => vertices.iter().for_each(&mut extend),
+            Item::Polyline{vertices, ..} => vertices.iter().for_each(&mut extend),
+            Item::Text{position, text, anchor, style} => {
+                let size  = style.font_size.unwrap_or(font_size);
+                let width = size * TEXT_WIDTH * text.chars().count() as f64;
+
+                let (start, end) = match anchor {
+                    Anchor::Start  => (0f64, width),
+                    Anchor::Middle => (-width / 2f64, width / 2f64),
+                    Anchor::End    => (-width, 0f64)
+                };
+
+                // glyphs extend above or below the baseline depending on the y direction of the scene
+                extend(&position.add((start, -size)));
+                extend(&position.add((end, size)));
+            },
+            Item::Group(group) => {
+                if let Some((min, max)) = group.extent(font_size) {
+                    extend(&min);
+                    extend(&max);
+                }
+            }
+        }
+
+        bounds
+    }
+
+    fn node<F : Fn(&Vect<f64>) -> Vect<f64>>(&self, map : &F) -> Box<dyn svg::Node> {
+        let points = |vertices : &Vec<Vect<f64>>| {
+            vertices.iter()
+                .map(|vertex| {
+                    let (x, y) = map(vertex);
+                    format!("{},{}", x, y)
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        match self {
+            Item::Path{subpaths, style} => {
+                let mut data = svg::node::element::path::Data::new();
+
+                for (vertices, closed) in subpaths {
+                    for (index, vertex) in vertices.iter().enumerate() {
+                        let (x, y) = map(vertex);
+                        let parameters = (x as f32, y as f32);
+
+                        data = match index {
+                            0 => data.move_to(parameters),
+                            _ => data.line_to(parameters)
+                        };
+                    }
+
+                    if *closed && !vertices.is_empty() {
+                        data = data.close();
+                    }
+                }
+
+                let path = svg::node::element::Path::new().set("d", data);
+                Box::new(style.apply(path))
+            },
+            Item::Polygon{vertices, style} => {
+                let polygon = svg::node::element::Polygon::new().set("points", points(vertices));
+                Box::new(style.apply(polygon))
+            },
+            Item::Polyline{vertices, style} => {
+                let polyline = svg::node::element::Polyline::new().set("points", points(vertices));
+                Box::new(style.apply(polyline))
+            },
+            Item::Text{position, text, anchor, style} => {
+                let (x, y) = map(position);
+
+                let anchor = match anchor {
+                    Anchor::Start  => "start",
+                    Anchor::Middle => "middle",
+                    Anchor::End    => "end"
+                };
+
+                let text = svg::node::element::Text::new()
+                    .set("x", x)
+                    .set("y", y)
+                    .set("text-anchor", anchor)
+                    .add(svg::node::Text::new(text.as_str()));
+
+                Box::new(style.apply(text))
+            },
+            Item::Group(group) => Box::new(group.node(map))
+        }
+    }
+}
+
+impl Group {
+    pub fn new() -> Group {
+        Group::default()
+    }
+
+    /// create new named group that is shown as layer by editors like inkscape
+    pub fn layer(name : &str) -> Group {
+        let mut group = Group::new().id(name);
+        group.layer = Some(name.to_string());
+        group
+    }
+
+    pub fn id(mut self, id : &str) -> Group {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// style inherited by all elements of group
+    pub fn style(mut self, style : Style) -> Group {
+        self.style = style;
+        self
+    }
+
+    /// add subgroup
+    pub fn group(mut self, group : Group) -> Group {
+        self.items.push(Item::Group(group));
+        self
+    }
+
+    /// add path of open (closed = false) or closed polylines
+    pub fn path<V : Vector>(mut self, subpaths : &[(&[V], bool)], style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        let subpaths = subpaths.iter()
+            .map(|(vertices, closed)| (Group::vertices(vertices), *closed))
+            .collect();
+
+        self.items.push(Item::Path{subpaths : subpaths, style : style});
+        self
+    }
+
+    /// add line segments as a single path
+    pub fn segments<Iter : IntoIterator>(mut self, segments : Iter, style : Style) -> Group
+    where Iter::Item                   : Segment,
+          <Iter::Item as Segment>::Val : Cast<f64>
+    {
+        let subpaths = segments.into_iter()
+            .map(|segment| (Group::vertices(&segment.pnts()), false))
+            .collect();
+
+        self.items.push(Item::Path{subpaths : subpaths, style : style});
+        self
+    }
+
+    /// add open polyline
+    pub fn polyline<V : Vector>(mut self, vertices : &[V], style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        self.items.push(Item::Polyline{vertices : Group::vertices(vertices), style : style});
+        self
+    }
+
+    /// add closed contour as polygon
+    pub fn contour<V : Vector>(mut self, contour : &[V], style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        self.items.push(Item::Polygon{vertices : Group::vertices(contour), style : style});
+        self
+    }
+
+    /// add polygon
+    pub fn poly<V : Vector>(self, poly : &Poly<V>, style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        self.contour(poly.vertices(), style)
+    }
+
+    /// add polygon with holes as path of closed subpaths filled with fill rule of style (defaults to even odd)
+    pub fn poly_with_holes<V : Vector>(self, outer : &[V], holes : &[&[V]], style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        let style = match style.fill_rule {
+            None    => style.fill_rule(FillRule::EvenOdd),
+            Some(_) => style
+        };
+
+        let mut subpaths = vec![(outer, true)];
+        subpaths.extend(holes.iter().map(|hole| (*hole, true)));
+
+        self.path(&subpaths, style)
+    }
+
+    /// add text label at position
+    pub fn text<V : Vector>(mut self, position : V, text : &str, anchor : Anchor, style : Style) -> Group
+    where V::Val : Cast<f64>
+    {
+        let position = (position.x().cast(), position.y().cast());
+
+        self.items.push(Item::Text{position : position, text : text.to_string(), anchor : anchor, style : style});
+        self
+    }
+
+    // Annotation methods
+
+    /// add calibrated scale bar of length scene units (millimetres) starting at start with alternating filled divisions
+    ///
+    /// the scale bar is labeled with its length and the magnification the document is printed with (see Scene::scale)
+    pub fn scale_bar<V : Vector>(self, start : V, length : f64, divisions : usize, magnification : f64, style : Style, font_size : f64) -> Group
+    where V::Val : Cast<f64>
+    {
+        let (x, y) = Group::vertex(&start);
+
+        let divisions = divisions.max(1);
+        let division  = length / divisions as f64;
+        let height    = font_size / 2f64;
+
+        let color = style.stroke.clone().unwrap_or("black".to_string());
+
+        let text_style = Style::fill(&color).font_size(font_size);
+        let mut group  = Group::new().id("scale-bar");
+
+        for index in 0..divisions {
+            let x0 = x + division * index as f64;
+            let x1 = x0 + division;
+
+            let fill = match index % 2 {
+                0 => color.as_str(),
+                _ => "none"
+            };
+
+            let block = [(x0, y), (x1, y), (x1, y + height), (x0, y + height)];
+            group = group.contour(&block, style.clone().fill_color(fill));
+        }
+
+        let label_y = y - font_size * 1.2f64;
+        let note_y  = label_y - font_size * 1.2f64;
+        let note    = format!("scale 1:1 at {}% magnification", Group::format(magnification * 100f64, 1));
+
+        let group = group
+            .text((x, label_y), "0", Anchor::Middle, text_style.clone())
+            .text((x + length, label_y), &format!("{} mm", Group::format(length, 2)), Anchor::Middle, text_style.clone())
+            .text((x, note_y), &note, Anchor::Start, text_style);
+
+        self.group(group)
+    }
+
+    /// add ruler from a towards b with a tick every spacing scene units (millimetres) and a labeled major tick every major ticks
+    pub fn ruler<V : Vector>(self, a : V, b : V, spacing : f64, major : usize, style : Style, font_size : f64) -> Group
+    where V::Val : Cast<f64>
+    {
+        let a = Group::vertex(&a);
+        let b = Group::vertex(&b);
+
+        let ab  = b.sub(&a);
+        let len = ab.len();
+
+        let mut group = Group::new().id("ruler").polyline(&[a, b], style.clone());
+
+        if len <= 0f64 || spacing <= 0f64 {
+            return self.group(group)
+        }
+
+        let dir  = ab.vdiv(len);
+        let orth = dir.orth_l();
+
+        let major = major.max(1);
+        let color = style.stroke.clone().unwrap_or("black".to_string());
+
+        let text_style = Style::fill(&color).font_size(font_size);
+
+        let count = (len / spacing).floor() as usize;
+
+        for index in 0..=count {
+            let dist = spacing * index as f64;
+            let base = a.add(dir.vmul(dist));
+
+            let tick = match index % major {
+                0 => font_size,
+                _ => font_size / 2f64
+            };
+
+            let end = base.add(orth.vmul(tick));
+            group = group.polyline(&[base, end], style.clone());
+
+            if index % major == 0 {
+                // baseline of text is lowered to center the label at the end of the tick
+                let label = base.add(orth.vmul(tick + font_size)).sub((0f64, font_size / 3f64));
+                group = group.text(label, &Group::format(dist, 2), Anchor::Middle, text_style.clone());
+            }
+        }
+
+        self.group(group)
+    }
+
+    /// add arc of radius at vertex from direction a to direction b (the smaller angle) labeled with the angle in degrees
+    pub fn angle<V : Vector>(self, vertex : V, dir_a : V, dir_b : V, radius : f64, style : Style, font_size : f64) -> Group
+    where V::Val : Cast<f64>
+    {
+        let vertex = Group::vertex(&vertex);
+        let dir_a  = Group::vertex(&dir_a);
+        let dir_b  = Group::vertex(&dir_b);
+
+        if dir_a.len() <= 0f64 || dir_b.len() <= 0f64 {
+            return self
+        }
+
+        let (dir_a, dir_b) = match dir_a.angle_l(&dir_b) <= std::f64::consts::PI {
+            false => (dir_b, dir_a),
+            true  => (dir_a, dir_b)
+        };
+
+        let angle = dir_a.angle_l(&dir_b);
+        let start = dir_a.y().atan2(dir_a.x());
+
+        // at least 2 arc vertices per degree
+        let steps = ((angle.to_degrees() * 2f64).ceil() as usize).max(1);
+
+        let arc = (0..=steps)
+            .map(|step| {
+                let phi = start + angle * step as f64 / steps as f64;
+                (vertex.x() + radius * phi.cos(), vertex.y() + radius * phi.sin())
+            })
+            .collect::<Vec<Vect<f64>>>();
+
+        let bisector = start + angle / 2f64;
+        let label_radius = radius + font_size;
+        let label = (vertex.x() + label_radius * bisector.cos(), vertex.y() + label_radius * bisector.sin() - font_size / 3f64);
+
+        let color = style.stroke.clone().unwrap_or("black".to_string());
+        let text  = format!("{}°", Group::format(angle.to_degrees(), 1));
+
+        let group = Group::new()
+            .id("angle")
+            .polyline(&arc, style)
+            .text(label, &text, Anchor::Middle, Style::fill(&color).font_size(font_size));
+
+        self.group(group)
+    }
+
+    /// add angle arc between the lines through two segments at their intersection (see Group::angle)
+    ///
+    /// the directions point from the intersection to the segment ends farther away, nothing is added for parallel segments
+    pub fn angle_segments<S : Segment>(self, seg_a : S, seg_b : S, radius : f64, style : Style, font_size : f64) -> Group
+    where S::Val : Cast<f64>
+    {
+        let [a0, a1] = seg_a.pnts();
+        let [b0, b1] = seg_b.pnts();
+
+        let a0 = Group::vertex(&a0);
+        let a1 = Group::vertex(&a1);
+        let b0 = Group::vertex(&b0);
+        let b1 = Group::vertex(&b1);
+
+        let ab = a1.sub(&a0);
+        let cd = b1.sub(&b0);
+
+        let det = ab.det(&cd);
+
+        if det == 0f64 {
+            return self
+        }
+
+        let r = b0.sub(&a0).det(&cd) / det;
+        let vertex = a0.add(ab.vmul(r));
+
+        let far = |p : Vect<f64>, q : Vect<f64>| match p.sub(&vertex).len() < q.sub(&vertex).len() {
+            false => p.sub(&vertex),
+            true  => q.sub(&vertex)
+        };
+
+        let dir_a = far(a0, a1);
+        let dir_b = far(b0, b1);
+
+        self.angle(vertex, dir_a, dir_b, radius, style, font_size)
+    }
+
+    /// add title block with its top left corner at position
+    pub fn title_block<V : Vector>(self, position : V, width : f64, block : &TitleBlock, style : Style, font_size : f64) -> Group
+    where V::Val : Cast<f64>
+    {
+        let (x, y) = Group::vertex(&position);
+
+        let row    = font_size * 1.6f64;
+        let rows   = block.entries().len() + 1;
+        let height = row * rows as f64;
+        let split  = x + width * 0.4f64;
+        let pad    = font_size / 2f64;
+
+        let color = style.stroke.clone().unwrap_or("black".to_string());
+
+        let text_style  = Style::fill(&color).font_size(font_size);
+        let title_style = text_style.clone().font_size(font_size * 1.2f64);
+
+        let frame = [(x, y), (x + width, y), (x + width, y - height), (x, y - height)];
+
+        let mut group = Group::new()
+            .id("title-block")
+            .contour(&frame, style.clone().fill_color("none"))
+            .text((x + pad, y - row + pad), block.title(), Anchor::Start, title_style);
+
+        for (index, (key, value)) in block.entries().iter().enumerate() {
+            let top  = y - row * (index + 1) as f64;
+            let base = top - row + pad;
+
+            group = group
+                .polyline(&[(x, top), (x + width, top)], style.clone())
+                .text((x + pad, base), key, Anchor::Start, text_style.clone())
+                .text((split + pad, base), value, Anchor::Start, text_style.clone());
+        }
+
+        let group = group.polyline(&[(split, y - row), (split, y - height)], style);
+
+        self.group(group)
+    }
+
+    /// componentwise min and max of all points of group including the estimated extent of texts
+    pub fn bounds(&self) -> Option<(Vect<f64>, Vect<f64>)> {
+        self.extent(FONT_SIZE)
+    }
+
+    /// componentwise min and max of all items with the font size inherited from outside of group
+    fn extent(&self, font_size : f64) -> Option<(Vect<f64>, Vect<f64>)> {
+        let font_size = self.style.font_size.unwrap_or(font_size);
+        let mut bounds : Option<(Vect<f64>, Vect<f64>)> = None;
+
+        for item in &self.items {
+            if let Some((item_min, item_max)) = item.bounds(font_size) {
+                bounds = match bounds {
+                    None => Some((item_min, item_max)),
+                    Some((min, max)) => Some((min.min(item_min), max.max(item_max)))
+                }
+            }
+        }
+
+        bounds
+    }
+
+    fn node<F : Fn(&Vect<f64>) -> Vect<f64>>(&self, map : &F) -> svg::node::element::Group {
+        let mut group = svg::node::element::Group::new();
+
+        if let Some(id) = &self.id {
+            group = group.set("id", id.as_str());
+        }
+
+        if let Some(layer) = &self.layer {
+            group = group
+                .set("inkscape:groupmode", "layer")
+                .set("inkscape:label", layer.as_str());
+        }
+
+        let mut group = self.style.apply(group);
+
+        for item in &self.items {
+            group = group.add(item.node(map));
+        }
+
+        group
+    }
+
+    fn vertices<V : Vector>(vertices : &[V]) -> Vec<Vect<f64>>
+    where V::Val : Cast<f64>
+    {
+        vertices.iter().map(|vertex| (vertex.x().cast(), vertex.y().cast())).collect()
+    }
+
+    fn vertex<V : Vector>(vertex : &V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        (vertex.x().cast(), vertex.y().cast())
+    }
+
+    /// format value with at most decimals decimal places and without trailing zeros
+    fn format(val : f64, decimals : usize) -> String {
+        let text = format!("{:.*}", decimals, val);
+
+        match text.contains('.') {
+            false => text,
+            true  => text.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+    }
+}
+
+impl TitleBlock {
+    pub fn new(title : &str) -> TitleBlock {
+        TitleBlock{title : title.to_string(), entries : Vec::new()}
+    }
+
+    /// title block of template of model generated for inclination and anteversion (radians) printed with magnification
+    pub fn template(model : &str, inclination : f64, anteversion : f64, magnification : f64) -> TitleBlock {
+        TitleBlock::new(model)
+            .entry("inclination", &format!("{}°", Group::format(inclination.to_degrees(), 1)))
+            .entry("anteversion", &format!("{}°", Group::format(anteversion.to_degrees(), 1)))
+            .entry("magnification", &format!("{}%", Group::format(magnification * 100f64, 1)))
+    }
+
+    /// add row of key and value
+    pub fn entry(mut self, key : &str, value : &str) -> TitleBlock {
+        self.entries.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn entries(&self) -> &Vec<(String, String)> {
+        &self.entries
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Scene {
+        Scene{
+            bounds : None,
+            margin : 0f64,
+            unit   : Unit::Px,
+            scale  : 1f64,
+            flip_y : true,
+            layers : Vec::new()
+        }
+    }
+}
+
+impl Scene {
+    pub fn new() -> Scene {
+        Scene::default()
+    }
+
+    /// scene area shown by viewBox, fitted to all layers if not set
+    pub fn bounds<V : Vector>(mut self, bounds : Bounds<V>) -> Scene
+    where V::Val : Cast<f64>
+    {
+        let start = bounds.start();
+        let end   = bounds.end();
+
+        let start = (start.x().cast(), start.y().cast());
+        let end   = (end.x().cast(), end.y().cast());
+
+        self.bounds = Some(Bounds::new(start, end));
+        self
+    }
+
+    /// margin around bounds in scene units
+    pub fn margin(mut self, margin : f64) -> Scene {
+        self.margin = margin;
+        self
+    }
+
+    /// unit of document width and height
+    pub fn unit(mut self, unit : Unit) -> Scene {
+        self.unit = unit;
+        self
+    }
+
+    /// document units per scene unit, e.g. 1.15 with Unit::Mm for templates magnified by 115%
+    pub fn scale(mut self, scale : f64) -> Scene {
+        self.scale = scale;
+        self
+    }
+
+    /// flip y axis so scene y axis points upwards (default true)
+    pub fn flip_y(mut self, flip_y : bool) -> Scene {
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// add layer drawn above all previous layers
+    pub fn layer(mut self, layer : Group) -> Scene {
+        self.layers.push(layer);
+        self
+    }
+
+    pub fn layers(&self) -> &Vec<Group> {
+        &self.layers
+    }
+
+    /// scene area of viewBox including margin
+    pub fn view(&self) -> Bounds<Vect<f64>> {
+        let (start, end) = match &self.bounds {
+            Some(bounds) => (bounds.start(), bounds.end()),
+            None => {
+                let mut bounds : Option<(Vect<f64>, Vect<f64>)> = None;
+
+                for layer in &self.layers {
+                    if let Some((layer_min, layer_max)) = layer.bounds() {
+                        bounds = match bounds {
+                            None => Some((layer_min, layer_max)),
+                            Some((min, max)) => Some((min.min(layer_min), max.max(layer_max)))
+                        }
+                    }
+                }
+
+                bounds.unwrap_or(((0f64, 0f64), (0f64, 0f64)))
+            }
+        };
+
+        Bounds::new_unchecked(start.vsub(self.margin), end.vadd(self.margin))
+    }
+
+    /// map scene point to svg user coordinates
+    pub fn map<V : Vector<Val = f64>>(&self, pnt : V) -> Vect<f64> {
+        match self.flip_y {
+            false => (pnt.x(), pnt.y()),
+            true  => (pnt.x(), 0f64 - pnt.y())
+        }
+    }
+
+    pub fn document(&self) -> svg::Document {
+        let view = self.view();
+        let size = view.size();
+
+        let view_y = match self.flip_y {
+            false => view.start().y(),
+            true  => -view.end().y()
+        };
+
+        let width  = format!("{}{}", size.x() * self.scale, self.unit.suffix());
+        let height = format!("{}{}", size.y() * self.scale, self.unit.suffix());
+
+        let mut document = svg::Document::new()
+            .set("xmlns:inkscape", "http://www.inkscape.org/namespaces/inkscape")
+            .set("width", width)
+            .set("height", height)
+            .set("viewBox", (view.start().x(), view_y, size.x(), size.y()));
+
+        let map = |pnt : &Vect<f64>| self.map(pnt);
+
+        for layer in &self.layers {
+            document = document.add(layer.node(&map));
+        }
+
+        document
+    }
+
+    pub fn save<P : AsRef<std::path::Path>>(&self, path : P) -> std::io::Result<()> {
+        svg::save(path, &self.document())
+    }
+}
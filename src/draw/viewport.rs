@@ -0,0 +1,149 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds
+};
+
+/// millimetres per inch
+const MM_PER_INCH : f64 = 25.4;
+
+/// mapping of world coordinates onto the pixels of an image
+///
+/// pixel = offset + (world - start) * scale where y is measured from the end of the world bounds if y is flipped
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    start  : Vect<f64>,
+    end    : Vect<f64>,
+    width  : u32,
+    height : u32,
+    scale  : f64,
+    offset : Vect<f64>,
+    flip_y : bool
+}
+
+impl Viewport {
+    /// viewport where world coordinates are pixel coordinates
+    pub fn pixels(width : u32, height : u32) -> Viewport {
+        let end = (width as f64, height as f64);
+        Viewport{start : (0f64, 0f64), end : end, width : width, height : height, scale : 1f64, offset : (0f64, 0f64), flip_y : false}
+    }
+
+    /// viewport that fits world bounds centered into image with margin in pixels and y axis pointing upwards
+    pub fn fit<V : Vector>(bounds : Bounds<V>, width : u32, height : u32, margin : f64) -> Viewport
+    where V::Val : Cast<f64>
+    {
+        let (start, end) = Viewport::corners(bounds);
+
+        let size_x = (end.x() - start.x()).max(f64::EPSILON);
+        let size_y = (end.y() - start.y()).max(f64::EPSILON);
+
+        let scale_x = (width as f64 - 2f64 * margin).max(1f64) / size_x;
+        let scale_y = (height as f64 - 2f64 * margin).max(1f64) / size_y;
+        let scale   = scale_x.min(scale_y);
+
+        let offset = ((width as f64 - size_x * scale) / 2f64, (height as f64 - size_y * scale) / 2f64);
+
+        Viewport{start : start, end : end, width : width, height : height, scale : scale, offset : offset, flip_y : true}
+    }
+
+    /// viewport of world bounds in millimetres printed at true scale with dots per inch and margin in millimetres
+    pub fn dpi<V : Vector>(bounds : Bounds<V>, dpi : f64, margin : f64) -> Viewport
+    where V::Val : Cast<f64>
+    {
+        let (start, end) = Viewport::corners(bounds);
+
+        let scale = dpi / MM_PER_INCH;
+
+        let width  = ((end.x() - start.x() + 2f64 * margin) * scale).ceil().max(1f64) as u32;
+        let height = ((end.y() - start.y() + 2f64 * margin) * scale).ceil().max(1f64) as u32;
+
+        Viewport{start : start, end : end, width : width, height : height, scale : scale, offset : (margin * scale, margin * scale), flip_y : true}
+    }
+
+    /// y axis pointing upwards (true) or downwards like pixel rows (false)
+    pub fn flip_y(mut self, flip_y : bool) -> Viewport {
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// pixels per world unit
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// pixel position of world point
+    pub fn map<V : Vector>(&self, pnt : &V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        let x : f64 = pnt.x().cast();
+        let y : f64 = pnt.y().cast();
+
+        let y = match self.flip_y {
+            false => y - self.start.y(),
+            true  => self.end.y() - y
+        };
+
+        (self.offset.x() + (x - self.start.x()) * self.scale, self.offset.y() + y * self.scale)
+    }
+
+    /// world point of pixel position
+    pub fn unmap(&self, pixel : Vect<f64>) -> Vect<f64> {
+        let x = (pixel.x() - self.offset.x()) / self.scale + self.start.x();
+        let y = (pixel.y() - self.offset.y()) / self.scale;
+
+        let y = match self.flip_y {
+            false => y + self.start.y(),
+            true  => self.end.y() - y
+        };
+
+        (x, y)
+    }
+
+    /// pixel length of world length
+    pub fn map_len(&self, len : f64) -> f64 {
+        len * self.scale
+    }
+
+    /// range of pixels (start inclusive and end exclusive) covered by world bounds clamped to the image
+    pub fn pixel_bounds<V : Vector>(&self, bounds : Bounds<V>) -> Bounds<(u32, u32)>
+    where V::Val : Cast<f64>
+    {
+        let a = self.map(&bounds.start());
+        let b = self.map(&bounds.end());
+
+        let clamp = |val : f64, max : u32| val.clamp(0f64, max as f64) as u32;
+
+        let start = (clamp(a.x().min(b.x()).floor(), self.width), clamp(a.y().min(b.y()).floor(), self.height));
+        let end   = (clamp(a.x().max(b.x()).ceil(), self.width), clamp(a.y().max(b.y()).ceil(), self.height));
+
+        Bounds::new_unchecked(start, end)
+    }
+
+    fn corners<V : Vector>(bounds : Bounds<V>) -> (Vect<f64>, Vect<f64>)
+    where V::Val : Cast<f64>
+    {
+        let start = bounds.start();
+        let end   = bounds.end();
+
+        ((start.x().cast(), start.y().cast()), (end.x().cast(), end.y().cast()))
+    }
+}
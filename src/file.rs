@@ -1 +1,3 @@
-pub mod stl;
\ No newline at end of file
+pub mod dxf;
+pub mod stl;
+pub mod svg;
\ No newline at end of file
@@ -0,0 +1,641 @@
+use std::{
+    fs,
+    io
+};
+
+use std::convert::{
+    AsRef
+};
+
+use std::fmt::{
+    Write
+};
+
+use std::path::{
+    Path
+};
+
+use std::vec::{
+    Vec
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::mesh::ind::{
+    IndSegMesh
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::ellipse::{
+    steps
+};
+
+use crate::geom::d2::prim::seg::{
+    Segment
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::mesh::ind::{
+    IndSeg
+};
+
+/// autocad color index of layers without explicit color (white/black)
+const DEFAULT_COLOR : u8 = 7;
+
+/// maximal distance in millimetres between bulged lwpolyline arcs and their flattened vertices
+const ARC_TOLERANCE : f64 = 0.01;
+
+/// maximal number of vertices of a flattened lwpolyline arc
+const MAX_ARC_VERTICES : usize = 1024;
+
+/// dxf entity in millimetres on named layer
+#[derive(Clone, Debug)]
+pub enum Entity {
+    Polyline{layer : String, vertices : Vec<Vect<f64>>, closed : bool},
+    Line{layer : String, a : Vect<f64>, b : Vect<f64>},
+    Text{layer : String, position : Vect<f64>, height : f64, text : String}
+}
+
+/// dxf drawing of polylines, lines and texts on named layers with millimetre units
+#[derive(Clone, Debug)]
+pub struct Dxf {
+    layers   : Vec<(String, u8)>,
+    entities : Vec<Entity>
+}
+
+impl Entity {
+    pub fn layer(&self) -> &str {
+        match self {
+            Entity::Polyline{layer, ..} => layer,
+            Entity::Line{layer, ..}     => layer,
+            Entity::Text{layer, ..}     => layer
+        }
+    }
+}
+
+impl Default for Dxf {
+    fn default() -> Dxf {
+        Dxf::new()
+    }
+}
+
+impl Dxf {
+    pub fn new() -> Dxf {
+        Dxf{layers : Vec::new(), entities : Vec::new()}
+    }
+
+    /// define layer with autocad color index (layers of entities are defined with color 7 otherwise)
+    pub fn layer(mut self, name : &str, color : u8) -> Dxf {
+        match self.layers.iter_mut().find(|(layer, _)| layer == name) {
+            Some(layer) => layer.1 = color,
+            None => self.layers.push((name.to_string(), color))
+        }
+
+        self
+    }
+
+    pub fn layers(&self) -> &Vec<(String, u8)> {
+        &self.layers
+    }
+
+    pub fn entities(&self) -> &Vec<Entity> {
+        &self.entities
+    }
+
+    /// add entity and define its layer if necessary
+    pub fn entity(mut self, entity : Entity) -> Dxf {
+        if !self.layers.iter().any(|(layer, _)| layer == entity.layer()) {
+            self.layers.push((entity.layer().to_string(), DEFAULT_COLOR));
+        }
+
+        self.entities.push(entity);
+        self
+    }
+
+    /// add open polyline
+    pub fn polyline<V : Vector>(self, layer : &str, vertices : &[V]) -> Dxf
+    where V::Val : Cast<f64>
+    {
+        self.entity(Entity::Polyline{layer : layer.to_string(), vertices : Dxf::vertices(vertices), closed : false})
+    }
+
+    /// add closed contour as closed polyline
+    pub fn contour<V : Vector>(self, layer : &str, contour : &[V]) -> Dxf
+    where V::Val : Cast<f64>
+    {
+        self.entity(Entity::Polyline{layer : layer.to_string(), vertices : Dxf::vertices(contour), closed : true})
+    }
+
+    /// add polygon as closed polyline
+    pub fn poly<V : Vector>(self, layer : &str, poly : &Poly<V>) -> Dxf
+    where V::Val : Cast<f64>
+    {
+        self.contour(layer, poly.vertices())
+    }
+
+    /// add line segments as lines
+    pub fn segments<Iter : IntoIterator>(self, layer : &str, segments : Iter) -> Dxf
+    where Iter::Item                   : Segment,
+          <Iter::Item as Segment>::Val : Cast<f64>
+    {
+        segments.into_iter().fold(self, |dxf, segment| {
+            let [a, b] = segment.pnts();
+            dxf.entity(Entity::Line{layer : layer.to_string(), a : Dxf::vertex(&a), b : Dxf::vertex(&b)})
+        })
+    }
+
+    /// add text with baseline starting at position and height in millimetres
+    pub fn text<V : Vector>(self, layer : &str, position : V, height : f64, text : &str) -> Dxf
+    where V::Val : Cast<f64>
+    {
+        self.entity(Entity::Text{layer : layer.to_string(), position : Dxf::vertex(&position), height : height, text : text.to_string()})
+    }
+
+    /// drawing with the entities of layer only
+    pub fn filter_layer(&self, name : &str) -> Dxf {
+        let layers   = self.layers.iter().filter(|(layer, _)| layer == name).cloned().collect();
+        let entities = self.entities.iter().filter(|entity| entity.layer() == name).cloned().collect();
+
+        Dxf{layers : layers, entities : entities}
+    }
+
+    /// polygons of all closed polylines
+    pub fn polys(&self) -> Vec<Poly<Vect<f64>>> {
+        self.entities.iter()
+            .filter_map(|entity| match entity {
+                Entity::Polyline{vertices, closed : true, ..} if vertices.len() >= 3 => Some(Poly::new(vertices.clone())),
+                _ => None
+            })
+            .collect()
+    }
+
+    /// indexed segment mesh of all polylines and lines where every entity has its own vertices
+    pub fn ind_seg_mesh(&self) -> IndSegMesh<Vect<f64>> {
+        let mut vertices = Vec::new();
+        let mut segments = Vec::new();
+
+        for entity in &self.entities {
+            let offset = vertices.len();
+
+            match entity {
+                Entity::Polyline{vertices : polyline, closed, ..} => {
+                    let len = polyline.len();
+
+                    vertices.extend(polyline.iter().copied());
+
+                    for index in 1..len {
+                        segments.push(IndSeg::new(offset + index - 1, offset + index));
+                    }
+
+                    if *closed && len > 2 {
+                        segments.push(IndSeg::new(offset + len - 1, offset));
+                    }
+                },
+                Entity::Line{a, b, ..} => {
+                    vertices.push(*a);
+                    vertices.push(*b);
+                    segments.push(IndSeg::new(offset, offset + 1));
+                },
+                Entity::Text{..} => ()
+            }
+        }
+
+        IndSegMesh::new_unchecked(vertices, segments)
+    }
+
+    // Write methods
+
+    /// dxf (autocad 2000) document text with the tables, blocks and objects required by autocad
+    ///
+    /// line breaks in texts and layer names are replaced by spaces and characters not allowed in layer names by underscores
+    pub fn document(&self) -> String {
+        let mut body   = String::new();
+        let mut handle = 0x20usize;
+
+        let mut pair = |code : u16, value : &str| {
+            let _ = write!(body, "{:>3}\n{}\n", code, value);
+        };
+
+        let mut next = || {
+            handle += 1;
+            format!("{:X}", handle)
+        };
+
+        // layer 0 always exists and duplicate names after sanitizing are written once
+        let mut layers : Vec<(String, u8)> = vec![("0".to_string(), DEFAULT_COLOR)];
+
+        for (name, color) in &self.layers {
+            let name = Dxf::layer_name(name);
+
+            match layers.iter_mut().find(|(layer, _)| *layer == name) {
+                Some(layer) => layer.1 = *color,
+                None => layers.push((name, *color))
+            }
+        }
+
+        let model_space = next();
+        let paper_space = next();
+
+        pair(0, "SECTION");
+        pair(2, "CLASSES");
+        pair(0, "ENDSEC");
+
+        pair(0, "SECTION");
+        pair(2, "TABLES");
+
+        for table in ["VPORT", "LTYPE", "LAYER", "STYLE", "VIEW", "UCS", "APPID", "DIMSTYLE", "BLOCK_RECORD"] {
+            let owner = next();
+
+            let count = match table {
+                "LTYPE"        => 3,
+                "LAYER"        => layers.len(),
+                "STYLE"        => 1,
+                "APPID"        => 1,
+                "DIMSTYLE"     => 1,
+                "BLOCK_RECORD" => 2,
+                _ => 0
+            };
+
+            pair(0, "TABLE");
+            pair(2, table);
+            pair(5, &owner);
+            pair(330, "0");
+            pair(100, "AcDbSymbolTable");
+            pair(70, &count.to_string());
+
+            let record = |kind : &str, subclass : &str, name : &str, handle : &str, pair : &mut dyn FnMut(u16, &str)| {
+                pair(0, kind);
+                pair(if kind == "DIMSTYLE" { 105 } else { 5 }, handle);
+                pair(330, &owner);
+                pair(100, "AcDbSymbolTableRecord");
+                pair(100, subclass);
+                pair(2, name);
+                pair(70, "0");
+            };
+
+            match table {
+                "LTYPE" => {
+                    for (name, description) in [("ByBlock", ""), ("ByLayer", ""), ("Continuous", "Solid line")] {
+                        record("LTYPE", "AcDbLinetypeTableRecord", name, &next(), &mut pair);
+                        pair(3, description);
+                        pair(72, "65");
+                        pair(73, "0");
+                        pair(40, "0.0");
+                    }
+                },
+                "LAYER" => {
+                    for (name, color) in &layers {
+                        record("LAYER", "AcDbLayerTableRecord", name, &next(), &mut pair);
+                        pair(62, &color.to_string());
+                        pair(6, "Continuous");
+                    }
+                },
+                "STYLE" => {
+                    record("STYLE", "AcDbTextStyleTableRecord", "Standard", &next(), &mut pair);
+                    pair(40, "0.0");
+                    pair(41, "1.0");
+                    pair(50, "0.0");
+                    pair(71, "0");
+                    pair(42, "2.5");
+                    pair(3, "txt");
+                    pair(4, "");
+                },
+                "APPID" => {
+                    record("APPID", "AcDbRegAppTableRecord", "ACAD", &next(), &mut pair);
+                },
+                "DIMSTYLE" => {
+                    pair(100, "AcDbDimStyleTable");
+                    record("DIMSTYLE", "AcDbDimStyleTableRecord", "Standard", &next(), &mut pair);
+                },
+                "BLOCK_RECORD" => {
+                    record("BLOCK_RECORD", "AcDbBlockTableRecord", "*Model_Space", &model_space, &mut pair);
+                    record("BLOCK_RECORD", "AcDbBlockTableRecord", "*Paper_Space", &paper_space, &mut pair);
+                },
+                _ => ()
+            }
+
+            pair(0, "ENDTAB");
+        }
+
+        pair(0, "ENDSEC");
+
+        pair(0, "SECTION");
+        pair(2, "BLOCKS");
+
+        for (name, owner, paper) in [("*Model_Space", &model_space, false), ("*Paper_Space", &paper_space, true)] {
+            pair(0, "BLOCK");
+            pair(5, &next());
+            pair(330, owner);
+            pair(100, "AcDbEntity");
+
+            if paper {
+                pair(67, "1");
+            }
+
+            pair(8, "0");
+            pair(100, "AcDbBlockBegin");
+            pair(2, name);
+            pair(70, "0");
+            pair(10, "0.0");
+            pair(20, "0.0");
+            pair(30, "0.0");
+            pair(3, name);
+            pair(1, "");
+
+            pair(0, "ENDBLK");
+            pair(5, &next());
+            pair(330, owner);
+            pair(100, "AcDbEntity");
+
+            if paper {
+                pair(67, "1");
+            }
+
+            pair(8, "0");
+            pair(100, "AcDbBlockEnd");
+        }
+
+        pair(0, "ENDSEC");
+
+        pair(0, "SECTION");
+        pair(2, "ENTITIES");
+
+        for entity in &self.entities {
+            let (kind, subclass) = match entity {
+                Entity::Polyline{..} => ("LWPOLYLINE", "AcDbPolyline"),
+                Entity::Line{..}     => ("LINE", "AcDbLine"),
+                Entity::Text{..}     => ("TEXT", "AcDbText")
+            };
+
+            pair(0, kind);
+            pair(5, &next());
+            pair(330, &model_space);
+            pair(100, "AcDbEntity");
+            pair(8, &Dxf::layer_name(entity.layer()));
+            pair(100, subclass);
+
+            match entity {
+                Entity::Polyline{vertices, closed, ..} => {
+                    pair(90, &vertices.len().to_string());
+                    pair(70, if *closed { "1" } else { "0" });
+
+                    for vertex in vertices {
+                        pair(10, &vertex.x().to_string());
+                        pair(20, &vertex.y().to_string());
+                    }
+                },
+                Entity::Line{a, b, ..} => {
+                    pair(10, &a.x().to_string());
+                    pair(20, &a.y().to_string());
+                    pair(30, "0");
+                    pair(11, &b.x().to_string());
+                    pair(21, &b.y().to_string());
+                    pair(31, "0");
+                },
+                Entity::Text{position, height, text, ..} => {
+                    pair(10, &position.x().to_string());
+                    pair(20, &position.y().to_string());
+                    pair(30, "0");
+                    pair(40, &height.to_string());
+                    pair(1, &text.replace(['\r', '\n'], " "));
+                    pair(100, "AcDbText");
+                }
+            }
+        }
+
+        pair(0, "ENDSEC");
+
+        // root dictionary with the group dictionary
+        let root  = next();
+        let group = next();
+
+        pair(0, "SECTION");
+        pair(2, "OBJECTS");
+        pair(0, "DICTIONARY");
+        pair(5, &root);
+        pair(330, "0");
+        pair(100, "AcDbDictionary");
+        pair(281, "1");
+        pair(3, "ACAD_GROUP");
+        pair(350, &group);
+        pair(0, "DICTIONARY");
+        pair(5, &group);
+        pair(330, &root);
+        pair(100, "AcDbDictionary");
+        pair(281, "1");
+        pair(0, "ENDSEC");
+        pair(0, "EOF");
+
+        let mut doc = String::new();
+
+        let mut pair = |code : u16, value : &str| {
+            let _ = write!(doc, "{:>3}\n{}\n", code, value);
+        };
+
+        pair(0, "SECTION");
+        pair(2, "HEADER");
+        pair(9, "$ACADVER");
+        pair(1, "AC1015");
+        pair(9, "$HANDSEED");
+        pair(5, &format!("{:X}", handle + 1));
+        pair(9, "$INSUNITS");
+        pair(70, "4");
+        pair(9, "$MEASUREMENT");
+        pair(70, "1");
+        pair(0, "ENDSEC");
+
+        doc.push_str(&body);
+        doc
+    }
+
+    pub fn write<A : AsRef<Path>>(&self, path : A) -> io::Result<()> {
+        fs::write(path, self.document())
+    }
+
+    // Read methods
+
+    /// read lwpolyline and line entities of dxf file (see Dxf::parse)
+    pub fn read<A : AsRef<Path>>(path : A) -> io::Result<Dxf> {
+        let content = fs::read_to_string(path)?;
+        Dxf::parse(&content)
+    }
+
+    /// parse lwpolyline and line entities and layer colors of dxf document (other entities are skipped)
+    ///
+    /// bulged lwpolyline segments are flattened to arcs within ARC_TOLERANCE
+    pub fn parse(content : &str) -> io::Result<Dxf> {
+        let lines = content.lines().map(|line| line.trim()).collect::<Vec<&str>>();
+
+        if lines.len() % 2 != 0 && lines.last().is_some_and(|line| !line.is_empty()) {
+            return Err(Dxf::invalid("parse", "odd number of lines"))
+        }
+
+        let mut pairs = Vec::with_capacity(lines.len() / 2);
+
+        for chunk in lines.chunks_exact(2) {
+            match chunk[0].parse::<u16>() {
+                Ok(code) => pairs.push((code, chunk[1])),
+                Err(_)   => return Err(Dxf::invalid("parse", &format!("invalid group code \"{}\"", chunk[0])))
+            }
+        }
+
+        let mut dxf     = Dxf::new();
+        let mut section = "";
+
+        // records are the group pairs between two 0 codes
+        let mut index = 0;
+
+        while index < pairs.len() {
+            let (code, kind) = pairs[index];
+            let end = pairs[index + 1..].iter().position(|(code, _)| *code == 0).map_or(pairs.len(), |pos| index + 1 + pos);
+
+            let record = &pairs[index + 1..end];
+
+            index = end;
+
+            if code != 0 {
+                continue;
+            }
+
+            let value = |code : u16| record.iter().find(|(c, _)| *c == code).map(|(_, value)| *value);
+
+            match (section, kind) {
+                (_, "SECTION") => section = match value(2) {
+                    Some("TABLES")   => "TABLES",
+                    Some("ENTITIES") => "ENTITIES",
+                    _ => ""
+                },
+                (_, "ENDSEC") => section = "",
+                ("TABLES", "LAYER") => {
+                    if let Some(name) = value(2) {
+                        let color = match value(62) {
+                            None => DEFAULT_COLOR,
+                            Some(color) => Dxf::number::<i16>(color)?.unsigned_abs().min(255) as u8
+                        };
+
+                        dxf = dxf.layer(name, color);
+                    }
+                },
+                ("ENTITIES", "LWPOLYLINE") => {
+                    let layer  = value(8).unwrap_or("0");
+                    let closed = Dxf::number::<u16>(value(70).unwrap_or("0"))? & 1 == 1;
+
+                    let mut vertices : Vec<(Vect<f64>, f64)> = Vec::new();
+
+                    for (code, value) in record {
+                        match code {
+                            10 => vertices.push(((Dxf::number::<f64>(value)?, 0f64), 0f64)),
+                            20 => match vertices.last_mut() {
+                                Some(vertex) => vertex.0.1 = Dxf::number::<f64>(value)?,
+                                None => return Err(Dxf::invalid("parse", "lwpolyline y coordinate without x coordinate"))
+                            },
+                            42 => match vertices.last_mut() {
+                                Some(vertex) => vertex.1 = Dxf::number::<f64>(value)?,
+                                None => return Err(Dxf::invalid("parse", "lwpolyline bulge without vertex"))
+                            },
+                            _ => ()
+                        }
+                    }
+
+                    // bulges are arcs to the next vertex which are flattened
+                    let mut flattened = Vec::with_capacity(vertices.len());
+
+                    for (index, (vertex, bulge)) in vertices.iter().enumerate() {
+                        flattened.push(*vertex);
+
+                        let next = match index + 1 < vertices.len() {
+                            true  => Some(vertices[index + 1].0),
+                            false => if closed { vertices.first().map(|(first, _)| *first) } else { None }
+                        };
+
+                        if let Some(next) = next {
+                            Dxf::flatten_bulge(&mut flattened, *vertex, next, *bulge);
+                        }
+                    }
+
+                    let vertices = flattened;
+
+                    dxf = dxf.entity(Entity::Polyline{layer : layer.to_string(), vertices : vertices, closed : closed});
+                },
+                ("ENTITIES", "LINE") => {
+                    let layer = value(8).unwrap_or("0");
+                    let coord = |code : u16| Dxf::number::<f64>(value(code).unwrap_or("0"));
+
+                    let a = (coord(10)?, coord(20)?);
+                    let b = (coord(11)?, coord(21)?);
+
+                    dxf = dxf.entity(Entity::Line{layer : layer.to_string(), a : a, b : b});
+                },
+                _ => ()
+            }
+        }
+
+        Ok(dxf)
+    }
+
+    /// layer name without line breaks and characters not allowed by autocad
+    fn layer_name(name : &str) -> String {
+        name.chars()
+            .map(|c| match c {
+                '\r' | '\n' => ' ',
+                '<' | '>' | '/' | '\\' | '"' | ':' | ';' | '?' | '*' | '|' | '=' | '`' => '_',
+                c => c
+            })
+            .collect()
+    }
+
+    /// push vertices of arc from a to b with bulge (tangent of a quarter of the counterclockwise arc angle) without a and b
+    fn flatten_bulge(vertices : &mut Vec<Vect<f64>>, a : Vect<f64>, b : Vect<f64>, bulge : f64) {
+        let chord = b.sub(a);
+        let half  = chord.len() / 2f64;
+
+        if half == 0f64 || bulge == 0f64 {
+            return
+        }
+
+        let angle  = 4f64 * bulge.atan();
+        let center = a.add(chord.vmul(0.5f64)).add(chord.orth_l().vdiv(2f64 * half).vmul(half / (angle / 2f64).tan()));
+        let radius = a.sub(center).len();
+
+        let count = steps(radius, ARC_TOLERANCE, angle, 1).min(MAX_ARC_VERTICES);
+
+        let start = a.sub(center);
+
+        for index in 1..count {
+            let (sin, cos) = (angle * index as f64 / count as f64).sin_cos();
+            vertices.push(center.add((cos * start.x() - sin * start.y(), sin * start.x() + cos * start.y())));
+        }
+    }
+
+    fn invalid(func : &str, msg : &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("tg.file.dxf.Dxf.{}: {}", func, msg))
+    }
+
+    fn number<T : std::str::FromStr>(value : &str) -> io::Result<T> {
+        match value.parse::<T>() {
+            Ok(number) => Ok(number),
+            Err(_) => Err(Dxf::invalid("number", &format!("invalid number \"{}\"", value)))
+        }
+    }
+
+    fn vertices<V : Vector>(vertices : &[V]) -> Vec<Vect<f64>>
+    where V::Val : Cast<f64>
+    {
+        vertices.iter().map(Dxf::vertex).collect()
+    }
+
+    fn vertex<V : Vector>(vertex : &V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        (vertex.x().cast(), vertex.y().cast())
+    }
+}
@@ -118,6 +118,18 @@ impl Stl {
         &self.head
     }
 
+    /// text of head up to first zero byte without surrounding whitespace
+    pub fn name(&self) -> String {
+        let end = self.head.iter().position(|byte| *byte == 0).unwrap_or(self.head.len());
+
+        let name = self.head[0..end]
+            .iter()
+            .map(|byte| *byte as char)
+            .collect::<String>();
+
+        name.trim().to_string()
+    }
+
     pub fn triangles(&self) -> &Vec<Tri> {
         &self.triangles
     }
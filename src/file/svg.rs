@@ -0,0 +1,715 @@
+use std::{
+    fs,
+    io
+};
+
+use std::convert::{
+    AsRef
+};
+
+use std::path::{
+    Path
+};
+
+use std::vec::{
+    Vec
+};
+
+use ::svg::node::element::tag::{
+    Type
+};
+
+use ::svg::parser::{
+    Event
+};
+
+use crate::geom::d2::mesh::ind::{
+    IndSegMesh
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::bezier::{
+    Bezier
+};
+
+use crate::geom::d2::prim::ellipse::{
+    steps
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::mesh::ind::{
+    IndSeg
+};
+
+/// elements whose children are not drawn
+const HIDDEN_ELEMENTS : [&str; 7] = ["defs", "clipPath", "mask", "marker", "pattern", "symbol", "title"];
+
+/// affine transformation (x, y) -> (a * x + c * y + e, b * x + d * y + f) stored as \[a, b, c, d, e, f\]
+#[derive(Clone, Copy, Debug)]
+struct Transform {
+    matrix : [f64; 6]
+}
+
+/// flattened subpath of svg element in document user units
+#[derive(Clone, Debug)]
+pub struct SvgPath {
+    id       : Option<String>,
+    vertices : Vec<Vect<f64>>,
+    closed   : bool
+}
+
+/// flattened outlines of svg document
+#[derive(Clone, Debug)]
+pub struct Svg {
+    paths : Vec<SvgPath>
+}
+
+/// reader of path data and point lists that accepts numbers separated by whitespace, commas or signs
+struct DataReader<'a> {
+    chars : Vec<char>,
+    pos   : usize,
+    data  : &'a str
+}
+
+impl Transform {
+    fn identity() -> Transform {
+        Transform{matrix : [1f64, 0f64, 0f64, 1f64, 0f64, 0f64]}
+    }
+
+    /// transformation that applies other first and then self
+    fn then(&self, other : &Transform) -> Transform {
+        let [a0, b0, c0, d0, e0, f0] = self.matrix;
+        let [a1, b1, c1, d1, e1, f1] = other.matrix;
+
+        let matrix = [
+            a0 * a1 + c0 * b1,
+            b0 * a1 + d0 * b1,
+            a0 * c1 + c0 * d1,
+            b0 * c1 + d0 * d1,
+            a0 * e1 + c0 * f1 + e0,
+            b0 * e1 + d0 * f1 + f0
+        ];
+
+        Transform{matrix : matrix}
+    }
+
+    fn apply(&self, pnt : Vect<f64>) -> Vect<f64> {
+        let [a, b, c, d, e, f] = self.matrix;
+
+        (a * pnt.x() + c * pnt.y() + e, b * pnt.x() + d * pnt.y() + f)
+    }
+
+    /// mean scaling factor of transformation
+    fn scale(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.matrix;
+
+        (a * d - b * c).abs().sqrt()
+    }
+
+    /// parse transform attribute like "translate(10 20) rotate(45)"
+    fn parse(text : &str) -> io::Result<Transform> {
+        let mut transform = Transform::identity();
+
+        for part in text.split(')') {
+            let part = part.trim_matches(|c : char| c.is_whitespace() || c == ',');
+
+            if part.is_empty() {
+                continue;
+            }
+
+            let Some((name, args)) = part.split_once('(') else {
+                return Err(Svg::invalid("Transform.parse", "missing opening parenthesis"))
+            };
+
+            let mut reader = DataReader::new(args);
+            let mut vals   = Vec::new();
+
+            while let Some(val) = reader.number()? {
+                vals.push(val);
+            }
+
+            let matrix = match (name.trim(), vals.as_slice()) {
+                ("matrix", [a, b, c, d, e, f]) => [*a, *b, *c, *d, *e, *f],
+                ("translate", [x]) => [1f64, 0f64, 0f64, 1f64, *x, 0f64],
+                ("translate", [x, y]) => [1f64, 0f64, 0f64, 1f64, *x, *y],
+                ("scale", [s]) => [*s, 0f64, 0f64, *s, 0f64, 0f64],
+                ("scale", [x, y]) => [*x, 0f64, 0f64, *y, 0f64, 0f64],
+                ("rotate", [angle]) => Transform::rotation(*angle, 0f64, 0f64),
+                ("rotate", [angle, x, y]) => Transform::rotation(*angle, *x, *y),
+                ("skewX", [angle]) => [1f64, 0f64, angle.to_radians().tan(), 1f64, 0f64, 0f64],
+                ("skewY", [angle]) => [1f64, angle.to_radians().tan(), 0f64, 1f64, 0f64, 0f64],
+                _ => return Err(Svg::invalid("Transform.parse", "invalid transform function"))
+            };
+
+            transform = transform.then(&Transform{matrix : matrix});
+        }
+
+        Ok(transform)
+    }
+
+    /// rotation by angle in degrees around (x, y)
+    fn rotation(angle : f64, x : f64, y : f64) -> [f64; 6] {
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        [cos, sin, -sin, cos, x - cos * x + sin * y, y - sin * x - cos * y]
+    }
+}
+
+impl<'a> DataReader<'a> {
+    fn new(data : &'a str) -> DataReader<'a> {
+        DataReader{chars : data.chars().collect(), pos : 0, data : data}
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.chars.len() && (self.chars[self.pos].is_whitespace() || self.chars[self.pos] == ',') {
+            self.pos += 1;
+        }
+    }
+
+    /// next command letter if next token is a letter
+    fn command(&mut self) -> Option<char> {
+        self.skip_separators();
+
+        let c = *self.chars.get(self.pos)?;
+
+        match c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            false => None,
+            true  => {
+                self.pos += 1;
+                Some(c)
+            }
+        }
+    }
+
+    /// true if there are no tokens left
+    fn done(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.chars.len()
+    }
+
+    /// next number or None if next token is no number
+    fn number(&mut self) -> io::Result<Option<f64>> {
+        self.skip_separators();
+
+        let start = self.pos;
+
+        if self.pos < self.chars.len() && (self.chars[self.pos] == '-' || self.chars[self.pos] == '+') {
+            self.pos += 1;
+        }
+
+        let mut digits = false;
+        let mut dot    = false;
+
+        while self.pos < self.chars.len() {
+            let c = self.chars[self.pos];
+
+            if c.is_ascii_digit() {
+                digits = true;
+            }
+            else if c == '.' && !dot {
+                dot = true;
+            }
+            else {
+                break;
+            }
+
+            self.pos += 1;
+        }
+
+        if !digits {
+            self.pos = start;
+            return Ok(None)
+        }
+
+        if self.pos < self.chars.len() && (self.chars[self.pos] == 'e' || self.chars[self.pos] == 'E') {
+            let exponent = self.pos;
+            self.pos += 1;
+
+            if self.pos < self.chars.len() && (self.chars[self.pos] == '-' || self.chars[self.pos] == '+') {
+                self.pos += 1;
+            }
+
+            let digits_start = self.pos;
+
+            while self.pos < self.chars.len() && self.chars[self.pos].is_ascii_digit() {
+                self.pos += 1;
+            }
+
+            if digits_start == self.pos {
+                self.pos = exponent;
+            }
+        }
+
+        let text = self.chars[start..self.pos].iter().collect::<String>();
+
+        match text.parse::<f64>() {
+            Ok(val) => Ok(Some(val)),
+            Err(_)  => Err(Svg::invalid("DataReader.number", &format!("invalid number \"{}\" in \"{}\"", text, self.data)))
+        }
+    }
+
+    /// next number that has to exist
+    fn expect(&mut self) -> io::Result<f64> {
+        match self.number()? {
+            Some(val) => Ok(val),
+            None => Err(Svg::invalid("DataReader.expect", &format!("missing number in \"{}\"", self.data)))
+        }
+    }
+
+    /// next arc flag which may not be separated from the following number
+    fn flag(&mut self) -> io::Result<bool> {
+        self.skip_separators();
+
+        match self.chars.get(self.pos) {
+            Some('0') => {
+                self.pos += 1;
+                Ok(false)
+            },
+            Some('1') => {
+                self.pos += 1;
+                Ok(true)
+            },
+            _ => Err(Svg::invalid("DataReader.flag", &format!("missing arc flag in \"{}\"", self.data)))
+        }
+    }
+}
+
+impl SvgPath {
+    /// id of svg element the path belongs to
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn vertices(&self) -> &Vec<Vect<f64>> {
+        &self.vertices
+    }
+
+    /// true if the last vertex is connected to the first vertex
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl Svg {
+    // General methods
+
+    /// read svg file and flatten its curves with maximal deviation tolerance
+    pub fn read<A : AsRef<Path>>(path : A, tolerance : f64) -> io::Result<Svg> {
+        let content = fs::read_to_string(path)?;
+        Svg::parse(&content, tolerance)
+    }
+
+    /// parse svg document and flatten its curves with maximal deviation tolerance
+    ///
+    /// reads path, polygon, polyline, line, rect, circle and ellipse elements with the transforms of their groups.
+    /// coordinates are user units of the document with y axis pointing downwards (see Svg::flip_y)
+    pub fn parse(content : &str, tolerance : f64) -> io::Result<Svg> {
+        if tolerance <= 0f64 {
+            return Err(Svg::invalid("Svg.parse", "tolerance has to be positive"))
+        }
+
+        let mut paths = Vec::new();
+
+        // transforms of open elements
+        let mut transforms = vec![Transform::identity()];
+
+        // depth of open hidden elements
+        let mut hidden = 0;
+
+        for event in ::svg::read(content)? {
+            let (name, kind, attributes) = match event {
+                Event::Error(err) => return Err(Svg::invalid("Svg.parse", &err.to_string())),
+                Event::Tag(name, kind, attributes) => (name, kind, attributes),
+                _ => continue
+            };
+
+            if kind == Type::End {
+                transforms.pop();
+
+                if hidden > 0 {
+                    hidden -= 1;
+                }
+
+                continue;
+            }
+
+            let parent = *transforms.last().unwrap_or(&Transform::identity());
+
+            let transform = match attributes.get("transform") {
+                None => parent,
+                Some(text) => parent.then(&Transform::parse(text)?)
+            };
+
+            if kind == Type::Start {
+                transforms.push(transform);
+
+                if hidden > 0 || HIDDEN_ELEMENTS.contains(&name) {
+                    hidden += 1;
+                }
+            }
+
+            if hidden > 0 || HIDDEN_ELEMENTS.contains(&name) {
+                continue;
+            }
+
+            let attr = |key : &str| attributes.get(key).map(|val| val.to_string());
+
+            let num = |key : &str| -> io::Result<f64> {
+                match attributes.get(key) {
+                    None => Ok(0f64),
+                    Some(val) => Ok(DataReader::new(val).number()?.unwrap_or(0f64))
+                }
+            };
+
+            // tolerance in element coordinates
+            let local_tolerance = match transform.scale() > 0f64 {
+                false => tolerance,
+                true  => tolerance / transform.scale()
+            };
+
+            let subpaths = match name {
+                "path" => match attr("d") {
+                    None => Vec::new(),
+                    Some(data) => Svg::parse_path_data(&data, local_tolerance)?
+                },
+                "polygon" | "polyline" => match attr("points") {
+                    None => Vec::new(),
+                    Some(points) => vec![(Svg::parse_points(&points)?, name == "polygon")]
+                },
+                "line" => vec![(vec![(num("x1")?, num("y1")?), (num("x2")?, num("y2")?)], false)],
+                "rect" => {
+                    let (x, y) = (num("x")?, num("y")?);
+                    let (w, h) = (num("width")?, num("height")?);
+
+                    vec![(vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h)], true)]
+                },
+                "circle" => {
+                    let r = num("r")?;
+                    vec![(Svg::ellipse((num("cx")?, num("cy")?), r, r, local_tolerance), true)]
+                },
+                "ellipse" => vec![(Svg::ellipse((num("cx")?, num("cy")?), num("rx")?, num("ry")?, local_tolerance), true)],
+                _ => Vec::new()
+            };
+
+            for (vertices, closed) in subpaths {
+                if vertices.len() < 2 {
+                    continue;
+                }
+
+                let vertices = vertices.into_iter().map(|vertex| transform.apply(vertex)).collect();
+
+                paths.push(SvgPath{id : attr("id"), vertices : vertices, closed : closed});
+            }
+        }
+
+        Ok(Svg{paths : paths})
+    }
+
+    pub fn paths(&self) -> &Vec<SvgPath> {
+        &self.paths
+    }
+
+    /// mirror all paths at x axis to get coordinates with y axis pointing upwards
+    pub fn flip_y(&self) -> Svg {
+        let paths = self.paths.iter()
+            .map(|path| {
+                let vertices = path.vertices.iter().map(|vertex| (vertex.x(), 0f64 - vertex.y())).collect();
+                SvgPath{id : path.id.clone(), vertices : vertices, closed : path.closed}
+            })
+            .collect();
+
+        Svg{paths : paths}
+    }
+
+    /// polygons of all closed paths
+    pub fn polys(&self) -> Vec<Poly<Vect<f64>>> {
+        self.paths.iter()
+            .filter(|path| path.closed && path.vertices.len() >= 3)
+            .map(|path| Poly::new(path.vertices.clone()))
+            .collect()
+    }
+
+    /// indexed segment mesh of all paths where every path has its own vertices
+    pub fn ind_seg_mesh(&self) -> IndSegMesh<Vect<f64>> {
+        let mut vertices = Vec::new();
+        let mut segments = Vec::new();
+
+        for path in &self.paths {
+            let offset = vertices.len();
+            let len    = path.vertices.len();
+
+            vertices.extend(path.vertices.iter().copied());
+
+            for index in 0..len - 1 {
+                segments.push(IndSeg::new(offset + index, offset + index + 1));
+            }
+
+            if path.closed && len > 2 {
+                segments.push(IndSeg::new(offset + len - 1, offset));
+            }
+        }
+
+        IndSegMesh::new_unchecked(vertices, segments)
+    }
+
+    // Parse methods
+
+    fn invalid(func : &str, msg : &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("tg.file.svg.{}: {}", func, msg))
+    }
+
+    fn parse_points(points : &str) -> io::Result<Vec<Vect<f64>>> {
+        let mut reader   = DataReader::new(points);
+        let mut vertices = Vec::new();
+
+        while let Some(x) = reader.number()? {
+            let y = reader.expect()?;
+            vertices.push((x, y));
+        }
+
+        Ok(vertices)
+    }
+
+    /// parse path data into flattened subpaths
+    fn parse_path_data(data : &str, tolerance : f64) -> io::Result<Vec<(Vec<Vect<f64>>, bool)>> {
+        let mut reader   = DataReader::new(data);
+        let mut subpaths = Vec::new();
+
+        let mut current  = Vec::new();
+        let mut pnt      = (0f64, 0f64);
+        let mut start    = (0f64, 0f64);
+
+        // last control point of curve for reflection by smooth curve commands
+        let mut control : Option<(char, Vect<f64>)> = None;
+
+        let mut command = None;
+
+        while !reader.done() {
+            let pos = reader.pos;
+
+            let next = match reader.command() {
+                Some(next) => next,
+                None => match command {
+                    // coordinates after move are implicit line commands
+                    Some('M') => 'L',
+                    Some('m') => 'l',
+                    // close path takes no coordinates so it can not be repeated implicitly
+                    Some('Z') | Some('z') => return Err(Svg::invalid("Svg.parse_path_data", &format!("coordinates after close path in \"{}\"", data))),
+                    Some(command) => command,
+                    None => return Err(Svg::invalid("Svg.parse_path_data", &format!("missing command in \"{}\"", data)))
+                }
+            };
+
+            command = Some(next);
+
+            let relative = next.is_ascii_lowercase();
+            let origin   = if relative { pnt } else { (0f64, 0f64) };
+
+            let read = |reader : &mut DataReader| -> io::Result<Vect<f64>> {
+                let x = reader.expect()?;
+                let y = reader.expect()?;
+
+                Ok(origin.add((x, y)))
+            };
+
+            match next.to_ascii_uppercase() {
+                'M' => {
+                    if current.len() > 1 {
+                        subpaths.push((current, false));
+                    }
+
+                    pnt   = read(&mut reader)?;
+                    start = pnt;
+
+                    current = vec![pnt];
+                    control = None;
+                },
+                'L' => {
+                    pnt = read(&mut reader)?;
+                    current.push(pnt);
+                    control = None;
+                },
+                'H' => {
+                    let x = reader.expect()?;
+                    pnt = (if relative { pnt.x() + x } else { x }, pnt.y());
+                    current.push(pnt);
+                    control = None;
+                },
+                'V' => {
+                    let y = reader.expect()?;
+                    pnt = (pnt.x(), if relative { pnt.y() + y } else { y });
+                    current.push(pnt);
+                    control = None;
+                },
+                'C' | 'S' => {
+                    let c1 = match next.to_ascii_uppercase() {
+                        'C' => read(&mut reader)?,
+                        _ => match control {
+                            Some(('C', c)) | Some(('S', c)) => pnt.add(pnt.sub(c)),
+                            _ => pnt
+                        }
+                    };
+
+                    let c2  = read(&mut reader)?;
+                    let end = read(&mut reader)?;
+
+                    current.extend(Bezier::cubic(pnt, c1, c2, end).vertices(tolerance).into_iter().skip(1));
+
+                    pnt     = end;
+                    control = Some((next.to_ascii_uppercase(), c2));
+                },
+                'Q' | 'T' => {
+                    let c = match next.to_ascii_uppercase() {
+                        'Q' => read(&mut reader)?,
+                        _ => match control {
+                            Some(('Q', c)) | Some(('T', c)) => pnt.add(pnt.sub(c)),
+                            _ => pnt
+                        }
+                    };
+
+                    let end = read(&mut reader)?;
+
+                    current.extend(Bezier::quadratic(pnt, c, end).vertices(tolerance).into_iter().skip(1));
+
+                    pnt     = end;
+                    control = Some((next.to_ascii_uppercase(), c));
+                },
+                'A' => {
+                    let rx    = reader.expect()?;
+                    let ry    = reader.expect()?;
+                    let phi   = reader.expect()?;
+                    let large = reader.flag()?;
+                    let sweep = reader.flag()?;
+                    let end   = read(&mut reader)?;
+
+                    Svg::flatten_arc(&mut current, pnt, end, rx, ry, phi, large, sweep, tolerance);
+
+                    pnt     = end;
+                    control = None;
+                },
+                'Z' => {
+                    if current.len() > 1 && current.last().is_some_and(|last| last.equal(start)) {
+                        current.pop();
+                    }
+
+                    if current.len() > 1 {
+                        subpaths.push((current, true));
+                    }
+
+                    pnt     = start;
+                    current = vec![pnt];
+                    control = None;
+                },
+                _ => return Err(Svg::invalid("Svg.parse_path_data", &format!("unknown command {} in \"{}\"", next, data)))
+            }
+
+            // every command consumes at least one token or parsing would never end
+            if reader.pos == pos {
+                return Err(Svg::invalid("Svg.parse_path_data", &format!("unexpected token in \"{}\"", data)))
+            }
+        }
+
+        if current.len() > 1 {
+            subpaths.push((current, false));
+        }
+
+        Ok(subpaths)
+    }
+
+    // Flatten methods
+
+    /// push vertices of elliptical arc without its first point (svg endpoint parameterization)
+    ///
+    /// see https://www.w3.org/TR/SVG/implnote.html#ArcConversionEndpointToCenter
+    #[allow(clippy::too_many_arguments)]
+    fn flatten_arc(vertices : &mut Vec<Vect<f64>>, p0 : Vect<f64>, p1 : Vect<f64>, rx : f64, ry : f64, phi : f64, large : bool, sweep : bool, tolerance : f64) {
+        let mut rx = rx.abs();
+        let mut ry = ry.abs();
+
+        if p0.equal(p1) {
+            return
+        }
+
+        if rx == 0f64 || ry == 0f64 {
+            vertices.push(p1);
+            return
+        }
+
+        let (sin, cos) = phi.to_radians().sin_cos();
+
+        let dx = (p0.x() - p1.x()) / 2f64;
+        let dy = (p0.y() - p1.y()) / 2f64;
+
+        let x1 =  cos * dx + sin * dy;
+        let y1 = -sin * dx + cos * dy;
+
+        // scale up radii that are too small to reach p1
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+
+        if lambda > 1f64 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+
+        let mut coef = (num / den).max(0f64).sqrt();
+
+        if large == sweep {
+            coef = -coef;
+        }
+
+        let cx1 =  coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        let cx = cos * cx1 - sin * cy1 + (p0.x() + p1.x()) / 2f64;
+        let cy = sin * cx1 + cos * cy1 + (p0.y() + p1.y()) / 2f64;
+
+        let angle = |ux : f64, uy : f64, vx : f64, vy : f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+
+        let theta = angle(1f64, 0f64, (x1 - cx1) / rx, (y1 - cy1) / ry);
+        let mut delta = angle((x1 - cx1) / rx, (y1 - cy1) / ry, (-x1 - cx1) / rx, (-y1 - cy1) / ry);
+
+        if !sweep && delta > 0f64 {
+            delta -= 2f64 * std::f64::consts::PI;
+        }
+        else if sweep && delta < 0f64 {
+            delta += 2f64 * std::f64::consts::PI;
+        }
+
+        let steps = steps(rx.max(ry), tolerance, delta, 1);
+
+        for step in 1..=steps {
+            let t = theta + delta * step as f64 / steps as f64;
+
+            let x = cx + rx * t.cos() * cos - ry * t.sin() * sin;
+            let y = cy + rx * t.cos() * sin + ry * t.sin() * cos;
+
+            vertices.push((x, y));
+        }
+
+        // end exactly at p1
+        vertices.pop();
+        vertices.push(p1);
+    }
+
+    /// vertices of closed ellipse with center and radii
+    fn ellipse(center : Vect<f64>, rx : f64, ry : f64, tolerance : f64) -> Vec<Vect<f64>> {
+        let steps = steps(rx.abs().max(ry.abs()), tolerance, 2f64 * std::f64::consts::PI, 3);
+
+        (0..steps)
+            .map(|step| {
+                let t = 2f64 * std::f64::consts::PI * step as f64 / steps as f64;
+                (center.x() + rx * t.cos(), center.y() + ry * t.sin())
+            })
+            .collect()
+    }
+}
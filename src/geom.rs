@@ -4,4 +4,7 @@ pub mod d2;
 /// module for 3D geometry
 pub mod d3;
 
+/// module for least squares fits of circles and spheres
+pub mod fit;
+
 pub mod mesh;
\ No newline at end of file
@@ -1,11 +1,38 @@
+/// module for rotating calipers of 2D point sets
+pub mod calipers;
+
+/// module for boolean operations of 2D polygons
+pub mod clip;
+
+/// module for closed 2D contours
+pub mod contour;
+
+/// module for convex hulls of 2D point sets
+pub mod hull;
+
+/// module for intersections of 2D segment sets
+pub mod intsec;
+
 /// module for 2D primitives
 pub mod prim;
 
 /// module for 2D meshes 
 pub mod mesh;
 
+/// module for offsetting 2D polygons
+pub mod offset;
+
 /// module for 2D polygons
 pub mod poly;
 
+/// module for simplification of closed 2D contours
+pub mod simplify;
+
+/// module for smoothing and resampling of closed 2D contours
+pub mod smooth;
+
 /// module for 2D objects
-pub mod shape;
\ No newline at end of file
+pub mod shape;
+
+/// module for triangulation of 2D polygons and point sets
+pub mod triangulate;
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::geom::d2::hull;
+
+use crate::geom::d2::prim::rect::{
+    PRect
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Float
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// enclosing rectangle of points with minimal area
+///
+/// the rectangle is counterclockwise with its first edge on an edge of the convex hull;
+/// None if there are no points and degenerate rectangles for equal or collinear points
+pub fn min_area_rect<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Option<PRect<V::Own>>
+where V::Val : Float + HPOrd
+{
+    calipers(pnts, |width, height| width * height)
+}
+
+/// enclosing rectangle of points with minimal width (distance between the closest pair of parallel supporting lines)
+///
+/// the rectangle is counterclockwise with its first edge on an edge of the convex hull and ad() along the width;
+/// None if there are no points and degenerate rectangles for equal or collinear points
+pub fn min_width_rect<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Option<PRect<V::Own>>
+where V::Val : Float + HPOrd
+{
+    calipers(pnts, |_, height| height)
+}
+
+/// enclosing rectangle with edge on convex hull edge that minimizes cost of its width along and height across the edge
+///
+/// for every hull edge the calipers at the farthest points along, against and across the edge only move forward
+fn calipers<V : Vector, I : IntoIterator<Item = V>, F : Fn(V::Val, V::Val) -> V::Val>(pnts : I, cost : F) -> Option<PRect<V::Own>>
+where V::Val : Float + HPOrd
+{
+    let hull = hull::monotone_chain(pnts);
+    let len = hull.len();
+
+    match len {
+        0 => return None,
+        1 => return Some(PRect::new_unchecked(V::of(&hull[0]), V::of(&hull[0]), V::of(&hull[0]), V::of(&hull[0]))),
+        2 => return Some(PRect::new_unchecked(V::of(&hull[0]), V::of(&hull[1]), V::of(&hull[1]), V::of(&hull[0]))),
+        _ => {}
+    }
+
+    let dir = |index : usize| {
+        let edge = hull[(index + 1) % len].sub(&hull[index]);
+        edge.vdiv(edge.len())
+    };
+
+    // calipers of largest projection along the edge, across the edge and against the edge
+    let mut right = 1;
+    let mut top   = 1;
+    let mut left  = 1;
+
+    let mut best : Option<(V::Val, PRect<V::Own>)> = None;
+
+    for index in 0..len {
+        let origin = V::of(&hull[index]);
+
+        let u = dir(index);
+        let v = u.orth_l();
+
+        let along  = |pnt : usize| hull[pnt].sub(&origin).dot(&u);
+        let across = |pnt : usize| hull[pnt].sub(&origin).dot(&v);
+
+        if index == 0 {
+            left = right;
+        }
+
+        while along((right + 1) % len) >= along(right) && (right + 1) % len != index {
+            right = (right + 1) % len;
+        }
+
+        if index == 0 {
+            top = right;
+        }
+
+        while across((top + 1) % len) >= across(top) && (top + 1) % len != index {
+            top = (top + 1) % len;
+        }
+
+        if index == 0 {
+            left = top;
+        }
+
+        while along((left + 1) % len) <= along(left) && left != index {
+            left = (left + 1) % len;
+        }
+
+        let max = along(right);
+        let min = along(left);
+
+        let height = across(top);
+        let cost = cost(max - min, height);
+
+        if best.as_ref().is_some_and(|(best, _)| *best <= cost) {
+            continue;
+        }
+
+        let a = origin.add(u.vmul(min));
+        let b = origin.add(u.vmul(max));
+        let c = b.add(v.vmul(height));
+        let d = a.add(v.vmul(height));
+
+        best = Some((cost, PRect::new_unchecked(a, b, c, d)));
+    }
+
+    best.map(|(_, rect)| rect)
+}
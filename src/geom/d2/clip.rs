@@ -0,0 +1,553 @@
+use std::collections::{
+    HashMap
+};
+
+use std::f64::consts::{
+    PI
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::contour;
+
+use crate::geom::d2::intsec::{
+    Snap
+};
+
+use crate::geom::d2::poly::{
+    MultiPoly,
+    Poly,
+    PolyWithHoles
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// boolean operation on the regions of subject and clip polygons
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoolOp {
+    Union,
+    Intersection,
+    /// subject without clip
+    Difference,
+    Xor
+}
+
+/// rule which decides from the winding number of contours around a point if the point is inside
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    EvenOdd,
+    NonZero,
+    /// inside if counterclockwise contours wind more often around the point than clockwise ones
+    Positive
+}
+
+/// points closer than this fraction of the extent of all vertices are merged
+const SNAP : f64 = 1e-9;
+
+/// maximal number of edge midpoints of a hole tested for containment in outer contours
+const HOLE_SAMPLES : usize = 8;
+
+/// sines of angles between segments below this are treated as parallel
+const PARALLEL : f64 = 1e-12;
+
+/// boolean operation of regions enclosed by closed contours with even-odd rule (outer contours and holes of any orientation)
+///
+/// the contours are overlaid as planar graph where all edges are split at intersections and collinear overlaps are merged,
+/// both sides of every edge are classified by the winding numbers of a ray and the edges between inside and outside are traced to counterclockwise
+/// outer contours and clockwise holes, so that shared edges vanish and regions touching at vertices stay separate polygons
+pub fn boolean<A : Vector, B : Vector>(subject : &[&[A]], clip : &[&[B]], op : BoolOp) -> MultiPoly<Vect<f64>>
+where A::Val : Cast<f64>,
+      B::Val : Cast<f64>
+{
+    let mut segments = Vec::new();
+
+    push_contours(&mut segments, subject, Operand::Subject);
+    push_contours(&mut segments, clip, Operand::Clip);
+
+    overlay(&segments, |(subject, clip)| op.apply(Winding::EvenOdd.inside(subject), Winding::EvenOdd.inside(clip)))
+}
+
+/// region of possibly self-intersecting and overlapping closed contours with winding rule as disjoint polygons with holes
+pub fn resolve<V : Vector>(contours : &[&[V]], winding : Winding) -> MultiPoly<Vect<f64>>
+where V::Val : Cast<f64>
+{
+    let mut segments = Vec::new();
+
+    push_contours(&mut segments, contours, Operand::Subject);
+
+    overlay(&segments, |(subject, _)| winding.inside(subject))
+}
+
+/// trace region of segments where inside decides from the winding numbers of subject and clip if a point is inside
+fn overlay<F : Fn((i32, i32)) -> bool>(segments : &[(Vect<f64>, Vect<f64>, Operand)], inside : F) -> MultiPoly<Vect<f64>> {
+    let extent = segments.iter().fold(1f64, |extent, (a, b, _)| {
+        extent.max(a.x().abs()).max(a.y().abs()).max(b.x().abs()).max(b.y().abs())
+    });
+
+    let mut graph = Graph::new(SNAP * extent);
+
+    graph.overlay(segments);
+
+    let rings = graph.rings(inside);
+
+    assemble(rings)
+}
+
+/// edges grouped into equal bands of one coordinate where every edge is in all bands its coordinate interval overlaps
+struct Bands {
+    start : f64,
+    size  : f64,
+    bands : Vec<Vec<usize>>
+}
+
+/// polygon a given operand belongs to
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Subject,
+    Clip
+}
+
+/// planar graph of merged vertices and undirected edges with the number of subject and clip edges lying on them
+/// (counted negative for edges from the larger to the smaller vertex index)
+struct Graph {
+    snap  : Snap,
+    edges : HashMap<(usize, usize), (i32, i32)>
+}
+
+impl Graph {
+    fn new(eps : f64) -> Graph {
+        Graph{snap : Snap::new(eps), edges : HashMap::new()}
+    }
+
+    /// split segments at each other and add the pieces to the graph
+    fn overlay(&mut self, segments : &[(Vect<f64>, Vect<f64>, Operand)]) {
+        let mut splits = segments.iter().map(|(a, b, _)| vec![(0f64, *a), (1f64, *b)]).collect::<Vec<_>>();
+
+        // sweep over segments sorted by their minimal x value
+        let mut order = (0..segments.len()).collect::<Vec<_>>();
+        order.sort_by(|i, j| {
+            let i = segments[*i].0.x().min(segments[*i].1.x());
+            let j = segments[*j].0.x().min(segments[*j].1.x());
+            i.total_cmp(&j)
+        });
+
+        for (pos, &i) in order.iter().enumerate() {
+            let (a, b, _) = segments[i];
+            let max_x = a.x().max(b.x()) + self.snap.eps();
+
+            for &j in &order[(pos + 1)..] {
+                let (c, d, _) = segments[j];
+
+                if c.x().min(d.x()) > max_x {
+                    break;
+                }
+
+                if c.y().max(d.y()) + self.snap.eps() < a.y().min(b.y()) || c.y().min(d.y()) - self.snap.eps() > a.y().max(b.y()) {
+                    continue;
+                }
+
+                for (t, u, pnt) in self.intersections(a, b, c, d) {
+                    if let Some(t) = t {
+                        splits[i].push((t, pnt));
+                    }
+
+                    if let Some(u) = u {
+                        splits[j].push((u, pnt));
+                    }
+                }
+            }
+        }
+
+        for (index, mut points) in splits.into_iter().enumerate() {
+            points.sort_by(|p, q| p.0.total_cmp(&q.0));
+
+            let indices = points.iter().map(|(_, pnt)| self.snap.vertex(*pnt)).collect::<Vec<_>>();
+
+            for pair in indices.windows(2) {
+                if pair[0] == pair[1] {
+                    continue;
+                }
+
+                let key   = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+                let entry = self.edges.entry(key).or_insert((0, 0));
+
+                let count = match pair[0] < pair[1] {
+                    true  => 1,
+                    false => -1
+                };
+
+                match segments[index].2 {
+                    Operand::Subject => entry.0 += count,
+                    Operand::Clip    => entry.1 += count
+                }
+            }
+        }
+
+        // edges of opposite contours cancel out
+        self.edges.retain(|_, count| *count != (0, 0));
+    }
+
+    /// split parameters on ab (t) and cd (u) with split point of crossings, touchings and collinear overlaps
+    fn intersections(&self, a : Vect<f64>, b : Vect<f64>, c : Vect<f64>, d : Vect<f64>) -> Vec<(Option<f64>, Option<f64>, Vect<f64>)> {
+        let mut result = Vec::new();
+
+        let ab = b.sub(a);
+        let cd = d.sub(c);
+        let ac = c.sub(a);
+
+        let len_ab = ab.len();
+        let len_cd = cd.len();
+
+        let denom = ab.det(cd);
+
+        if denom.abs() <= PARALLEL * len_ab * len_cd {
+            // parallel segments only touch if they are collinear
+            if ac.det(ab).abs() / len_ab > self.snap.eps() {
+                return result
+            }
+
+            for (pnt, on_cd) in [(c, true), (d, true), (a, false), (b, false)] {
+                let (start, dir, len) = match on_cd {
+                    true  => (a, ab, len_ab),
+                    false => (c, cd, len_cd)
+                };
+
+                let param = pnt.sub(start).dot(dir) / (len * len);
+
+                if param * len > self.snap.eps() && (1f64 - param) * len > self.snap.eps() {
+                    match on_cd {
+                        true  => result.push((Some(param), None, pnt)),
+                        false => result.push((None, Some(param), pnt))
+                    }
+                }
+            }
+
+            return result
+        }
+
+        let t = ac.det(cd) / denom;
+        let u = ac.det(ab) / denom;
+
+        let tol_t = self.snap.eps() / len_ab;
+        let tol_u = self.snap.eps() / len_cd;
+
+        if t < -tol_t || t > 1f64 + tol_t || u < -tol_u || u > 1f64 + tol_u {
+            return result
+        }
+
+        // prefer exact end points of touching segments
+        let pnt = if u <= tol_u {
+            c
+        }
+        else if u >= 1f64 - tol_u {
+            d
+        }
+        else if t <= tol_t {
+            a
+        }
+        else if t >= 1f64 - tol_t {
+            b
+        }
+        else {
+            a.add(ab.vmul(t))
+        };
+
+        result.push((Some(t.clamp(0f64, 1f64)), Some(u.clamp(0f64, 1f64)), pnt));
+        result
+    }
+
+    /// directed edges between inside and outside with the inside on their left
+    ///
+    /// the rays cast from the edge midpoints only test the edges of the band of rows or columns they lie in
+    fn boundary<F : Fn((i32, i32)) -> bool>(&self, inside : F) -> Vec<(usize, usize)> {
+        let edges = self.edges.iter().map(|(key, count)| (*key, *count)).collect::<Vec<_>>();
+
+        let ends = edges.iter().map(|((i, j), _)| (self.snap.vertices()[*i], self.snap.vertices()[*j])).collect::<Vec<_>>();
+
+        let rows = Bands::new(ends.iter().map(|(a, b)| (a.y(), b.y())));
+        let cols = Bands::new(ends.iter().map(|(a, b)| (a.x(), b.x())));
+
+        let mut boundary = Vec::new();
+
+        for (index, ((i, j), (subject, clip))) in edges.iter().enumerate() {
+            let (a, b) = ends[index];
+            let mid = a.add(b).vmul(0.5f64);
+
+            // cast ray from midpoint along the axis the edge is least aligned with
+            let horizontal = (b.y() - a.y()).abs() >= (b.x() - a.x()).abs();
+
+            let candidates = match horizontal {
+                true  => rows.get(mid.y()),
+                false => cols.get(mid.x())
+            };
+
+            let mut winding = (0, 0);
+
+            for other in candidates {
+                if *other == index {
+                    continue;
+                }
+
+                let (c, d) = ends[*other];
+                let (_, (other_subject, other_clip)) = edges[*other];
+
+                // counterclockwise contours cross rays in positive x direction upwards and rays in positive y direction leftwards
+                let sign = match horizontal {
+                    true if (c.y() > mid.y()) != (d.y() > mid.y()) && c.x() + (mid.y() - c.y()) * (d.x() - c.x()) / (d.y() - c.y()) > mid.x() => {
+                        match d.y() > c.y() {
+                            true  => 1,
+                            false => -1
+                        }
+                    },
+                    false if (c.x() > mid.x()) != (d.x() > mid.x()) && c.y() + (mid.x() - c.x()) * (d.y() - c.y()) / (d.x() - c.x()) > mid.y() => {
+                        match d.x() < c.x() {
+                            true  => 1,
+                            false => -1
+                        }
+                    },
+                    _ => continue
+                };
+
+                winding.0 += sign * other_subject;
+                winding.1 += sign * other_clip;
+            }
+
+            // the ray side is right of the edge direction for upward edges (horizontal ray) and leftward edges (vertical ray)
+            let ray_right = match horizontal {
+                true  => b.y() > a.y(),
+                false => b.x() < a.x()
+            };
+
+            // winding numbers left of an edge are larger by the number of contours along its direction
+            let (left, right) = match ray_right {
+                true  => ((winding.0 + subject, winding.1 + clip), winding),
+                false => (winding, (winding.0 - subject, winding.1 - clip))
+            };
+
+            let left  = inside(left);
+            let right = inside(right);
+
+            if left != right {
+                match left {
+                    true  => boundary.push((*i, *j)),
+                    false => boundary.push((*j, *i))
+                }
+            }
+        }
+
+        boundary
+    }
+
+    /// closed rings of boundary edges where every ring turns as far clockwise as possible at vertices with several edges
+    fn rings<F : Fn((i32, i32)) -> bool>(&self, inside : F) -> Vec<Vec<Vect<f64>>> {
+        let boundary = self.boundary(inside);
+
+        let mut outgoing = vec![Vec::new(); self.snap.vertices().len()];
+
+        for (index, (from, _)) in boundary.iter().enumerate() {
+            outgoing[*from].push(index);
+        }
+
+        let mut used  = vec![false; boundary.len()];
+        let mut rings = Vec::new();
+
+        for start in 0..boundary.len() {
+            if used[start] {
+                continue;
+            }
+
+            let mut ring    = Vec::new();
+            let mut current = start;
+            let mut closed  = false;
+
+            loop {
+                used[current] = true;
+
+                let (from, to) = boundary[current];
+                ring.push(self.snap.vertices()[from]);
+
+                if to == boundary[start].0 {
+                    closed = true;
+                    break;
+                }
+
+                let back = self.snap.vertices()[from].sub(self.snap.vertices()[to]);
+
+                // smallest clockwise angle from the direction back along the incoming edge
+                let next = outgoing[to].iter().filter(|edge| !used[**edge]).map(|edge| {
+                    let dir   = self.snap.vertices()[boundary[*edge].1].sub(self.snap.vertices()[to]);
+                    let angle = -back.det(dir).atan2(back.dot(dir));
+
+                    match angle <= 0f64 {
+                        true  => (*edge, angle + 2f64 * PI),
+                        false => (*edge, angle)
+                    }
+                }).min_by(|p, q| p.1.total_cmp(&q.1));
+
+                match next {
+                    Some((edge, _)) => current = edge,
+                    None            => break
+                }
+            }
+
+            if closed {
+                rings.push(simplify(ring, self.snap.eps()));
+            }
+        }
+
+        rings.retain(|ring| ring.len() >= 3);
+        rings
+    }
+}
+
+impl Bands {
+    /// bands of about one edge each over the range of all edge intervals
+    fn new<I : ExactSizeIterator<Item = (f64, f64)>>(intervals : I) -> Bands {
+        let count     = intervals.len().max(1);
+        let intervals = intervals.map(|(a, b)| (a.min(b), a.max(b))).collect::<Vec<_>>();
+
+        let start = intervals.iter().fold(f64::INFINITY, |start, (min, _)| start.min(*min));
+        let end   = intervals.iter().fold(f64::NEG_INFINITY, |end, (_, max)| end.max(*max));
+
+        let size = match end > start {
+            true  => (end - start) / count as f64,
+            false => 1f64
+        };
+
+        let mut bands = Bands{start : start, size : size, bands : vec![Vec::new(); count]};
+
+        for (index, (min, max)) in intervals.iter().enumerate() {
+            for band in bands.band(*min)..=bands.band(*max) {
+                bands.bands[band].push(index);
+            }
+        }
+
+        bands
+    }
+
+    /// index of band containing value clamped to the bands
+    fn band(&self, val : f64) -> usize {
+        (((val - self.start) / self.size).floor().max(0f64) as usize).min(self.bands.len() - 1)
+    }
+
+    /// indices of edges whose interval may contain value
+    fn get(&self, val : f64) -> &[usize] {
+        &self.bands[self.band(val)]
+    }
+}
+
+impl BoolOp {
+    fn apply(&self, subject : bool, clip : bool) -> bool {
+        match self {
+            BoolOp::Union        => subject || clip,
+            BoolOp::Intersection => subject && clip,
+            BoolOp::Difference   => subject && !clip,
+            BoolOp::Xor          => subject != clip
+        }
+    }
+}
+
+impl Winding {
+    fn inside(&self, winding : i32) -> bool {
+        match self {
+            Winding::EvenOdd  => winding % 2 != 0,
+            Winding::NonZero  => winding != 0,
+            Winding::Positive => winding > 0
+        }
+    }
+}
+
+fn push_contours<V : Vector>(segments : &mut Vec<(Vect<f64>, Vect<f64>, Operand)>, contours : &[&[V]], operand : Operand)
+where V::Val : Cast<f64>
+{
+    for contour in contours {
+        let len = contour.len();
+
+        for index in 0..len {
+            let a = &contour[index];
+            let b = &contour[(index + 1) % len];
+
+            let a = (a.x().cast(), a.y().cast());
+            let b = (b.x().cast(), b.y().cast());
+
+            if !a.equal(b) {
+                segments.push((a, b, operand));
+            }
+        }
+    }
+}
+
+/// remove vertices between collinear edges pointing in the same direction
+fn simplify(ring : Vec<Vect<f64>>, eps : f64) -> Vec<Vect<f64>> {
+    let len = ring.len();
+
+    let mut result = Vec::with_capacity(len);
+
+    for index in 0..len {
+        let prev = ring[(index + len - 1) % len];
+        let pnt  = ring[index];
+        let next = ring[(index + 1) % len];
+
+        let a = pnt.sub(prev);
+        let b = next.sub(pnt);
+
+        let collinear = a.det(b).abs() <= eps * (a.len() + b.len()) && a.dot(b) > 0f64;
+
+        if !collinear {
+            result.push(pnt);
+        }
+    }
+
+    result
+}
+
+/// assign clockwise holes to the smallest counterclockwise outer contour containing them
+///
+/// holes touching their outer contour are tested at several edge midpoints and holes outside of all outer contours
+/// (only possible by rounding) are assigned to the nearest outer contour
+fn assemble(rings : Vec<Vec<Vect<f64>>>) -> MultiPoly<Vect<f64>> {
+    let mut outers = Vec::new();
+    let mut holes  = Vec::new();
+
+    for ring in rings {
+        let poly = Poly::new(ring);
+
+        match poly.signed_area() > 0f64 {
+            true  => outers.push((poly.signed_area(), poly, Vec::new())),
+            false => holes.push(poly)
+        }
+    }
+
+    for hole in holes {
+        let vertices = hole.vertices();
+        let len = vertices.len();
+
+        let step = (len / HOLE_SAMPLES).max(1);
+        let pnts = (0..len).step_by(step).map(|index| vertices[index].add(vertices[(index + 1) % len]).vmul(0.5f64)).collect::<Vec<_>>();
+
+        let containing = outers.iter()
+            .enumerate()
+            .filter(|(_, (_, outer, _))| pnts.iter().any(|pnt| outer.contains_even_odd(*pnt)))
+            .min_by(|(_, p), (_, q)| p.0.total_cmp(&q.0))
+            .map(|(index, _)| index);
+
+        let nearest = || outers.iter()
+            .enumerate()
+            .filter_map(|(index, (_, outer, _))| contour::dist(outer.vertices(), vertices[0]).map(|dist| (index, dist)))
+            .min_by(|p, q| p.1.total_cmp(&q.1))
+            .map(|(index, _)| index);
+
+        if let Some(index) = containing.or_else(nearest) {
+            outers[index].2.push(hole);
+        }
+    }
+
+    let polys = outers.into_iter().map(|(_, outer, holes)| PolyWithHoles::new(outer, holes)).collect();
+
+    MultiPoly::new(polys)
+}
@@ -0,0 +1,188 @@
+use std::vec::{
+    Vec
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::prim::seg::{
+    PSeg,
+    Segment
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    Two,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// closed contour edges as point segments where the last edge connects the last and the first vertex
+pub fn edges<V : Vector>(contour : &[V]) -> impl Iterator<Item = PSeg<V::Own>> + '_ {
+    let len = contour.len();
+
+    (0..len).map(move |index| {
+        let a = V::of(&contour[index]);
+        let b = V::of(&contour[(index + 1) % len]);
+
+        PSeg::new(a, b)
+    })
+}
+
+/// length of closed contour
+pub fn len<V : Vector>(contour : &[V]) -> V::Val
+where V::Val : Float
+{
+    let mut len = V::Val::zero();
+
+    for edge in edges(contour) {
+        len = len + edge.ab().len();
+    }
+
+    len
+}
+
+/// signed area of closed contour with shoelace formula (positive if counterclockwise)
+pub fn signed_area<V : Vector>(contour : &[V]) -> V::Val
+where V::Val : Zero + Two + HAdd + HSub + HMul + HDiv
+{
+    let mut sum = V::Val::zero();
+
+    for edge in edges(contour) {
+        sum = sum + edge.a().det(edge.b());
+    }
+
+    sum / V::Val::two()
+}
+
+/// distance of point to closed contour
+pub fn dist<V : Vector, P : Vector<Val = V::Val>>(contour : &[V], pnt : P) -> Option<V::Val>
+where V::Val : Float + HPOrd
+{
+    let mut min = None;
+
+    for edge in edges(contour) {
+        let dist = edge.dist(&pnt);
+
+        min = match min {
+            None => Some(dist),
+            Some(min) => Some(dist.min(min))
+        }
+    }
+
+    min
+}
+
+/// hausdorff distance between two closed contours evaluated at the vertices of both contours
+pub fn hausdorff<A : Vector, B : Vector<Val = A::Val>>(a : &[A], b : &[B]) -> Option<A::Val>
+where A::Val : Float + HPOrd
+{
+    let mut max = A::Val::zero();
+
+    for vertex in a {
+        max = max.max(dist(b, vertex)?);
+    }
+
+    for vertex in b {
+        max = max.max(dist(a, vertex)?);
+    }
+
+    Some(max)
+}
+
+/// resample closed contour to count vertices with uniform arc length spacing starting at first vertex
+pub fn resample<V : Vector>(contour : &[V], count : usize) -> Vec<V::Own>
+where V::Val : Float + HPOrd,
+      usize  : Cast<V::Val>
+{
+    let mut resampled = Vec::with_capacity(count);
+
+    let total = len(contour);
+
+    if contour.is_empty() || count == 0 || total <= V::Val::zero() {
+        return resampled
+    }
+
+    let step = total / count.cast();
+
+    // arc length at start of current edge
+    let mut offset = V::Val::zero();
+
+    for edge in edges(contour) {
+        let edge_len = edge.ab().len();
+
+        while resampled.len() < count {
+            let target = step * resampled.len().cast();
+
+            if target > offset + edge_len {
+                break;
+            }
+
+            let r = match edge_len > V::Val::zero() {
+                false => V::Val::zero(),
+                true  => (target - offset) / edge_len
+            };
+
+            resampled.push(edge.a().add(edge.ab().vmul(r)));
+        }
+
+        offset = offset + edge_len;
+    }
+
+    // rounding errors can leave the last vertices unassigned
+    while resampled.len() < count {
+        resampled.push(V::of(&contour[0]));
+    }
+
+    resampled
+}
+
+/// cyclic shift of contour that minimizes the squared distances to the vertices of reference contour
+pub fn align<R : Vector, V : Vector<Val = R::Val>>(reference : &[R], contour : &[V]) -> Vec<V::Own>
+where V::Val : Zero + HAdd + HSub + HMul + HPOrd
+{
+    align_all(&[reference], contour)
+}
+
+/// cyclic shift of contour that minimizes the summed squared distances to the vertices of all reference contours
+pub fn align_all<R : Vector, V : Vector<Val = R::Val>>(references : &[&[R]], contour : &[V]) -> Vec<V::Own>
+where V::Val : Zero + HAdd + HSub + HMul + HPOrd
+{
+    let len = contour.len();
+    let mut best = None;
+
+    for shift in 0..len {
+        let mut sum = V::Val::zero();
+
+        for reference in references {
+            for (index, vertex) in reference.iter().enumerate().take(len) {
+                let diff = vertex.sub(&contour[(index + shift) % len]);
+                sum = sum + diff.dot(&diff);
+            }
+        }
+
+        best = match best {
+            None => Some((shift, sum)),
+            Some((best_shift, best_sum)) => if best_sum <= sum { Some((best_shift, best_sum)) } else { Some((shift, sum)) }
+        }
+    }
+
+    let shift = best.map_or(0, |(shift, _)| shift);
+
+    (0..len).map(|index| V::of(&contour[(index + shift) % len])).collect()
+}
@@ -0,0 +1,138 @@
+use std::cmp::{
+    Ordering
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero
+};
+
+use crate::ops::{
+    HSub,
+    HMul
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// convex hull of points with andrew's monotone chain algorithm
+///
+/// the hull is counterclockwise starting at the point with smallest x (and smallest y) without duplicate and collinear points,
+/// so that it has 1 point for equal points and 2 points for collinear points
+pub fn monotone_chain<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Vec<V::Own>
+where V::Val : Zero + HSub + HMul + HPOrd
+{
+    let pnts = sorted(pnts);
+
+    if pnts.len() < 3 {
+        return pnts
+    }
+
+    let mut lower : Vec<V::Own> = Vec::new();
+
+    for pnt in &pnts {
+        while lower.len() >= 2 && orient(&lower[lower.len() - 2], &lower[lower.len() - 1], pnt) <= V::Val::zero() {
+            lower.pop();
+        }
+
+        lower.push(V::of(pnt));
+    }
+
+    let mut upper : Vec<V::Own> = Vec::new();
+
+    for pnt in pnts.iter().rev() {
+        while upper.len() >= 2 && orient(&upper[upper.len() - 2], &upper[upper.len() - 1], pnt) <= V::Val::zero() {
+            upper.pop();
+        }
+
+        upper.push(V::of(pnt));
+    }
+
+    // last points of both chains are the first points of the other chain
+    lower.pop();
+    upper.pop();
+
+    lower.extend(upper);
+    lower
+}
+
+/// convex hull of points with quickhull algorithm with the same result as monotone_chain
+pub fn quickhull<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Vec<V::Own>
+where V::Val : Zero + HSub + HMul + HPOrd
+{
+    let mut pnts = sorted(pnts);
+
+    if pnts.len() < 3 {
+        return pnts
+    }
+
+    let b = pnts.pop().unwrap();
+    let a = pnts.remove(0);
+
+    let zero = V::Val::zero();
+
+    let below = pnts.iter().filter(|pnt| orient(&a, &b, pnt) < zero).map(V::of).collect::<Vec<_>>();
+    let above = pnts.iter().filter(|pnt| orient(&a, &b, pnt) > zero).map(V::of).collect::<Vec<_>>();
+
+    let mut hull = vec![V::of(&a)];
+    hull.extend(side(&b, &a, below));
+
+    // all points are collinear
+    if hull.len() == 1 && above.is_empty() {
+        return vec![a, b]
+    }
+
+    hull.push(V::of(&b));
+    hull.extend(side(&a, &b, above));
+    hull
+}
+
+/// hull points strictly left of edge from a to b in order from b to a
+fn side<V : Vector<Own = V>>(a : &V, b : &V, pnts : Vec<V>) -> Vec<V>
+where V::Val : Zero + HSub + HMul + HPOrd
+{
+    let Some(far) = (0..pnts.len()).fold(None, |far : Option<usize>, index| {
+        match far {
+            Some(far) if orient(a, b, &pnts[far]) >= orient(a, b, &pnts[index]) => Some(far),
+            _ => Some(index)
+        }
+    }) else {
+        return Vec::new()
+    };
+
+    let far = &pnts[far];
+    let zero = V::Val::zero();
+
+    let first  = pnts.iter().filter(|pnt| orient(a, far, pnt) > zero).map(V::of).collect::<Vec<_>>();
+    let second = pnts.iter().filter(|pnt| orient(far, b, pnt) > zero).map(V::of).collect::<Vec<_>>();
+
+    let mut hull = side(far, b, second);
+    hull.push(V::of(far));
+    hull.extend(side(a, far, first));
+    hull
+}
+
+/// points sorted by x and y without duplicates
+fn sorted<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Vec<V::Own>
+where V::Val : HPOrd
+{
+    let mut pnts = pnts.into_iter().map(V::of).collect::<Vec<_>>();
+
+    pnts.sort_by(|a, b| {
+        a.x().partial_cmp(&b.x()).unwrap_or(Ordering::Equal).then(a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+    });
+
+    pnts.dedup_by(|a, b| a.x() == b.x() && a.y() == b.y());
+    pnts
+}
+
+/// twice the signed area of triangle a b c (positive if counterclockwise)
+pub(crate) fn orient<A : Vector, B : Vector<Val = A::Val>, C : Vector<Val = A::Val>>(a : A, b : B, c : C) -> A::Val
+where A::Val : HSub + HMul
+{
+    b.sub(&a).det(c.sub(&a))
+}
@@ -0,0 +1,333 @@
+use std::collections::{
+    HashMap,
+    HashSet
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::hull::{
+    orient
+};
+
+use crate::geom::d2::prim::seg::{
+    Segment
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::mesh::ind::{
+    IndSeg
+};
+
+/// points closer than this fraction of the largest absolute coordinate are merged when splitting
+const SNAP : f64 = 1e-9;
+
+/// shared part of two intersecting segments
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntsecKind {
+    /// segments cross or touch in one point
+    Point(Vect<f64>),
+    /// collinear segments overlap between two points
+    Overlap(Vect<f64>, Vect<f64>)
+}
+
+/// intersection between segments with indices "a" < "b" of a segment set
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegIntsec {
+    a    : usize,
+    b    : usize,
+    kind : IntsecKind
+}
+
+impl SegIntsec {
+    pub fn a(&self) -> usize {
+        self.a
+    }
+
+    pub fn b(&self) -> usize {
+        self.b
+    }
+
+    pub fn kind(&self) -> IntsecKind {
+        self.kind
+    }
+}
+
+/// all intersections among indexed segments using a uniform grid
+///
+/// crossings, endpoints on segments and collinear overlaps are reported once per pair of segments;
+/// segments sharing a vertex index only intersect if they have more than the shared vertex in common
+pub fn intersections<V : Vector>(pnts : &[V], segments : &[IndSeg]) -> Vec<SegIntsec>
+where V::Val : Cast<f64>
+{
+    let pnts = pnts.iter().map(|pnt| (pnt.x().cast(), pnt.y().cast())).collect::<Vec<Vect<f64>>>();
+
+    candidates(&pnts, segments).into_iter().filter_map(|(a, b)| {
+        let (seg_a, seg_b) = (&segments[a], &segments[b]);
+
+        let kind = intsec(pnts[seg_a.a()], pnts[seg_a.b()], pnts[seg_b.a()], pnts[seg_b.b()])?;
+
+        // adjacent segments touching in their shared vertex
+        if let IntsecKind::Point(pnt) = kind {
+            let mut shared = [seg_a.a(), seg_a.b()].into_iter().filter(|index| seg_b.contains_index(*index));
+
+            if shared.any(|index| pnts[index] == pnt) {
+                return None
+            }
+        }
+
+        Some(SegIntsec{a : a, b : b, kind : kind})
+    }).collect()
+}
+
+/// all intersections among segments (see intersections)
+pub fn segments<S : Segment>(segs : &[S]) -> Vec<SegIntsec>
+where S::Val : Cast<f64>
+{
+    let pnts = segs.iter().flat_map(|seg| seg.pnts()).collect::<Vec<_>>();
+    let segments = (0..segs.len()).map(|index| IndSeg::new(2 * index, 2 * index + 1)).collect::<Vec<_>>();
+
+    intersections(&pnts, &segments)
+}
+
+/// planar graph of indexed segments split at all intersections
+///
+/// points closer than SNAP times the largest absolute coordinate are merged into one vertex, overlapping parts become one segment and zero length segments are removed
+pub fn split<V : Vector>(pnts : &[V], segments : &[IndSeg]) -> (Vec<Vect<f64>>, Vec<IndSeg>)
+where V::Val : Cast<f64>
+{
+    let pnts = pnts.iter().map(|pnt| (pnt.x().cast(), pnt.y().cast())).collect::<Vec<Vect<f64>>>();
+
+    let mut cuts = segments.iter().map(|seg| vec![pnts[seg.a()], pnts[seg.b()]]).collect::<Vec<_>>();
+
+    for intsec in intersections(&pnts, segments) {
+        let shared = match intsec.kind() {
+            IntsecKind::Point(pnt)        => vec![pnt],
+            IntsecKind::Overlap(from, to) => vec![from, to]
+        };
+
+        cuts[intsec.a()].extend(shared.iter().copied());
+        cuts[intsec.b()].extend(shared.iter().copied());
+    }
+
+    let extent = pnts.iter().fold(0f64, |extent, pnt| extent.max(pnt.x().abs()).max(pnt.y().abs()));
+
+    let mut snap = Snap::new(SNAP * extent);
+
+    let mut edges = HashSet::new();
+    let mut split = Vec::new();
+
+    for mut cut in cuts {
+        let (a, b) = (cut[0], cut[1]);
+        let ab = b.sub(a);
+
+        cut.sort_by(|p, q| p.sub(a).dot(ab).total_cmp(&q.sub(a).dot(ab)));
+
+        let mut cut = cut.into_iter().map(|pnt| snap.vertex(pnt)).collect::<Vec<usize>>();
+        cut.dedup();
+
+        for pair in cut.windows(2) {
+            if edges.insert((pair[0].min(pair[1]), pair[0].max(pair[1]))) {
+                split.push(IndSeg::new(pair[0], pair[1]));
+            }
+        }
+    }
+
+    (snap.vertices, split)
+}
+
+/// sorted pairs of segments which share at least one grid cell
+///
+/// the cell size is the larger of the mean segment length and the size for about one cell per segment
+fn candidates(pnts : &[Vect<f64>], segments : &[IndSeg]) -> Vec<(usize, usize)> {
+    if segments.len() < 2 {
+        return Vec::new()
+    }
+
+    let (mut min, mut max) = ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY));
+
+    for seg in segments {
+        for pnt in [pnts[seg.a()], pnts[seg.b()]] {
+            min = (min.0.min(pnt.x()), min.1.min(pnt.y()));
+            max = (max.0.max(pnt.x()), max.1.max(pnt.y()));
+        }
+    }
+
+    let size = max.sub(min);
+    let mean = segments.iter().map(|seg| pnts[seg.b()].sub(pnts[seg.a()]).len()).sum::<f64>() / segments.len() as f64;
+
+    let cell = f64::max(mean, (size.x() * size.y() / segments.len() as f64).sqrt());
+    let cell = match cell > 0f64 && cell.is_finite() {
+        true  => f64::max(cell, f64::max(size.x(), size.y()) / (4 * segments.len()) as f64),
+        false => 1f64
+    };
+
+    let cols = (size.x() / cell) as usize + 1;
+    let rows = (size.y() / cell) as usize + 1;
+
+    let coord = |val : f64, min : f64, count : usize| (((val - min) / cell) as usize).min(count - 1);
+
+    let mut cells : HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (index, seg) in segments.iter().enumerate() {
+        let (a, b) = (pnts[seg.a()], pnts[seg.b()]);
+
+        let (col_a, col_b) = (coord(a.x().min(b.x()), min.0, cols), coord(a.x().max(b.x()), min.0, cols));
+        let (row_a, row_b) = (coord(a.y().min(b.y()), min.1, rows), coord(a.y().max(b.y()), min.1, rows));
+
+        for col in col_a..=col_b {
+            for row in row_a..=row_b {
+                let start = (min.0 + col as f64 * cell, min.1 + row as f64 * cell);
+                let end   = (start.0 + cell, start.1 + cell);
+
+                // line of segment passes through cell if the cell corners are not all on one side
+                let orients = [start, (end.0, start.1), end, (start.0, end.1)].map(|corner| orient(a, b, corner));
+
+                let below = orients.iter().any(|orient| *orient <= 0f64);
+                let above = orients.iter().any(|orient| *orient >= 0f64);
+
+                if below && above {
+                    cells.entry(col * rows + row).or_default().push(index);
+                }
+            }
+        }
+    }
+
+    let mut pairs = HashSet::new();
+
+    for indices in cells.values() {
+        for (i, a) in indices.iter().enumerate() {
+            for b in &indices[i + 1..] {
+                pairs.insert((*a.min(b), *a.max(b)));
+            }
+        }
+    }
+
+    let mut pairs = pairs.into_iter().collect::<Vec<_>>();
+    pairs.sort();
+    pairs
+}
+
+/// intersection of segments a b and c d without epsilon zero checks
+///
+/// endpoints lying on the other segment are returned unchanged
+fn intsec(a : Vect<f64>, b : Vect<f64>, c : Vect<f64>, d : Vect<f64>) -> Option<IntsecKind> {
+    let (a_cd, b_cd) = (orient(c, d, a), orient(c, d, b));
+    let (c_ab, d_ab) = (orient(a, b, c), orient(a, b, d));
+
+    if a_cd == 0f64 && b_cd == 0f64 && c_ab == 0f64 && d_ab == 0f64 {
+        return overlap(a, b, c, d)
+    }
+
+    let apart = |p : f64, q : f64| p > 0f64 && q > 0f64 || p < 0f64 && q < 0f64;
+
+    if apart(a_cd, b_cd) || apart(c_ab, d_ab) {
+        return None
+    }
+
+    // endpoints on the line of the other segment are only checked against its bounds as rounded orientations may disagree
+    let pnt = if a_cd == 0f64 {
+        a
+    } else if b_cd == 0f64 {
+        b
+    } else if c_ab == 0f64 {
+        c
+    } else if d_ab == 0f64 {
+        d
+    } else {
+        return Some(IntsecKind::Point(a.add(b.sub(a).vmul(a_cd / (a_cd - b_cd)))))
+    };
+
+    let within = |p : Vect<f64>, q : Vect<f64>| pnt.x() >= p.x().min(q.x()) && pnt.x() <= p.x().max(q.x()) && pnt.y() >= p.y().min(q.y()) && pnt.y() <= p.y().max(q.y());
+
+    if !within(a, b) || !within(c, d) {
+        return None
+    }
+
+    Some(IntsecKind::Point(pnt))
+}
+
+/// shared part of collinear segments a b and c d
+fn overlap(a : Vect<f64>, b : Vect<f64>, c : Vect<f64>, d : Vect<f64>) -> Option<IntsecKind> {
+    let dir = match a == b {
+        true  => d.sub(c),
+        false => b.sub(a)
+    };
+
+    if dir == (0f64, 0f64) {
+        return match a == c {
+            true  => Some(IntsecKind::Point(a)),
+            false => None
+        }
+    }
+
+    let along = |pnt : Vect<f64>| pnt.sub(a).dot(dir);
+
+    let sorted = |p : Vect<f64>, q : Vect<f64>| match along(p) <= along(q) {
+        true  => (p, q),
+        false => (q, p)
+    };
+
+    let (start_ab, end_ab) = sorted(a, b);
+    let (start_cd, end_cd) = sorted(c, d);
+
+    let start = match along(start_ab) >= along(start_cd) { true => start_ab, false => start_cd };
+    let end   = match along(end_ab)   <= along(end_cd)   { true => end_ab,   false => end_cd };
+
+    match along(start).total_cmp(&along(end)) {
+        std::cmp::Ordering::Less    => Some(IntsecKind::Overlap(start, end)),
+        std::cmp::Ordering::Equal   => Some(IntsecKind::Point(start)),
+        std::cmp::Ordering::Greater => None
+    }
+}
+
+/// vertices where points closer than eps are merged into the first one
+pub(crate) struct Snap {
+    eps      : f64,
+    vertices : Vec<Vect<f64>>,
+    cells    : HashMap<(i64, i64), Vec<usize>>
+}
+
+impl Snap {
+    pub(crate) fn new(eps : f64) -> Snap {
+        Snap{eps : eps, vertices : Vec::new(), cells : HashMap::new()}
+    }
+
+    pub(crate) fn eps(&self) -> f64 {
+        self.eps
+    }
+
+    pub(crate) fn vertices(&self) -> &Vec<Vect<f64>> {
+        &self.vertices
+    }
+
+    /// index of vertex within eps of point or of new vertex
+    pub(crate) fn vertex(&mut self, pnt : Vect<f64>) -> usize {
+        let size = match self.eps > 0f64 {
+            true  => self.eps,
+            false => 1f64
+        };
+
+        let cell = ((pnt.x() / size).floor() as i64, (pnt.y() / size).floor() as i64);
+
+        for col in cell.0 - 1..=cell.0 + 1 {
+            for row in cell.1 - 1..=cell.1 + 1 {
+                for index in self.cells.get(&(col, row)).into_iter().flatten() {
+                    if self.vertices[*index].sub(pnt).len() <= self.eps {
+                        return *index
+                    }
+                }
+            }
+        }
+
+        self.vertices.push(pnt);
+        self.cells.entry(cell).or_default().push(self.vertices.len() - 1);
+
+        self.vertices.len() - 1
+    }
+}
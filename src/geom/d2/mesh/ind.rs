@@ -2,10 +2,27 @@ use std::vec::{
     Vec
 };
 
+use crate::geom::d2::hull;
+
+use crate::geom::d2::intsec::{
+    self,
+    SegIntsec
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
 use crate::geom::d2::prim::{
     Segment
 };
 
+use crate::geom::d2::prim::tri::{
+    PTri
+};
+
+use crate::geom::d2::triangulate;
+
 use crate::geom::d2::prim::seg::{
     PSeg 
 };
@@ -19,7 +36,12 @@ use crate::geom::d2::shape::d2::{
 };
 
 use crate::geom::mesh::ind::{
-    IndSeg
+    IndSeg,
+    IndTri
+};
+
+use crate::conv::{
+    Cast
 };
 
 use crate::num::{
@@ -31,7 +53,9 @@ use crate::num::{
 
 use crate::ops::{
     HAdd,
-    HMul
+    HSub,
+    HMul,
+    HDiv
 };
 
 use crate::rel::{
@@ -92,6 +116,11 @@ pub struct IndSegMesh<Vect : Vector> {
     segments : Vec<IndSeg>
 }
 
+pub struct IndTriMesh<Vect : Vector> {
+    vertices  : Vec<Vect>,
+    triangles : Vec<IndTri>
+}
+
 impl<Vect : Vector> ContourAdjacentResult<Vect> {
     /// Returns the result with the smaller angle. If both angles are equal returns result with smaller vector length
     pub fn choose(result_a : ContourAdjacentResult<Vect>, result_b : ContourAdjacentResult<Vect>) -> ContourAdjacentResult<Vect> 
@@ -222,6 +251,39 @@ impl<Vect : Vector> IndSegMesh<Vect> {
         Bounds::new_unchecked(start, end)
     }
 
+    /// convex hull of vertices as counterclockwise polygon without duplicate and collinear vertices (see hull::monotone_chain)
+    pub fn convex_hull(&self) -> Poly<Vect::Own>
+    where Vect::Val : Zero + HSub + HMul + HPOrd
+    {
+        Poly::new(hull::monotone_chain(&self.vertices))
+    }
+
+    /// all intersections among segments including endpoints on segments and collinear overlaps (see intsec::intersections)
+    pub fn intersections(&self) -> Vec<SegIntsec>
+    where Vect::Val : Cast<f64>
+    {
+        intsec::intersections(&self.vertices, &self.segments)
+    }
+
+    /// planar graph of segments split at all intersections (see intsec::split)
+    pub fn split_intersections(&self) -> IndSegMesh<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        let (vertices, segments) = intsec::split(&self.vertices, &self.segments);
+
+        IndSegMesh::new_unchecked(vertices, segments)
+    }
+
+    /// constrained delaunay triangulation of vertices with segments as edges (see triangulate::constrained_delaunay)
+    pub fn triangulate(&self, interior : bool) -> IndTriMesh<Vect::Own>
+    where Vect::Val : Cast<f64>
+    {
+        let vertices  = self.vertices.iter().map(Vect::of).collect();
+        let triangles = triangulate::constrained_delaunay(&self.vertices, &self.segments, interior);
+
+        IndTriMesh::new_unchecked(vertices, triangles)
+    }
+
     pub fn contour(&self) -> Option<Vec<Vect::Own>> 
     where Vect::Val : Zero + One + Two + Float + HPOrd + std::fmt::Debug
     {   
@@ -831,4 +893,39 @@ impl<Vect : Vector<Own = Vect>> IndSegMesh<Vect> {
             *v = v.vmul(val);
         }
     }
-}
\ No newline at end of file
+}
+
+impl<Vect : Vector> IndTriMesh<Vect> {
+    pub fn new_unchecked(vertices : Vec<Vect>, triangles : Vec<IndTri>) -> IndTriMesh<Vect> {
+        IndTriMesh{vertices : vertices, triangles : triangles}
+    }
+
+    pub fn vertices(&self) -> &Vec<Vect> {
+        &self.vertices
+    }
+
+    pub fn triangles(&self) -> &Vec<IndTri> {
+        &self.triangles
+    }
+
+    /// point triangle of indexed triangle
+    pub fn triangle(&self, tri : &IndTri) -> PTri<Vect::Own> {
+        let [a, b, c] = tri.indices().map(|index| Vect::of(&self.vertices[index]));
+        PTri::new(a, b, c)
+    }
+
+    /// point triangles of all indexed triangles
+    pub fn ptris(&self) -> Vec<PTri<Vect::Own>> {
+        self.triangles.iter().map(|tri| self.triangle(tri)).collect()
+    }
+
+    /// sum of signed triangle areas (positive for counterclockwise triangles)
+    pub fn area(&self) -> Vect::Val
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv
+    {
+        self.triangles.iter().fold(Vect::Val::zero(), |area, tri| {
+            let [a, b, c] = tri.indices().map(|index| &self.vertices[index]);
+            area + b.sub(a).det(c.sub(a)) / Vect::Val::two()
+        })
+    }
+}
@@ -0,0 +1,169 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::clip::{
+    self,
+    Winding
+};
+
+use crate::geom::d2::poly::{
+    MultiPoly
+};
+
+use crate::geom::d2::prim::ellipse::{
+    steps
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// shape of offset contours at corners where the offset edges move apart
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinType {
+    /// extend offset edges until they meet unless the miter limit is exceeded (bevel otherwise)
+    Miter,
+    /// circular arc around the corner
+    Round,
+    /// cut perpendicular to the corner bisector at offset distance from the corner
+    Square
+}
+
+/// offsetting of polygon regions where positive distances inflate and negative distances deflate
+#[derive(Clone, Copy, Debug)]
+pub struct Offset {
+    join        : JoinType,
+    miter_limit : f64,
+    tolerance   : f64
+}
+
+impl Offset {
+    /// offset with join, miter limit 4 and round join tolerance 0.01
+    pub fn new(join : JoinType) -> Offset {
+        Offset{join : join, miter_limit : 4f64, tolerance : 0.01f64}
+    }
+
+    /// maximal ratio of miter length to offset distance before miter joins become bevel joins
+    pub fn miter_limit(mut self, miter_limit : f64) -> Offset {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// maximal distance between round joins and their chords
+    pub fn tolerance(mut self, tolerance : f64) -> Offset {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// region of closed contours with even-odd rule offset by delta (outwards if positive)
+    ///
+    /// every contour of the cleaned region is offset edge by edge with joins at corners where the offset edges move apart
+    /// and loops through the corner at corners where they overlap, so that the offset region is where the offset contours
+    /// wind positively (self-intersections of deflated contours and overlaps of inflated contours vanish)
+    pub fn contours<V : Vector>(&self, contours : &[&[V]], delta : f64) -> MultiPoly<Vect<f64>>
+    where V::Val : Cast<f64>
+    {
+        let region = clip::resolve(contours, Winding::EvenOdd);
+
+        if delta == 0f64 {
+            return region
+        }
+
+        let raw = region.contours().iter().map(|contour| self.raw(contour, delta)).collect::<Vec<_>>();
+        let raw = raw.iter().map(|contour| contour.as_slice()).collect::<Vec<_>>();
+
+        clip::resolve(&raw, Winding::Positive)
+    }
+
+    /// offset contour of counterclockwise outer contour or clockwise hole before removing self-intersections
+    fn raw(&self, contour : &[Vect<f64>], delta : f64) -> Vec<Vect<f64>> {
+        let len = contour.len();
+
+        let mut raw = Vec::new();
+
+        for index in 0..len {
+            let prev = contour[(index + len - 1) % len];
+            let pnt  = contour[index];
+            let next = contour[(index + 1) % len];
+
+            let dir_a = pnt.sub(prev).vdiv(pnt.sub(prev).len());
+            let dir_b = next.sub(pnt).vdiv(next.sub(pnt).len());
+
+            // normals on the side the offset moves to
+            let nrm_a = dir_a.orth_r().vmul(delta.signum());
+            let nrm_b = dir_b.orth_r().vmul(delta.signum());
+
+            let dist = delta.abs();
+
+            let a = pnt.add(nrm_a.vmul(dist));
+            let b = pnt.add(nrm_b.vmul(dist));
+
+            let cross = dir_a.det(dir_b);
+
+            // offset edges overlap and are connected through the corner
+            if cross * delta < 0f64 {
+                raw.extend([a, pnt, b]);
+                continue;
+            }
+
+            // straight continuation
+            if cross == 0f64 && dir_a.dot(dir_b) > 0f64 {
+                raw.push(a);
+                continue;
+            }
+
+            raw.extend(self.join(pnt, dir_a, dir_b, delta));
+        }
+
+        raw
+    }
+
+    /// vertices of join at corner from offset point of incoming edge with direction a to offset point of outgoing edge with direction b
+    fn join(&self, pnt : Vect<f64>, dir_a : Vect<f64>, dir_b : Vect<f64>, delta : f64) -> Vec<Vect<f64>> {
+        let nrm_a = dir_a.orth_r().vmul(delta.signum());
+        let nrm_b = dir_b.orth_r().vmul(delta.signum());
+
+        let dist = delta.abs();
+
+        let a = pnt.add(nrm_a.vmul(dist));
+        let b = pnt.add(nrm_b.vmul(dist));
+
+        let sum = nrm_a.add(nrm_b);
+        let len = sum.len();
+
+        match self.join {
+            JoinType::Miter => {
+                // ratio of miter length to offset distance is 1 / cos(angle / 2) = 2 / |nrm_a + nrm_b|
+                match len > 0f64 && 2f64 / len <= self.miter_limit {
+                    true  => vec![pnt.add(sum.vmul(2f64 * dist / (len * len)))],
+                    false => vec![a, b]
+                }
+            },
+            JoinType::Square => {
+                // bisector or edge direction at reversals
+                let bisector = match len > 0f64 {
+                    true  => sum.vdiv(len),
+                    false => dir_a
+                };
+
+                let ext = dist * (1f64 - nrm_a.dot(bisector)) / dir_a.dot(bisector);
+
+                vec![a.add(dir_a.vmul(ext)), b.sub(dir_b.vmul(ext))]
+            },
+            JoinType::Round => {
+                // sweep from nrm_a to nrm_b counterclockwise for inflation and clockwise for deflation
+                let angle = nrm_a.det(nrm_b).abs().atan2(nrm_a.dot(nrm_b)) * delta.signum();
+                let count = steps(dist, self.tolerance, angle, 1);
+
+                (0..=count).map(|step| {
+                    let (sin, cos) = (angle * step as f64 / count as f64).sin_cos();
+                    let nrm = (cos * nrm_a.x() - sin * nrm_a.y(), sin * nrm_a.x() + cos * nrm_a.y());
+
+                    pnt.add(nrm.vmul(dist))
+                }).collect()
+            }
+        }
+    }
+}
@@ -2,19 +2,67 @@ use std::vec::{
     Vec
 };
 
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::calipers;
+
+use crate::geom::d2::clip::{
+    self,
+    BoolOp
+};
+
+use crate::geom::d2::contour;
+
+use crate::geom::d2::offset::{
+    Offset
+};
+
+use crate::geom::d2::simplify;
+
+use crate::geom::d2::mesh::ind::{
+    IndTriMesh
+};
+
+use crate::geom::d2::triangulate;
+
+use crate::geom::d2::smooth::{
+    self,
+    Smooth
+};
+
+use crate::geom::d2::prim::rect::{
+    PRect
+};
+
+use crate::geom::d2::prim::seg::{
+    Segment
+};
+
 use crate::geom::d2::prim::vect::{
     Vector
 };
 
 use crate::geom::d2::shape::d2::{
-    Bounds 
+    Bounds,
+    Shape,
+    impl_shape
+};
+
+use crate::num::{
+    Zero,
+    One,
+    Two,
+    Float
 };
 
 use crate::ops::{
     HAdd,
     HSub,
     HMul,
-    HDiv
+    HDiv,
+    HNeg
 };
 
 use crate::rel::{
@@ -25,6 +73,34 @@ pub struct Poly<Vect : Vector> {
     vertices : Vec<Vect>
 }
 
+/// polygon with holes where the region is enclosed by the outer contour and not by the holes
+pub struct PolyWithHoles<Vect : Vector> {
+    outer : Poly<Vect>,
+    holes : Vec<Poly<Vect>>
+}
+
+/// set of disjoint polygons with holes (e.g. result of boolean operations)
+pub struct MultiPoly<Vect : Vector> {
+    polys : Vec<PolyWithHoles<Vect>>
+}
+
+/// orientation of polygon vertices in a coordinate system with y axis pointing upwards
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    /// polygon without area
+    Degenerate
+}
+
+/// second moments of area about the centroid (xx = integral of y², yy = integral of x², xy = integral of x * y)
+#[derive(Clone, Copy, Debug)]
+pub struct Moments<Val> {
+    pub xx : Val,
+    pub yy : Val,
+    pub xy : Val
+}
+
 impl<Vect : Vector> Poly<Vect> {
     // General methods
 
@@ -57,6 +133,13 @@ impl<Vect : Vector> Poly<Vect> {
         Poly::new(vertices)
     }
 
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Poly<Vect::Own> 
+    where Vect::Val : HSub
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.sub(&vect)).collect();
+        Poly::new(vertices)
+    }
+
     pub fn mul<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Poly<Vect::Own> 
     where Vect::Val : HMul
     {
@@ -64,6 +147,41 @@ impl<Vect : Vector> Poly<Vect> {
         Poly::new(vertices)
     }
 
+    pub fn div<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Poly<Vect::Own> 
+    where Vect::Val : HDiv
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.div(&vect)).collect();
+        Poly::new(vertices)
+    }
+
+    pub fn vadd(&self, val : Vect::Val) -> Poly<Vect::Own> 
+    where Vect::Val : HAdd
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.vadd(val)).collect();
+        Poly::new(vertices)
+    }
+
+    pub fn vsub(&self, val : Vect::Val) -> Poly<Vect::Own> 
+    where Vect::Val : HSub
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.vsub(val)).collect();
+        Poly::new(vertices)
+    }
+
+    pub fn vmul(&self, val : Vect::Val) -> Poly<Vect::Own> 
+    where Vect::Val : HMul
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.vmul(val)).collect();
+        Poly::new(vertices)
+    }
+
+    pub fn vdiv(&self, val : Vect::Val) -> Poly<Vect::Own> 
+    where Vect::Val : HDiv
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.vdiv(val)).collect();
+        Poly::new(vertices)
+    }
+
     pub fn bounds(&self) -> Bounds<Vect::Own> 
     where Vect::Val : HPOrd
     {
@@ -77,4 +195,481 @@ impl<Vect : Vector> Poly<Vect> {
 
         Bounds::new_unchecked(start, end)
     }
-} 
\ No newline at end of file
+
+    /// check if polygon contains point with non-zero winding rule
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        self.contains_non_zero(pnt)
+    }
+
+    // Boolean methods
+
+    /// boolean operation with other polygon (see clip::boolean)
+    pub fn boolean<V : Vector>(&self, other : &Poly<V>, op : BoolOp) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>,
+          V::Val    : Cast<f64>
+    {
+        clip::boolean(&[&self.vertices], &[&other.vertices], op)
+    }
+
+    /// polygon offset by delta (outwards if positive, see Offset::contours)
+    pub fn offset(&self, delta : f64, offset : &Offset) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        offset.contours(&[&self.vertices], delta)
+    }
+
+    // Simplification methods
+
+    /// Ramer-Douglas-Peucker simplification with maximal hausdorff distance tol (see simplify::rdp)
+    pub fn rdp(&self, tol : Vect::Val, topology : bool) -> Poly<Vect::Own> 
+    where Vect::Val : Float + HPOrd
+    {
+        Poly::new(simplify::rdp(&self.vertices, tol, topology))
+    }
+
+    /// Visvalingam-Whyatt simplification with maximal hausdorff distance tol (see simplify::visvalingam)
+    pub fn visvalingam(&self, tol : Vect::Val, topology : bool) -> Poly<Vect::Own> 
+    where Vect::Val : Float + HPOrd
+    {
+        Poly::new(simplify::visvalingam(&self.vertices, tol, topology))
+    }
+
+    // Smoothing methods
+
+    /// polygon resampled with uniform spacing between corners above corner angle (see smooth::resample)
+    pub fn resample(&self, spacing : f64, corner_angle : f64) -> Poly<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        Poly::new(smooth::resample(&self.vertices, spacing, corner_angle))
+    }
+
+    /// smoothed polygon (see Smooth::contour)
+    pub fn smooth(&self, smooth : &Smooth) -> Poly<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        Poly::new(smooth.contour(&self.vertices))
+    }
+
+    // Enclosing methods
+
+    /// enclosing rectangle with minimal area (see calipers::min_area_rect)
+    pub fn min_area_rect(&self) -> Option<PRect<Vect::Own>>
+    where Vect::Val : Float + HPOrd
+    {
+        calipers::min_area_rect(&self.vertices)
+    }
+
+    /// enclosing rectangle with minimal width (see calipers::min_width_rect)
+    pub fn min_width_rect(&self) -> Option<PRect<Vect::Own>>
+    where Vect::Val : Float + HPOrd
+    {
+        calipers::min_width_rect(&self.vertices)
+    }
+
+    // Triangulation methods
+
+    /// counterclockwise triangles of polygon by ear clipping (see triangulate::ear_clipping)
+    pub fn triangulate(&self) -> IndTriMesh<Vect::Own>
+    where Vect::Val : Cast<f64>
+    {
+        let vertices  = self.vertices.iter().map(Vect::of).collect();
+        let triangles = triangulate::ear_clipping(&[self.vertices.as_slice()]);
+
+        IndTriMesh::new_unchecked(vertices, triangles)
+    }
+
+    // Measurement methods
+
+    /// signed area with shoelace formula (positive if counterclockwise)
+    pub fn signed_area(&self) -> Vect::Val 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv
+    {
+        contour::signed_area(&self.vertices)
+    }
+
+    pub fn area(&self) -> Vect::Val 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv + HNeg + HPOrd
+    {
+        let area = self.signed_area();
+
+        match area < Vect::Val::zero() {
+            false => area,
+            true  => -area
+        }
+    }
+
+    /// length of closed boundary
+    pub fn perimeter(&self) -> Vect::Val 
+    where Vect::Val : Float
+    {
+        contour::len(&self.vertices)
+    }
+
+    pub fn orientation(&self) -> Orientation 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv + HPOrd
+    {
+        let area = self.signed_area();
+        let zero = Vect::Val::zero();
+
+        if area > zero {
+            Orientation::CounterClockwise
+        }
+        else if area < zero {
+            Orientation::Clockwise
+        }
+        else {
+            Orientation::Degenerate
+        }
+    }
+
+    /// polygon with reversed vertex order that keeps the first vertex
+    pub fn reverse(&self) -> Poly<Vect::Own> {
+        let mut vertices = self.vertices.iter().map(Vect::of).collect::<Vec<_>>();
+
+        if vertices.len() > 1 {
+            vertices[1..].reverse();
+        }
+
+        Poly::new(vertices)
+    }
+
+    /// polygon with orientation where degenerate polygons are returned unchanged
+    pub fn with_orientation(&self, orientation : Orientation) -> Poly<Vect::Own> 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv + HPOrd
+    {
+        let current = self.orientation();
+
+        match current == orientation || current == Orientation::Degenerate || orientation == Orientation::Degenerate {
+            true  => Poly::new(self.vertices.iter().map(Vect::of).collect()),
+            false => self.reverse()
+        }
+    }
+
+    /// center of area or None if polygon has no area
+    pub fn centroid(&self) -> Option<Vect::Own> 
+    where Vect::Val : Float + HPOrd
+    {
+        let zero = Vect::Val::zero();
+        let area = self.signed_area();
+
+        if area == zero {
+            return None
+        }
+
+        let mut x = zero;
+        let mut y = zero;
+
+        for edge in contour::edges(&self.vertices) {
+            let a = edge.a();
+            let b = edge.b();
+
+            let cross = a.det(&b);
+
+            x = x + (a.x() + b.x()) * cross;
+            y = y + (a.y() + b.y()) * cross;
+        }
+
+        let six = Vect::Val::two() + Vect::Val::two() + Vect::Val::two();
+
+        Some(Vect::of((x / (six * area), y / (six * area))))
+    }
+
+    /// second moments of area about the centroid or None if polygon has no area
+    pub fn moments(&self) -> Option<Moments<Vect::Val>> 
+    where Vect::Val : Float + HPOrd
+    {
+        let zero = Vect::Val::zero();
+        let two  = Vect::Val::two();
+
+        let area     = self.signed_area();
+        let centroid = self.centroid()?;
+
+        // moments about the origin of the vertices relative to the centroid
+        let mut xx = zero;
+        let mut yy = zero;
+        let mut xy = zero;
+
+        for edge in contour::edges(&self.vertices) {
+            let a = edge.a().sub(&centroid);
+            let b = edge.b().sub(&centroid);
+
+            let cross = a.det(&b);
+
+            xx = xx + (a.y() * a.y() + a.y() * b.y() + b.y() * b.y()) * cross;
+            yy = yy + (a.x() * a.x() + a.x() * b.x() + b.x() * b.x()) * cross;
+            xy = xy + (a.x() * b.y() + two * a.x() * a.y() + two * b.x() * b.y() + b.x() * a.y()) * cross;
+        }
+
+        let six    = two + two + two;
+        let twelve = six + six;
+
+        // sums of clockwise polygons have the sign of their area
+        let sign = match area < zero {
+            false => Vect::Val::one(),
+            true  => -Vect::Val::one()
+        };
+
+        Some(Moments{xx : sign * xx / twelve, yy : sign * yy / twelve, xy : sign * xy / (twelve + twelve)})
+    }
+
+    /// checks that no two edges intersect except adjacent edges at their shared vertex
+    pub fn is_simple(&self) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        let len = self.vertices.len();
+
+        if len < 3 {
+            return false
+        }
+
+        for i in 0..len {
+            let a = &self.vertices[i];
+            let b = &self.vertices[(i + 1) % len];
+
+            if a.equal(b) {
+                return false
+            }
+
+            for j in (i + 1)..len {
+                let c = &self.vertices[j];
+                let d = &self.vertices[(j + 1) % len];
+
+                let adjacent = j == i + 1 || (i == 0 && j == len - 1);
+
+                if adjacent {
+                    // adjacent edges may only touch at their shared vertex unless they fold back onto each other
+                    let (shared, other_a, other_b) = match j == i + 1 {
+                        true  => (b, a, d),
+                        false => (a, b, c)
+                    };
+
+                    if Poly::<Vect>::collinear_overlap(shared, other_a, other_b) {
+                        return false
+                    }
+
+                    continue;
+                }
+
+                if Poly::<Vect>::segments_intersect(a, b, c, d) {
+                    return false
+                }
+            }
+        }
+
+        true
+    }
+
+    /// winding number of polygon around point (positive for counterclockwise windings)
+    pub fn winding_number<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> isize 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        let zero = Vect::Val::zero();
+        let mut winding = 0;
+
+        for edge in contour::edges(&self.vertices) {
+            let a = edge.a();
+            let b = edge.b();
+
+            let side = b.sub(&a).det(pnt.sub(&a));
+
+            if a.y() <= pnt.y() {
+                if b.y() > pnt.y() && side > zero {
+                    winding += 1;
+                }
+            }
+            else if b.y() <= pnt.y() && side < zero {
+                winding -= 1;
+            }
+        }
+
+        winding
+    }
+
+    /// checks if point is inside with non-zero winding rule (points on edges may be inside or outside)
+    pub fn contains_non_zero<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        self.winding_number(pnt) != 0
+    }
+
+    /// checks if point is inside with even-odd rule (points on edges may be inside or outside)
+    pub fn contains_even_odd<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        self.winding_number(pnt) % 2 != 0
+    }
+
+    /// sign of determinant of (b - a) and (c - a) as -1, 0 or 1
+    fn side<A : Vector<Val = Vect::Val>, B : Vector<Val = Vect::Val>, C : Vector<Val = Vect::Val>>(a : A, b : B, c : C) -> i8 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        let det  = b.sub(&a).det(c.sub(&a));
+        let zero = Vect::Val::zero();
+
+        if det > zero {
+            1
+        }
+        else if det < zero {
+            -1
+        }
+        else {
+            0
+        }
+    }
+
+    /// checks if collinear point c lies within the bounds of segment ab
+    fn on_segment<A : Vector<Val = Vect::Val>, B : Vector<Val = Vect::Val>, C : Vector<Val = Vect::Val>>(a : A, b : B, c : C) -> bool 
+    where Vect::Val : HPOrd
+    {
+        let min = a.min(&b);
+        let max = a.max(&b);
+
+        min.x() <= c.x() && c.x() <= max.x() && min.y() <= c.y() && c.y() <= max.y()
+    }
+
+    /// checks if closed segments ab and cd have a common point
+    pub(crate) fn segments_intersect(a : &Vect, b : &Vect, c : &Vect, d : &Vect) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        let abc = Poly::<Vect>::side(a, b, c);
+        let abd = Poly::<Vect>::side(a, b, d);
+        let cda = Poly::<Vect>::side(c, d, a);
+        let cdb = Poly::<Vect>::side(c, d, b);
+
+        if abc * abd < 0 && cda * cdb < 0 {
+            return true
+        }
+
+        (abc == 0 && Poly::<Vect>::on_segment(a, b, c)) ||
+        (abd == 0 && Poly::<Vect>::on_segment(a, b, d)) ||
+        (cda == 0 && Poly::<Vect>::on_segment(c, d, a)) ||
+        (cdb == 0 && Poly::<Vect>::on_segment(c, d, b))
+    }
+
+    /// checks if segments from shared vertex to a and to b are collinear and point in the same direction
+    pub(crate) fn collinear_overlap(shared : &Vect, a : &Vect, b : &Vect) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        Poly::<Vect>::side(shared, a, b) == 0 && a.sub(shared).dot(b.sub(shared)) > Vect::Val::zero()
+    }
+}
+
+impl<Vect : Vector> PolyWithHoles<Vect> {
+    /// create new polygon of outer contour and holes
+    pub fn new(outer : Poly<Vect>, holes : Vec<Poly<Vect>>) -> PolyWithHoles<Vect> {
+        PolyWithHoles{outer : outer, holes : holes}
+    }
+
+    pub fn outer(&self) -> &Poly<Vect> {
+        &self.outer
+    }
+
+    pub fn holes(&self) -> &Vec<Poly<Vect>> {
+        &self.holes
+    }
+
+    /// vertices of outer contour followed by the vertices of the holes
+    pub fn contours(&self) -> Vec<&[Vect]> {
+        let mut contours = vec![self.outer.vertices().as_slice()];
+        contours.extend(self.holes.iter().map(|hole| hole.vertices().as_slice()));
+        contours
+    }
+
+    /// area of outer contour without the areas of the holes
+    pub fn area(&self) -> Vect::Val 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv + HNeg + HPOrd
+    {
+        self.holes.iter().fold(self.outer.area(), |area, hole| area - hole.area())
+    }
+
+    /// check if point is inside outer contour and outside of the holes
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        self.outer.contains_even_odd(&pnt) && !self.holes.iter().any(|hole| hole.contains_even_odd(&pnt))
+    }
+
+    /// boolean operation with other polygon with holes (see clip::boolean)
+    pub fn boolean<V : Vector>(&self, other : &PolyWithHoles<V>, op : BoolOp) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>,
+          V::Val    : Cast<f64>
+    {
+        clip::boolean(&self.contours(), &other.contours(), op)
+    }
+
+    /// region offset by delta (outwards if positive, see Offset::contours)
+    pub fn offset(&self, delta : f64, offset : &Offset) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        offset.contours(&self.contours(), delta)
+    }
+
+    /// counterclockwise triangles of region by ear clipping with vertices of contours (see triangulate::ear_clipping)
+    pub fn triangulate(&self) -> IndTriMesh<Vect::Own>
+    where Vect::Val : Cast<f64>
+    {
+        let contours  = self.contours();
+        let vertices  = contours.iter().flat_map(|contour| contour.iter()).map(Vect::of).collect();
+        let triangles = triangulate::ear_clipping(&contours);
+
+        IndTriMesh::new_unchecked(vertices, triangles)
+    }
+}
+
+impl<Vect : Vector> MultiPoly<Vect> {
+    pub fn new(polys : Vec<PolyWithHoles<Vect>>) -> MultiPoly<Vect> {
+        MultiPoly{polys : polys}
+    }
+
+    pub fn polys(&self) -> &Vec<PolyWithHoles<Vect>> {
+        &self.polys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.polys.is_empty()
+    }
+
+    /// vertices of all outer contours and holes
+    pub fn contours(&self) -> Vec<&[Vect]> {
+        self.polys.iter().flat_map(|poly| poly.contours()).collect()
+    }
+
+    pub fn area(&self) -> Vect::Val 
+    where Vect::Val : Zero + Two + HAdd + HSub + HMul + HDiv + HNeg + HPOrd
+    {
+        self.polys.iter().fold(Vect::Val::zero(), |area, poly| area + poly.area())
+    }
+
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool 
+    where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+    {
+        self.polys.iter().any(|poly| poly.contains(&pnt))
+    }
+
+    /// boolean operation with other multi polygon (see clip::boolean)
+    pub fn boolean<V : Vector>(&self, other : &MultiPoly<V>, op : BoolOp) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>,
+          V::Val    : Cast<f64>
+    {
+        clip::boolean(&self.contours(), &other.contours(), op)
+    }
+
+    /// region offset by delta (outwards if positive, see Offset::contours)
+    pub fn offset(&self, delta : f64, offset : &Offset) -> MultiPoly<(f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        offset.contours(&self.contours(), delta)
+    }
+}
+
+impl<Vect : Vector> Shape for Poly<Vect> 
+where Vect::Val : Zero + HAdd + HSub + HMul + HPOrd
+{
+    type Val  = Vect::Val;
+    type Vect = Vect::Own;
+    type Own  = Poly<Vect::Own>;
+
+    impl_shape!(Poly<Vect>);
+}
\ No newline at end of file
@@ -1,3 +1,18 @@
+/// module for 2D elliptic arc primitive
+pub mod arc;
+
+/// module for 2D bezier curve primitive
+pub mod bezier;
+
+/// module for 2D b-spline curve primitive
+pub mod bspline;
+
+/// module for 2D circle primitive
+pub mod circle;
+
+/// module for 2D ellipse primitive
+pub mod ellipse;
+
 /// module for 2D line primitive
 pub mod line;
 
@@ -13,6 +28,11 @@ pub mod tri;
 /// module for 2D vector primitive
 pub mod vect;
 
+pub use arc::*;
+pub use bezier::*;
+pub use bspline::*;
+pub use circle::*;
+pub use ellipse::*;
 pub use line::*;
 pub use rect::*;
 pub use seg::*;
@@ -0,0 +1,295 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::ellipse::{
+    Ellipse,
+    steps
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds,
+    Shape,
+    impl_shape
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    One,
+    Two,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// 2D elliptic arc of "ellipse" from parameter "start" over "sweep" in radians (counterclockwise if positive)
+///
+/// as shape the arc is the region between the arc and its chord
+pub struct Arc<V : Vector> {
+    ellipse : Ellipse<V>,
+    start   : V::Val,
+    sweep   : V::Val
+}
+
+impl<Vect : Vector> Arc<Vect> {
+    // General methods
+
+    /// create new Arc of "ellipse" from parameter "start" over "sweep" in radians
+    pub fn new(ellipse : Ellipse<Vect>, start : Vect::Val, sweep : Vect::Val) -> Arc<Vect> {
+        Arc{ellipse : ellipse, start : start, sweep : sweep}
+    }
+
+    /// create new circular Arc of "center" and "radius" from angle "start" over "sweep" in radians
+    pub fn circular(center : Vect, radius : Vect::Val, start : Vect::Val, sweep : Vect::Val) -> Arc<Vect::Own>
+    where Vect::Val : Zero
+    {
+        let zero    = Vect::Val::zero();
+        let ellipse = Ellipse::new(Vect::of(center), Vect::of((radius, zero)), Vect::of((zero, radius)));
+
+        Arc::new(ellipse, start, sweep)
+    }
+
+    pub fn ellipse(&self) -> &Ellipse<Vect> {
+        &self.ellipse
+    }
+
+    pub fn start(&self) -> Vect::Val {
+        self.start
+    }
+
+    pub fn sweep(&self) -> Vect::Val {
+        self.sweep
+    }
+
+    /// first point of arc
+    pub fn a(&self) -> Vect::Own
+    where Vect::Val : Float
+    {
+        self.ellipse.pnt(self.start)
+    }
+
+    /// last point of arc
+    pub fn b(&self) -> Vect::Own
+    where Vect::Val : Float
+    {
+        self.ellipse.pnt(self.start + self.sweep)
+    }
+
+    /// checks if arc covers the full ellipse
+    pub fn full(&self) -> bool
+    where Vect::Val : Float + HPOrd
+    {
+        let full = Vect::Val::two() * Vect::Val::pi();
+
+        self.sweep >= full || self.sweep <= -full
+    }
+
+    /// vertices of polyline from first to last point with maximal distance "tol" between arc and edges
+    pub fn vertices(&self, tol : Vect::Val) -> Vec<Vect::Own>
+    where Vect::Val : Float + HPOrd + Cast<f64>,
+          usize     : Cast<Vect::Val>
+    {
+        let steps = steps(self.ellipse.max_radius(), tol, self.sweep, 1);
+
+        (0..=steps).map(|step| {
+            let fraction : Vect::Val = step.cast() / steps.cast();
+            self.ellipse.pnt(self.start + self.sweep * fraction)
+        }).collect()
+    }
+
+    /// polygon of arc closed by its chord with maximal distance "tol" between arc and edges
+    pub fn poly(&self, tol : Vect::Val) -> Poly<Vect::Own>
+    where Vect::Val : Float + HPOrd + Cast<f64>,
+          usize     : Cast<Vect::Val>
+    {
+        let mut vertices = self.vertices(tol);
+
+        if self.full() {
+            vertices.pop();
+        }
+
+        Poly::new(vertices)
+    }
+
+    /// checks if point with coordinates in the unit circle space of the ellipse lies in the sector swept by the arc
+    fn in_sweep(&self, pnt : (Vect::Val, Vect::Val)) -> bool
+    where Vect::Val : Float + HPOrd
+    {
+        if self.full() {
+            return true
+        }
+
+        let zero = Vect::Val::zero();
+
+        let (start, end) = match self.sweep < zero {
+            false => (self.start, self.start + self.sweep),
+            true  => (self.start + self.sweep, self.start)
+        };
+
+        let s = (start.cos(), start.sin());
+        let e = (end.cos(), end.sin());
+
+        let angle = match self.sweep < zero {
+            false => self.sweep,
+            true  => -self.sweep
+        };
+
+        match angle <= Vect::Val::pi() {
+            true  => s.det(pnt) >= zero && pnt.det(e) >= zero,
+            false => !(e.det(pnt) > zero && pnt.det(s) > zero)
+        }
+    }
+
+    // Shape methods
+
+    /// add vector to ellipse center (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Arc<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Arc::new(self.ellipse.add(vect), self.start, self.sweep)
+    }
+
+    /// sub vector from ellipse center (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Arc<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Arc::new(self.ellipse.sub(vect), self.start, self.sweep)
+    }
+
+    /// mul vector to ellipse center and semi-axes
+    pub fn mul<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Arc<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Arc::new(self.ellipse.mul(vect), self.start, self.sweep)
+    }
+
+    /// div vector from ellipse center and semi-axes
+    pub fn div<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Arc<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Arc::new(self.ellipse.div(vect), self.start, self.sweep)
+    }
+
+    /// add vector values of ellipse center with value
+    pub fn vadd(&self, val : Vect::Val) -> Arc<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Arc::new(self.ellipse.vadd(val), self.start, self.sweep)
+    }
+
+    // sub vector values of ellipse center with value
+    pub fn vsub(&self, val : Vect::Val) -> Arc<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Arc::new(self.ellipse.vsub(val), self.start, self.sweep)
+    }
+
+    /// mul vector values of ellipse center and semi-axes with value (scaling)
+    pub fn vmul(&self, val : Vect::Val) -> Arc<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Arc::new(self.ellipse.vmul(val), self.start, self.sweep)
+    }
+
+    // div vector values of ellipse center and semi-axes with value (scaling)
+    pub fn vdiv(&self, val : Vect::Val) -> Arc<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Arc::new(self.ellipse.vdiv(val), self.start, self.sweep)
+    }
+
+    /// bounding axe aligned rectangle of end points and extreme points of the ellipse within the sweep
+    pub fn bounds(&self) -> Bounds<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let a = self.a();
+        let b = self.b();
+
+        let mut start = a.min(&b);
+        let mut end   = a.max(&b);
+
+        let u = self.ellipse.u();
+        let v = self.ellipse.v();
+
+        // parameters of extreme x and y values are the directions (u.x, v.x) and (u.y, v.y) in unit circle space
+        for dir in [(u.x(), v.x()), (u.y(), v.y())] {
+            let len = dir.len();
+
+            if len == Vect::Val::zero() {
+                continue;
+            }
+
+            let dir = dir.vdiv(len);
+
+            for dir in [dir, dir.neg()] {
+                if self.in_sweep(dir) {
+                    let pnt = self.ellipse.center().add(u.vmul(dir.x())).add(v.vmul(dir.y()));
+
+                    start = start.min(&pnt);
+                    end   = end.max(&pnt);
+                }
+            }
+        }
+
+        Bounds::new_unchecked(start, end)
+    }
+
+    /// check if region between arc and chord contains point (boundary included)
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool
+    where Vect::Val : Float + HPOrd
+    {
+        let Some(unit) = self.ellipse.unit(pnt) else {
+            return false
+        };
+
+        if unit.dot(unit) > Vect::Val::one() {
+            return false
+        }
+
+        if self.full() {
+            return true
+        }
+
+        let zero = Vect::Val::zero();
+
+        let end = self.start + self.sweep;
+
+        let s = (self.start.cos(), self.start.sin());
+        let e = (end.cos(), end.sin());
+
+        // counterclockwise arcs lie on the right of their chord from first to last point
+        let side = e.sub(s).det(unit.sub(s));
+
+        match self.sweep < zero {
+            false => side <= zero,
+            true  => side >= zero
+        }
+    }
+}
+
+impl<Vect : Vector> Shape for Arc<Vect>
+where Vect::Val : Float + HPOrd
+{
+    type Val  = Vect::Val;
+    type Vect = Vect::Own;
+    type Own  = Arc<Vect::Own>;
+
+    impl_shape!(Arc<Vect>);
+}
@@ -0,0 +1,433 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::fit::{
+    solve
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::seg::{
+    PSeg,
+    Segment
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    One,
+    Two,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// maximal subdivision depth of adaptive flattening and root finding
+const MAX_DEPTH : usize = 32;
+
+/// newton steps of parameter correction in curve fitting
+pub(crate) const FIT_ITERATIONS : usize = 20;
+
+/// 2D bezier curve defined by control points "pnts" of degree pnts.len() - 1 from first to last control point
+pub struct Bezier<V : Vector> {
+    pnts : Vec<V>
+}
+
+impl<Vect : Vector> Bezier<Vect> {
+    // General methods
+
+    /// create new Bezier of control points or None if there are no control points
+    pub fn new(pnts : Vec<Vect>) -> Option<Bezier<Vect>> {
+        match pnts.is_empty() {
+            true  => None,
+            false => Some(Bezier{pnts : pnts})
+        }
+    }
+
+    /// create new quadratic Bezier from "a" over control point "c" to "b"
+    pub fn quadratic(a : Vect, c : Vect, b : Vect) -> Bezier<Vect> {
+        Bezier{pnts : vec![a, c, b]}
+    }
+
+    /// create new cubic Bezier from "a" over control points "c" "d" to "b"
+    pub fn cubic(a : Vect, c : Vect, d : Vect, b : Vect) -> Bezier<Vect> {
+        Bezier{pnts : vec![a, c, d, b]}
+    }
+
+    pub fn pnts(&self) -> &Vec<Vect> {
+        &self.pnts
+    }
+
+    pub fn degree(&self) -> usize {
+        self.pnts.len().saturating_sub(1)
+    }
+
+    /// first point of curve
+    pub fn a(&self) -> Vect::Own {
+        Vect::of(&self.pnts[0])
+    }
+
+    /// last point of curve
+    pub fn b(&self) -> Vect::Own {
+        Vect::of(&self.pnts[self.pnts.len() - 1])
+    }
+
+    /// point at parameter t in [0, 1] with de casteljau algorithm
+    pub fn pnt(&self, t : Vect::Val) -> Vect::Own
+    where Vect::Val : Float
+    {
+        let mut pnts = self.pnts.iter().map(Vect::of).collect::<Vec<_>>();
+
+        for level in 1..pnts.len() {
+            for index in 0..(pnts.len() - level) {
+                pnts[index] = lerp(&pnts[index], &pnts[index + 1], t);
+            }
+        }
+
+        Vect::of(&pnts[0])
+    }
+
+    /// derivative curve of degree - 1 with control points degree * (pnts[i + 1] - pnts[i])
+    pub fn derivative(&self) -> Bezier<Vect::Own>
+    where Vect::Val : Float,
+          usize     : Cast<Vect::Val>
+    {
+        let degree : Vect::Val = self.degree().cast();
+
+        let pnts = self.pnts.windows(2).map(|pnts| pnts[1].sub(&pnts[0]).vmul(degree));
+
+        match self.pnts.len() > 1 {
+            true  => Bezier{pnts : pnts.collect()},
+            false => Bezier{pnts : vec![Vect::zero()]}
+        }
+    }
+
+    /// split curve at parameter t into curves from 0 to t and from t to 1
+    pub fn split(&self, t : Vect::Val) -> (Bezier<Vect::Own>, Bezier<Vect::Own>)
+    where Vect::Val : Float
+    {
+        let len = self.pnts.len();
+
+        let mut pnts = self.pnts.iter().map(Vect::of).collect::<Vec<_>>();
+
+        let mut first = Vec::with_capacity(len);
+        let mut second = Vec::with_capacity(len);
+
+        first.push(Vect::of(&pnts[0]));
+        second.push(Vect::of(&pnts[len - 1]));
+
+        for level in 1..len {
+            for index in 0..(len - level) {
+                pnts[index] = lerp(&pnts[index], &pnts[index + 1], t);
+            }
+
+            first.push(Vect::of(&pnts[0]));
+            second.push(Vect::of(&pnts[len - level - 1]));
+        }
+
+        second.reverse();
+
+        (Bezier{pnts : first}, Bezier{pnts : second})
+    }
+
+    /// vertices of polyline from first to last point with maximal distance "tol" between curve and edges
+    ///
+    /// the curve is split in halves until all control points of parts are within tol of their chords,
+    /// so that the convex hull of the control points and thus the part is within tol of the chord
+    pub fn vertices(&self, tol : Vect::Val) -> Vec<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let two = Vect::Val::two();
+
+        let mut vertices = vec![self.a()];
+        let mut stack = vec![(Bezier{pnts : self.pnts.iter().map(Vect::of).collect::<Vec<_>>()}, 0)];
+
+        while let Some((part, depth)) = stack.pop() {
+            let chord = PSeg::new(part.a(), part.b());
+            let flat  = part.pnts.iter().all(|pnt| chord.dist(pnt) <= tol);
+
+            match flat || depth >= MAX_DEPTH {
+                true  => vertices.push(part.b()),
+                false => {
+                    let (first, second) = part.split(Vect::Val::one() / two);
+
+                    stack.push((second, depth + 1));
+                    stack.push((first, depth + 1));
+                }
+            }
+        }
+
+        vertices
+    }
+
+    /// polygon of curve closed by its chord with maximal distance "tol" between curve and edges
+    pub fn poly(&self, tol : Vect::Val) -> Poly<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let mut vertices = self.vertices(tol);
+
+        // closed curves would repeat the first vertex
+        if vertices.len() > 1 && vertices[0].sub(&vertices[vertices.len() - 1]).len() == Vect::Val::zero() {
+            vertices.pop();
+        }
+
+        Poly::new(vertices)
+    }
+
+    /// least squares fit of curve of degree through first and last point to points with chord length parameters
+    ///
+    /// the parameters of the points are improved by newton steps towards their closest curve points;
+    /// closed curves are fitted to points repeating the first point at the end but only close with a corner (see BSpline::fit_closed);
+    /// None if there are less than degree + 1 points, the degree is 0 or the points do not determine the curve
+    pub fn fit<V : Vector<Val = Vect::Val>>(pnts : &[V], degree : usize) -> Option<Bezier<Vect::Own>>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        if degree == 0 || pnts.len() <= degree {
+            return None
+        }
+
+        let zero = Vect::Val::zero();
+
+        let mut params = Vec::with_capacity(pnts.len());
+        let mut total  = zero;
+
+        for index in 0..pnts.len() {
+            if index > 0 {
+                total = total + pnts[index].sub(&pnts[index - 1]).len();
+            }
+
+            params.push(total);
+        }
+
+        if total <= zero {
+            return None
+        }
+
+        let mut params = params.into_iter().map(|param| param / total).collect::<Vec<_>>();
+
+        let a = Vect::of(&pnts[0]);
+        let b = Vect::of(&pnts[pnts.len() - 1]);
+
+        let mut curve = None;
+
+        for iteration in 0..=FIT_ITERATIONS {
+            // normal equations of the inner control points
+            let mut matrix = vec![vec![zero; degree - 1]; degree - 1];
+            let mut rhs    = vec![(zero, zero); degree - 1];
+
+            for (pnt, param) in pnts.iter().zip(&params) {
+                let basis = bernstein(degree, *param);
+                let rest  = pnt.sub(a.vmul(basis[0])).sub(b.vmul(basis[degree]));
+
+                for row in 1..degree {
+                    for col in 1..degree {
+                        matrix[row - 1][col - 1] = matrix[row - 1][col - 1] + basis[row] * basis[col];
+                    }
+
+                    rhs[row - 1] = rhs[row - 1].add(rest.vmul(basis[row]));
+                }
+            }
+
+            let inner = solve(matrix, rhs.into_iter().map(|(x, y)| [x, y]).collect(), zero)?.into_iter().map(|[x, y]| (x, y)).collect::<Vec<_>>();
+
+            let mut ctrl = vec![Vect::of(&a)];
+            ctrl.extend(inner.iter().map(Vect::of));
+            ctrl.push(Vect::of(&b));
+
+            let fitted = Bezier{pnts : ctrl};
+
+            if iteration < FIT_ITERATIONS {
+                let first  = fitted.derivative();
+                let second = first.derivative();
+
+                for (pnt, param) in pnts.iter().zip(params.iter_mut()) {
+                    let diff = fitted.pnt(*param).sub(pnt);
+                    let d1   = first.pnt(*param);
+                    let d2   = second.pnt(*param);
+
+                    let denom = d1.dot(&d1) + diff.dot(&d2);
+
+                    if denom > zero {
+                        *param = (*param - diff.dot(&d1) / denom).max(zero).min(Vect::Val::one());
+                    }
+                }
+            }
+
+            curve = Some(fitted);
+        }
+
+        curve
+    }
+
+    // Shape methods
+
+    /// add vector to control points (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Bezier<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.add(&vect)).collect()}
+    }
+
+    /// sub vector from control points (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Bezier<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.sub(&vect)).collect()}
+    }
+
+    /// mul vector to control points
+    pub fn mul<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Bezier<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.mul(&vect)).collect()}
+    }
+
+    /// div vector from control points
+    pub fn div<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Bezier<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.div(&vect)).collect()}
+    }
+
+    /// add vector values of control points with value
+    pub fn vadd(&self, val : Vect::Val) -> Bezier<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.vadd(val)).collect()}
+    }
+
+    // sub vector values of control points with value
+    pub fn vsub(&self, val : Vect::Val) -> Bezier<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.vsub(val)).collect()}
+    }
+
+    /// mul vector values of control points with value (scaling)
+    pub fn vmul(&self, val : Vect::Val) -> Bezier<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.vmul(val)).collect()}
+    }
+
+    // div vector values of control points with value (scaling)
+    pub fn vdiv(&self, val : Vect::Val) -> Bezier<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Bezier{pnts : self.pnts.iter().map(|pnt| pnt.vdiv(val)).collect()}
+    }
+
+    /// bounding axe aligned rectangle of end points and points where the derivative of x or y is 0
+    pub fn bounds(&self) -> Bounds<Vect::Own>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        let a = self.a();
+        let b = self.b();
+
+        let mut start = a.min(&b);
+        let mut end   = a.max(&b);
+
+        let derivative = self.derivative();
+
+        let mut params = Vec::new();
+
+        roots(derivative.pnts.iter().map(|pnt| pnt.x()).collect(), Vect::Val::zero(), Vect::Val::one(), 0, &mut params);
+        roots(derivative.pnts.iter().map(|pnt| pnt.y()).collect(), Vect::Val::zero(), Vect::Val::one(), 0, &mut params);
+
+        for param in params {
+            let pnt = self.pnt(param);
+
+            start = start.min(&pnt);
+            end   = end.max(&pnt);
+        }
+
+        Bounds::new_unchecked(start, end)
+    }
+}
+
+/// linear interpolation between a and b at t
+fn lerp<A : Vector, B : Vector<Val = A::Val>>(a : A, b : B, t : A::Val) -> A::Own
+where A::Val : Float
+{
+    a.vmul(A::Val::one() - t).add(b.vmul(t))
+}
+
+/// bernstein polynomials of degree at t
+pub(crate) fn bernstein<Val : Float>(degree : usize, t : Val) -> Vec<Val> {
+    let mut basis = vec![Val::zero(); degree + 1];
+    basis[0] = Val::one();
+
+    for level in 1..=degree {
+        for index in (0..=level).rev() {
+            let prev = match index > 0 {
+                true  => basis[index - 1] * t,
+                false => Val::zero()
+            };
+
+            basis[index] = basis[index] * (Val::one() - t) + prev;
+        }
+    }
+
+    basis
+}
+
+/// parameters in [start, end] where 1D bezier curve of coefficients is 0 by subdivision of intervals with sign changes
+fn roots<Val : Float + HPOrd>(coeffs : Vec<Val>, start : Val, end : Val, depth : usize, params : &mut Vec<Val>) {
+    let zero = Val::zero();
+    let half = Val::one() / Val::two();
+
+    if coeffs.iter().all(|coeff| *coeff > zero) || coeffs.iter().all(|coeff| *coeff < zero) || coeffs.iter().all(|coeff| *coeff == zero) {
+        return
+    }
+
+    let mid = (start + end) * half;
+
+    if depth >= MAX_DEPTH {
+        params.push(mid);
+        return
+    }
+
+    let len = coeffs.len();
+
+    let mut level = coeffs;
+    let mut first = Vec::with_capacity(len);
+    let mut second = Vec::with_capacity(len);
+
+    first.push(level[0]);
+    second.push(level[len - 1]);
+
+    for step in 1..len {
+        for index in 0..(len - step) {
+            level[index] = (level[index] + level[index + 1]) * half;
+        }
+
+        first.push(level[0]);
+        second.push(level[len - step - 1]);
+    }
+
+    second.reverse();
+
+    roots(first, start, mid, depth + 1, params);
+    roots(second, mid, end, depth + 1, params);
+}
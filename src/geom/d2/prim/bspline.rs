@@ -0,0 +1,662 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::bezier::{
+    Bezier,
+    FIT_ITERATIONS
+};
+
+use crate::geom::fit::{
+    solve
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    One,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// 2D b-spline curve of "degree" with control points "pnts" and non-decreasing "knots" where knots.len() = pnts.len() + degree + 1
+///
+/// the curve is defined on the domain from knots[degree] to knots[pnts.len()]
+pub struct BSpline<V : Vector> {
+    pnts   : Vec<V>,
+    knots  : Vec<V::Val>,
+    degree : usize
+}
+
+/// curves before and after a split parameter
+pub type Parts<V> = (BSpline<V>, BSpline<V>);
+
+impl<Vect : Vector> BSpline<Vect> {
+    // General methods
+
+    /// create new BSpline of control points, knots and degree or None if the knots do not match
+    pub fn new(pnts : Vec<Vect>, knots : Vec<Vect::Val>, degree : usize) -> Option<BSpline<Vect>>
+    where Vect::Val : HPOrd
+    {
+        let valid = pnts.len() > degree
+            && knots.len() == pnts.len() + degree + 1
+            && knots.windows(2).all(|knots| knots[0] <= knots[1])
+            && knots[degree] < knots[pnts.len()];
+
+        match valid {
+            true  => Some(BSpline{pnts : pnts, knots : knots, degree : degree}),
+            false => None
+        }
+    }
+
+    /// create new BSpline with uniform knots on domain [0, 1] that are clamped so that the curve starts and ends at the first and last control point
+    pub fn uniform(pnts : Vec<Vect>, degree : usize) -> Option<BSpline<Vect>>
+    where Vect::Val : Zero + One + HDiv + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        if pnts.len() <= degree {
+            return None
+        }
+
+        let spans = pnts.len() - degree;
+
+        let knots = (0..(pnts.len() + degree + 1)).map(|index| {
+            let knot = Ord::min(index.saturating_sub(degree), spans);
+
+            knot.cast() / spans.cast()
+        }).collect();
+
+        BSpline::new(pnts, knots, degree)
+    }
+
+    pub fn pnts(&self) -> &Vec<Vect> {
+        &self.pnts
+    }
+
+    pub fn knots(&self) -> &Vec<Vect::Val> {
+        &self.knots
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// start and end parameter of curve
+    pub fn domain(&self) -> (Vect::Val, Vect::Val) {
+        (self.knots[self.degree], self.knots[self.pnts.len()])
+    }
+
+    /// index of knot span [knots[span], knots[span + 1]) of domain containing t with last span for the domain end
+    fn span(&self, t : Vect::Val) -> usize
+    where Vect::Val : HPOrd
+    {
+        let last = self.pnts.len() - 1;
+
+        (self.degree..last).find(|span| t < self.knots[span + 1]).unwrap_or(last)
+    }
+
+    /// point at parameter t clamped to domain with de boor algorithm
+    pub fn pnt(&self, t : Vect::Val) -> Vect::Own
+    where Vect::Val : Float + HPOrd
+    {
+        let (start, end) = self.domain();
+
+        let t = t.max(start).min(end);
+        let span = self.span(t);
+
+        let degree = self.degree;
+
+        let mut pnts = (0..=degree).map(|index| Vect::of(&self.pnts[span - degree + index])).collect::<Vec<_>>();
+
+        for level in 1..=degree {
+            for index in (level..=degree).rev() {
+                let knot = span - degree + index;
+                let denom = self.knots[knot + degree + 1 - level] - self.knots[knot];
+
+                let alpha = match denom > Vect::Val::zero() {
+                    true  => (t - self.knots[knot]) / denom,
+                    false => Vect::Val::zero()
+                };
+
+                pnts[index] = pnts[index - 1].vmul(Vect::Val::one() - alpha).add(pnts[index].vmul(alpha));
+            }
+        }
+
+        Vect::of(&pnts[degree])
+    }
+
+    /// derivative curve of degree - 1 or None for degree 0
+    pub fn derivative(&self) -> Option<BSpline<Vect::Own>>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        if self.degree == 0 {
+            return None
+        }
+
+        let degree : Vect::Val = self.degree.cast();
+
+        let pnts = (0..(self.pnts.len() - 1)).map(|index| {
+            let denom = self.knots[index + self.degree + 1] - self.knots[index + 1];
+
+            match denom > Vect::Val::zero() {
+                true  => self.pnts[index + 1].sub(&self.pnts[index]).vmul(degree / denom),
+                false => Vect::zero()
+            }
+        }).collect();
+
+        BSpline::new(pnts, self.knots[1..(self.knots.len() - 1)].to_vec(), self.degree - 1)
+    }
+
+    /// equal curve with knot t inserted into domain (boehm algorithm)
+    pub fn insert(&self, t : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let (start, end) = self.domain();
+
+        let t = t.max(start).min(end);
+        let span = self.span(t);
+
+        let degree = self.degree;
+
+        let mut pnts = Vec::with_capacity(self.pnts.len() + 1);
+
+        for index in 0..=self.pnts.len() {
+            let pnt = match index {
+                index if index + degree <= span => Vect::of(&self.pnts[index]),
+                index if index > span            => Vect::of(&self.pnts[index - 1]),
+                index => {
+                    let denom = self.knots[index + degree] - self.knots[index];
+
+                    let alpha = match denom > Vect::Val::zero() {
+                        true  => (t - self.knots[index]) / denom,
+                        false => Vect::Val::zero()
+                    };
+
+                    self.pnts[index - 1].vmul(Vect::Val::one() - alpha).add(self.pnts[index].vmul(alpha))
+                }
+            };
+
+            pnts.push(pnt);
+        }
+
+        let mut knots = self.knots.clone();
+        knots.insert(span + 1, t);
+
+        BSpline{pnts : pnts, knots : knots, degree : degree}
+    }
+
+    /// equal curve where knot t has at least multiplicity degree
+    fn saturate(&self, t : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let mut curve = BSpline{pnts : self.pnts.iter().map(Vect::of).collect(), knots : self.knots.clone(), degree : self.degree};
+
+        while curve.knots.iter().filter(|knot| **knot == t).count() < self.degree {
+            curve = curve.insert(t);
+        }
+
+        curve
+    }
+
+    /// split curve at parameter t into curves before and after t or None if t is not inside the domain
+    pub fn split(&self, t : Vect::Val) -> Option<Parts<Vect::Own>>
+    where Vect::Val : Float + HPOrd
+    {
+        let (start, end) = self.domain();
+
+        if !(t > start && t < end) {
+            return None
+        }
+
+        let curve = self.saturate(t);
+
+        // the curve passes through the control point before the first knot t
+        let first = curve.knots.iter().position(|knot| *knot == t)?;
+
+        let mut first_knots = curve.knots[..(first + curve.degree)].to_vec();
+        first_knots.push(t);
+
+        let mut second_knots = vec![t];
+        second_knots.extend_from_slice(&curve.knots[first..]);
+
+        let first_pnts  = curve.pnts[..first].iter().map(Vect::of).collect();
+        let second_pnts = curve.pnts[(first - 1)..].iter().map(Vect::of).collect();
+
+        Some((BSpline::new(first_pnts, first_knots, curve.degree)?, BSpline::new(second_pnts, second_knots, curve.degree)?))
+    }
+
+    /// bezier curves of the knot spans of the domain
+    pub fn beziers(&self) -> Vec<Bezier<Vect::Own>>
+    where Vect::Val : Float + HPOrd
+    {
+        let (start, end) = self.domain();
+
+        let mut curve = self.saturate(start).saturate(end);
+
+        for index in (self.degree + 1)..self.pnts.len() {
+            curve = curve.saturate(self.knots[index]);
+        }
+
+        // every knot span of the domain is preceded by degree equal knots
+        (curve.degree..(curve.pnts.len())).filter(|span| curve.knots[*span] < curve.knots[span + 1]).filter_map(|span| {
+            Bezier::new(curve.pnts[(span - curve.degree)..=span].iter().map(Vect::of).collect())
+        }).collect()
+    }
+
+    /// vertices of polyline from first to last point with maximal distance "tol" between curve and edges
+    pub fn vertices(&self, tol : Vect::Val) -> Vec<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let mut vertices = Vec::new();
+
+        for bezier in self.beziers() {
+            let part = bezier.vertices(tol);
+            let skip = match vertices.is_empty() {
+                true  => 0,
+                false => 1
+            };
+
+            vertices.extend(part.into_iter().skip(skip));
+        }
+
+        vertices
+    }
+
+    /// polygon of curve closed by its chord with maximal distance "tol" between curve and edges
+    pub fn poly(&self, tol : Vect::Val) -> Poly<Vect::Own>
+    where Vect::Val : Float + HPOrd
+    {
+        let mut vertices = self.vertices(tol);
+
+        // closed curves would repeat the first vertex
+        if vertices.len() > 1 && vertices[0].sub(&vertices[vertices.len() - 1]).len() == Vect::Val::zero() {
+            vertices.pop();
+        }
+
+        Poly::new(vertices)
+    }
+
+    /// least squares fit of curve of degree with count control points through first and last point to points
+    ///
+    /// the points get chord length parameters that are improved by newton steps towards their closest curve points and
+    /// the knots are averages of the initial parameters so that every knot span contains points;
+    /// closed contours are fitted smoothly by fit_closed;
+    /// None if there are less than count points, count is not above degree or the points do not determine the curve
+    pub fn fit<V : Vector<Val = Vect::Val>>(pnts : &[V], count : usize, degree : usize) -> Option<BSpline<Vect::Own>>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        if count <= degree || pnts.len() < count || count < 2 {
+            return None
+        }
+
+        let zero = Vect::Val::zero();
+        let one  = Vect::Val::one();
+
+        let mut params = Vec::with_capacity(pnts.len());
+        let mut total  = zero;
+
+        for index in 0..pnts.len() {
+            if index > 0 {
+                total = total + pnts[index].sub(&pnts[index - 1]).len();
+            }
+
+            params.push(total);
+        }
+
+        if total <= zero {
+            return None
+        }
+
+        let mut params = params.into_iter().map(|param| param / total).collect::<Vec<_>>();
+
+        // interior knots at evenly spaced fractional positions of the parameters so that every knot span contains points
+        let spans : Vect::Val = (count - degree).cast();
+        let ratio : Vect::Val = pnts.len().cast() / spans;
+
+        let mut knots = vec![zero; degree + 1];
+
+        for index in 1..(count - degree) {
+            let pos : Vect::Val = index.cast();
+            let pos = pos * ratio;
+
+            let mut lower = 0;
+
+            while (lower + 1).cast() <= pos && lower + 2 < pnts.len() {
+                lower += 1;
+            }
+
+            let frac = pos - lower.cast();
+
+            knots.push(params[lower] * (one - frac) + params[lower + 1] * frac);
+        }
+
+        knots.extend(vec![one; degree + 1]);
+
+        let zero_pnts = (0..count).map(|_| Vect::zero()).collect::<Vec<Vect::Own>>();
+        let basis = BSpline::new(zero_pnts, knots.clone(), degree)?;
+
+        let a = Vect::of(&pnts[0]);
+        let b = Vect::of(&pnts[pnts.len() - 1]);
+
+        let inner = count - 2;
+
+        let mut curve = None;
+
+        for iteration in 0..=FIT_ITERATIONS {
+            let mut matrix = vec![vec![zero; inner]; inner];
+            let mut rhs    = vec![(zero, zero); inner];
+
+            for (pnt, param) in pnts.iter().zip(&params) {
+                let funcs = basis.basis(*param);
+                let rest  = pnt.sub(a.vmul(funcs[0])).sub(b.vmul(funcs[count - 1]));
+
+                for row in 1..(count - 1) {
+                    if funcs[row] == zero {
+                        continue;
+                    }
+
+                    for col in 1..(count - 1) {
+                        matrix[row - 1][col - 1] = matrix[row - 1][col - 1] + funcs[row] * funcs[col];
+                    }
+
+                    rhs[row - 1] = rhs[row - 1].add(rest.vmul(funcs[row]));
+                }
+            }
+
+            let solution = solve(matrix, rhs.into_iter().map(|(x, y)| [x, y]).collect(), zero)?.into_iter().map(|[x, y]| (x, y)).collect::<Vec<_>>();
+
+            let mut ctrl = vec![Vect::of(&a)];
+            ctrl.extend(solution.iter().map(Vect::of));
+            ctrl.push(Vect::of(&b));
+
+            let fitted = BSpline::new(ctrl, knots.clone(), degree)?;
+
+            // newton steps of the parameters towards their closest curve points
+            if let (true, Some(first)) = (iteration < FIT_ITERATIONS, fitted.derivative()) {
+                let second = first.derivative();
+
+                for (pnt, param) in pnts.iter().zip(params.iter_mut()) {
+                    let diff = fitted.pnt(*param).sub(pnt);
+                    let d1   = first.pnt(*param);
+
+                    let denom = match &second {
+                        Some(second) => d1.dot(&d1) + diff.dot(second.pnt(*param)),
+                        None         => d1.dot(&d1)
+                    };
+
+                    if denom > zero {
+                        *param = (*param - diff.dot(&d1) / denom).max(zero).min(one);
+                    }
+                }
+            }
+
+            curve = Some(fitted);
+        }
+
+        curve
+    }
+
+    /// least squares fit of closed curve of degree with count control points to points of a closed contour
+    ///
+    /// the curve is periodic with the first degree control points repeated at the end, so that it closes smoothly,
+    /// and the knots are averages of the chord length parameters including the edge from the last to the first point
+    /// extended by the period on both sides; None if there are less than count points, count is not above degree
+    /// or the points do not determine the curve
+    pub fn fit_closed<V : Vector<Val = Vect::Val>>(pnts : &[V], count : usize, degree : usize) -> Option<BSpline<Vect::Own>>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        if count <= degree || pnts.len() < count {
+            return None
+        }
+
+        let zero = Vect::Val::zero();
+        let one  = Vect::Val::one();
+
+        let len = pnts.len();
+
+        let mut params = Vec::with_capacity(len + 1);
+        let mut total  = zero;
+
+        for index in 0..=len {
+            if index > 0 {
+                total = total + pnts[index % len].sub(&pnts[index - 1]).len();
+            }
+
+            params.push(total);
+        }
+
+        if total <= zero {
+            return None
+        }
+
+        let mut params = params.into_iter().map(|param| param / total).collect::<Vec<_>>();
+
+        // knots of the domain at evenly spaced fractional positions of the parameters so that every knot span contains points
+        let ratio : Vect::Val = len.cast() / count.cast();
+
+        let mut domain = vec![zero];
+
+        for index in 1..count {
+            let pos : Vect::Val = index.cast();
+            let pos = pos * ratio;
+
+            let mut lower = 0;
+
+            while (lower + 1).cast() <= pos && lower + 1 < len {
+                lower += 1;
+            }
+
+            let frac = pos - lower.cast();
+
+            domain.push(params[lower] * (one - frac) + params[lower + 1] * frac);
+        }
+
+        domain.push(one);
+
+        let mut knots = domain[(count - degree)..count].iter().map(|knot| *knot - one).collect::<Vec<_>>();
+        knots.extend_from_slice(&domain);
+        knots.extend(domain[1..=degree].iter().map(|knot| *knot + one));
+
+        // the parameter of the closing point equals the one of the first point
+        params.pop();
+
+        let zero_pnts = (0..(count + degree)).map(|_| Vect::zero()).collect::<Vec<Vect::Own>>();
+        let basis = BSpline::new(zero_pnts, knots.clone(), degree)?;
+
+        let mut curve = None;
+
+        for iteration in 0..=FIT_ITERATIONS {
+            let mut matrix = vec![vec![zero; count]; count];
+            let mut rhs    = vec![(zero, zero); count];
+
+            for (pnt, param) in pnts.iter().zip(&params) {
+                // the repeated control points are the first ones
+                let mut funcs = vec![zero; count];
+
+                for (index, func) in basis.basis(*param).into_iter().enumerate() {
+                    funcs[index % count] = funcs[index % count] + func;
+                }
+
+                for (row, func) in funcs.iter().enumerate() {
+                    if *func == zero {
+                        continue;
+                    }
+
+                    for (col, other) in funcs.iter().enumerate() {
+                        matrix[row][col] = matrix[row][col] + *func * *other;
+                    }
+
+                    rhs[row] = rhs[row].add(pnt.vmul(*func));
+                }
+            }
+
+            let solution = solve(matrix, rhs.into_iter().map(|(x, y)| [x, y]).collect(), zero)?.into_iter().map(|[x, y]| (x, y)).collect::<Vec<_>>();
+
+            let ctrl = (0..(count + degree)).map(|index| Vect::of(solution[index % count])).collect();
+
+            let fitted = BSpline::new(ctrl, knots.clone(), degree)?;
+
+            // newton steps of the parameters towards their closest curve points wrapped around the domain
+            if let (true, Some(first)) = (iteration < FIT_ITERATIONS, fitted.derivative()) {
+                let second = first.derivative();
+
+                for (pnt, param) in pnts.iter().zip(params.iter_mut()) {
+                    let diff = fitted.pnt(*param).sub(pnt);
+                    let d1   = first.pnt(*param);
+
+                    let denom = match &second {
+                        Some(second) => d1.dot(&d1) + diff.dot(second.pnt(*param)),
+                        None         => d1.dot(&d1)
+                    };
+
+                    if denom > zero {
+                        *param = match *param - diff.dot(&d1) / denom {
+                            param if param < zero => param + one,
+                            param if param > one  => param - one,
+                            param                 => param
+                        };
+                    }
+                }
+            }
+
+            curve = Some(fitted);
+        }
+
+        curve
+    }
+
+    /// values of all basis functions at parameter t clamped to domain (cox de boor recursion)
+    fn basis(&self, t : Vect::Val) -> Vec<Vect::Val>
+    where Vect::Val : Float + HPOrd
+    {
+        let (start, end) = self.domain();
+
+        let t = t.max(start).min(end);
+        let span = self.span(t);
+
+        let zero = Vect::Val::zero();
+
+        let mut funcs = vec![zero; self.knots.len() - 1];
+        funcs[span] = Vect::Val::one();
+
+        for degree in 1..=self.degree {
+            for index in 0..(self.knots.len() - 1 - degree) {
+                let left_denom  = self.knots[index + degree] - self.knots[index];
+                let right_denom = self.knots[index + degree + 1] - self.knots[index + 1];
+
+                let left = match left_denom > zero {
+                    true  => (t - self.knots[index]) / left_denom * funcs[index],
+                    false => zero
+                };
+
+                let right = match right_denom > zero {
+                    true  => (self.knots[index + degree + 1] - t) / right_denom * funcs[index + 1],
+                    false => zero
+                };
+
+                funcs[index] = left + right;
+            }
+        }
+
+        funcs.truncate(self.pnts.len());
+        funcs
+    }
+
+    // Shape methods
+
+    /// add vector to control points (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> BSpline<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.add(&vect)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// sub vector from control points (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> BSpline<Vect::Own>
+    where Vect::Val : HSub
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.sub(&vect)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// mul vector to control points
+    pub fn mul<V : Vector<Val = Vect::Val>>(&self, vect : V) -> BSpline<Vect::Own>
+    where Vect::Val : HMul
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.mul(&vect)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// div vector from control points
+    pub fn div<V : Vector<Val = Vect::Val>>(&self, vect : V) -> BSpline<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.div(&vect)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// add vector values of control points with value
+    pub fn vadd(&self, val : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.vadd(val)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    // sub vector values of control points with value
+    pub fn vsub(&self, val : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : HSub
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.vsub(val)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// mul vector values of control points with value (scaling)
+    pub fn vmul(&self, val : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : HMul
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.vmul(val)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    // div vector values of control points with value (scaling)
+    pub fn vdiv(&self, val : Vect::Val) -> BSpline<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        BSpline{pnts : self.pnts.iter().map(|pnt| pnt.vdiv(val)).collect(), knots : self.knots.clone(), degree : self.degree}
+    }
+
+    /// bounding axe aligned rectangle of the bezier curves of the knot spans
+    pub fn bounds(&self) -> Bounds<Vect::Own>
+    where Vect::Val : Float + HPOrd,
+          usize     : Cast<Vect::Val>
+    {
+        let beziers = self.beziers();
+
+        let first = beziers[0].bounds();
+
+        let (start, end) = beziers[1..].iter().map(|bezier| bezier.bounds()).fold((first.start(), first.end()), |(start, end), bounds| {
+            (start.min(bounds.start()), end.max(bounds.end()))
+        });
+
+        Bounds::new_unchecked(start, end)
+    }
+}
@@ -0,0 +1,258 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::fit::{
+    self,
+    Fit,
+    FitType,
+    Ransac
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::ellipse::{
+    Ellipse
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds,
+    Shape
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv,
+    HNeg
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// 2D circle defined by "center" and "radius"
+pub struct Circle<V : Vector> {
+    center : V,
+    radius : V::Val
+}
+
+impl<Vect : Vector> Circle<Vect> {
+    // General methods
+
+    /// create new Circle of "center" and "radius"
+    pub fn new(center : Vect, radius : Vect::Val) -> Circle<Vect> {
+        Circle{center : center, radius : radius}
+    }
+
+    pub fn center(&self) -> Vect::Own {
+        self.center.to()
+    }
+
+    pub fn radius(&self) -> Vect::Val {
+        self.radius
+    }
+
+    /// ellipse with semi-axes along x and y axis
+    pub fn ellipse(&self) -> Ellipse<Vect::Own>
+    where Vect::Val : Zero
+    {
+        let zero = Vect::Val::zero();
+
+        Ellipse::new(self.center(), Vect::of((self.radius, zero)), Vect::of((zero, self.radius)))
+    }
+
+    /// boundary point at counterclockwise angle t in radians from x axis
+    pub fn pnt(&self, t : Vect::Val) -> Vect::Own
+    where Vect::Val : Float
+    {
+        self.center.add((self.radius * t.cos(), self.radius * t.sin()))
+    }
+
+    pub fn area(&self) -> Vect::Val
+    where Vect::Val : Float
+    {
+        Vect::Val::pi() * self.radius * self.radius
+    }
+
+    /// vertices of polygon with maximal distance "tol" between boundary and edges
+    pub fn poly(&self, tol : Vect::Val) -> Poly<Vect::Own>
+    where Vect::Val : Float + HPOrd + Cast<f64>,
+          usize     : Cast<Vect::Val>
+    {
+        self.ellipse().poly(tol)
+    }
+
+    // Shape methods
+
+    /// add vector to center (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Circle<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Circle::new(self.center.add(&vect), self.radius)
+    }
+
+    /// sub vector from center (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Circle<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Circle::new(self.center.sub(&vect), self.radius)
+    }
+
+    /// add vector values of center with value
+    pub fn vadd(&self, val : Vect::Val) -> Circle<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Circle::new(self.center.vadd(val), self.radius)
+    }
+
+    // sub vector values of center with value
+    pub fn vsub(&self, val : Vect::Val) -> Circle<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Circle::new(self.center.vsub(val), self.radius)
+    }
+
+    /// mul vector values of center and radius with value (scaling)
+    pub fn vmul(&self, val : Vect::Val) -> Circle<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Circle::new(self.center.vmul(val), self.radius * val)
+    }
+
+    // div vector values of center and radius with value (scaling)
+    pub fn vdiv(&self, val : Vect::Val) -> Circle<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Circle::new(self.center.vdiv(val), self.radius / val)
+    }
+
+    /// bounding axe aligned rectangle
+    pub fn bounds(&self) -> Bounds<Vect::Own>
+    where Vect::Val : Zero + HAdd + HSub + HNeg + HPOrd
+    {
+        let radius = match self.radius < Vect::Val::zero() {
+            false => self.radius,
+            true  => -self.radius
+        };
+
+        Bounds::new_unchecked(self.center.vsub(radius), self.center.vadd(radius))
+    }
+
+    /// check if circle contains point (boundary included)
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool
+    where Vect::Val : HAdd + HSub + HMul + HPOrd
+    {
+        let diff = pnt.sub(&self.center);
+
+        diff.dot(&diff) <= self.radius * self.radius
+    }
+}
+
+impl Circle<(f64, f64)> {
+    // Fit methods
+
+    /// circle fitted to points with residual statistics (None for less than 3 points or collinear points)
+    pub fn fit<V : Vector, I : IntoIterator<Item = V>>(pnts : I, fit : FitType) -> Option<Fit<Circle<(f64, f64)>>>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| [pnt.x().cast(), pnt.y().cast()]).collect::<Vec<[f64; 2]>>();
+
+        let (center, radius) = fit::sphere(&pnts, fit)?;
+
+        let inliers = vec![true; pnts.len()];
+        let stats   = fit::stats(&pnts, &center, radius, &inliers);
+
+        Some(Fit::new_unchecked(Circle::new((center[0], center[1]), radius), stats, inliers))
+    }
+
+    /// circle fitted to the consensus set of noisy points with residual statistics of inliers (see Ransac)
+    pub fn ransac<V : Vector, I : IntoIterator<Item = V>>(pnts : I, ransac : &Ransac) -> Option<Fit<Circle<(f64, f64)>>>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| [pnt.x().cast(), pnt.y().cast()]).collect::<Vec<[f64; 2]>>();
+
+        let (center, radius, inliers) = fit::ransac(&pnts, ransac)?;
+
+        let stats = fit::stats(&pnts, &center, radius, &inliers);
+
+        Some(Fit::new_unchecked(Circle::new((center[0], center[1]), radius), stats, inliers))
+    }
+}
+
+/// circles scaled by vectors are ellipses, so that all transformations return ellipses
+impl<Vect : Vector> Shape for Circle<Vect>
+where Vect::Val : Float + HPOrd
+{
+    type Val  = Vect::Val;
+    type Vect = Vect::Own;
+    type Own  = Ellipse<Vect::Own>;
+
+    fn add<V : Vector<Val = Self::Val>>(&self, vect : V) -> Self::Own
+    where Self::Val : HAdd
+    {
+        self.ellipse().add(vect)
+    }
+
+    fn sub<V : Vector<Val = Self::Val>>(&self, vect : V) -> Self::Own
+    where Self::Val : HSub
+    {
+        self.ellipse().sub(vect)
+    }
+
+    fn mul<V : Vector<Val = Self::Val>>(&self, vect : V) -> Self::Own
+    where Self::Val : HMul
+    {
+        self.ellipse().mul(vect)
+    }
+
+    fn div<V : Vector<Val = Self::Val>>(&self, vect : V) -> Self::Own
+    where Self::Val : HDiv
+    {
+        self.ellipse().div(vect)
+    }
+
+    fn vadd(&self, val : Self::Val) -> Self::Own
+    where Self::Val : HAdd
+    {
+        self.ellipse().vadd(val)
+    }
+
+    fn vsub(&self, val : Self::Val) -> Self::Own
+    where Self::Val : HSub
+    {
+        self.ellipse().vsub(val)
+    }
+
+    fn vmul(&self, val : Self::Val) -> Self::Own
+    where Self::Val : HMul
+    {
+        self.ellipse().vmul(val)
+    }
+
+    fn vdiv(&self, val : Self::Val) -> Self::Own
+    where Self::Val : HDiv
+    {
+        self.ellipse().vdiv(val)
+    }
+
+    fn bounds(&self) -> Bounds<Self::Vect> {
+        Circle::bounds(self)
+    }
+
+    fn contains<V : Vector<Val = Self::Val>>(&self, pnt : V) -> bool {
+        Circle::contains(self, pnt)
+    }
+}
@@ -0,0 +1,258 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::shape::d2::{
+    Bounds,
+    Shape,
+    impl_shape
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    One,
+    Two,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// upper limit of tessellation steps of full ellipses
+pub(crate) const MAX_STEPS : usize = 1 << 16;
+
+/// 2D ellipse defined by "center" and conjugate semi-axes "u" "v" where the boundary is center + u * cos(t) + v * sin(t)
+///
+/// the representation is closed under translation and scaling of single axes
+pub struct Ellipse<V : Vector> {
+    center : V,
+    u      : V,
+    v      : V
+}
+
+impl<Vect : Vector> Ellipse<Vect> {
+    // General methods
+
+    /// create new Ellipse of "center" and conjugate semi-axes "u" "v"
+    pub fn new(center : Vect, u : Vect, v : Vect) -> Ellipse<Vect> {
+        Ellipse{center : center, u : u, v : v}
+    }
+
+    /// create new Ellipse of "center", semi-axis lengths "a" "b" and counterclockwise rotation in radians of semi-axis "a"
+    pub fn axes(center : Vect, a : Vect::Val, b : Vect::Val, rotation : Vect::Val) -> Ellipse<Vect::Own>
+    where Vect::Val : Float
+    {
+        let sin = rotation.sin();
+        let cos = rotation.cos();
+
+        let u = Vect::of((a * cos, a * sin));
+        let v = Vect::of((-(b * sin), b * cos));
+
+        Ellipse::new(Vect::of(center), u, v)
+    }
+
+    pub fn center(&self) -> Vect::Own {
+        self.center.to()
+    }
+
+    pub fn u(&self) -> Vect::Own {
+        self.u.to()
+    }
+
+    pub fn v(&self) -> Vect::Own {
+        self.v.to()
+    }
+
+    /// boundary point at parameter t in radians
+    pub fn pnt(&self, t : Vect::Val) -> Vect::Own
+    where Vect::Val : Float
+    {
+        self.center.add(self.u.vmul(t.cos())).add(self.v.vmul(t.sin()))
+    }
+
+    /// enclosed area (pi * |det(u, v)|)
+    pub fn area(&self) -> Vect::Val
+    where Vect::Val : Float + HPOrd
+    {
+        let det = self.u.det(&self.v);
+
+        match det < Vect::Val::zero() {
+            false => Vect::Val::pi() * det,
+            true  => -(Vect::Val::pi() * det)
+        }
+    }
+
+    /// largest distance of boundary points to center (largest singular value of matrix [u v])
+    pub fn max_radius(&self) -> Vect::Val
+    where Vect::Val : Float + HPOrd
+    {
+        let zero = Vect::Val::zero();
+        let two  = Vect::Val::two();
+
+        let trace = self.u.dot(&self.u) + self.v.dot(&self.v);
+        let det   = self.u.det(&self.v);
+
+        let disc = trace * trace - two * two * det * det;
+        let disc = match disc < zero {
+            false => disc,
+            true  => zero
+        };
+
+        ((trace + disc.sqrt()) / two).sqrt()
+    }
+
+    /// coordinates of point in the unit circle space of the conjugate semi-axes or None if ellipse is degenerate
+    pub(crate) fn unit<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> Option<(Vect::Val, Vect::Val)>
+    where Vect::Val : Zero + HSub + HMul + HDiv + HPOrd
+    {
+        let det = self.u.det(&self.v);
+
+        if det == Vect::Val::zero() {
+            return None
+        }
+
+        let diff = pnt.sub(&self.center);
+
+        Some((diff.det(&self.v) / det, self.u.det(&diff) / det))
+    }
+
+    /// vertices of polygon with maximal distance "tol" between boundary and edges
+    pub fn poly(&self, tol : Vect::Val) -> Poly<Vect::Own>
+    where Vect::Val : Float + HPOrd + Cast<f64>,
+          usize     : Cast<Vect::Val>
+    {
+        let steps = steps(self.max_radius(), tol, Vect::Val::two() * Vect::Val::pi(), 3);
+
+        let vertices = (0..steps).map(|step| {
+            let fraction : Vect::Val = step.cast() / steps.cast();
+            self.pnt(Vect::Val::two() * Vect::Val::pi() * fraction)
+        });
+
+        Poly::new(vertices.collect())
+    }
+
+    // Shape methods
+
+    /// add vector to center (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Ellipse<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Ellipse::new(self.center.add(&vect), self.u(), self.v())
+    }
+
+    /// sub vector from center (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Ellipse<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Ellipse::new(self.center.sub(&vect), self.u(), self.v())
+    }
+
+    /// mul vector to center and semi-axes
+    pub fn mul<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Ellipse<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Ellipse::new(self.center.mul(&vect), self.u.mul(&vect), self.v.mul(&vect))
+    }
+
+    /// div vector from center and semi-axes
+    pub fn div<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Ellipse<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Ellipse::new(self.center.div(&vect), self.u.div(&vect), self.v.div(&vect))
+    }
+
+    /// add vector values of center with value
+    pub fn vadd(&self, val : Vect::Val) -> Ellipse<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Ellipse::new(self.center.vadd(val), self.u(), self.v())
+    }
+
+    // sub vector values of center with value
+    pub fn vsub(&self, val : Vect::Val) -> Ellipse<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Ellipse::new(self.center.vsub(val), self.u(), self.v())
+    }
+
+    /// mul vector values of center and semi-axes with value (scaling)
+    pub fn vmul(&self, val : Vect::Val) -> Ellipse<Vect::Own>
+    where Vect::Val : HMul
+    {
+        Ellipse::new(self.center.vmul(val), self.u.vmul(val), self.v.vmul(val))
+    }
+
+    // div vector values of center and semi-axes with value (scaling)
+    pub fn vdiv(&self, val : Vect::Val) -> Ellipse<Vect::Own>
+    where Vect::Val : HDiv
+    {
+        Ellipse::new(self.center.vdiv(val), self.u.vdiv(val), self.v.vdiv(val))
+    }
+
+    /// bounding axe aligned rectangle with half extents sqrt(u.x² + v.x²) and sqrt(u.y² + v.y²)
+    pub fn bounds(&self) -> Bounds<Vect::Own>
+    where Vect::Val : Float
+    {
+        let x = (self.u.x() * self.u.x() + self.v.x() * self.v.x()).sqrt();
+        let y = (self.u.y() * self.u.y() + self.v.y() * self.v.y()).sqrt();
+
+        Bounds::new_unchecked(self.center.sub((x, y)), self.center.add((x, y)))
+    }
+
+    /// check if ellipse contains point (boundary included)
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool
+    where Vect::Val : Zero + One + HAdd + HSub + HMul + HDiv + HPOrd
+    {
+        match self.unit(pnt) {
+            Some((a, b)) => a * a + b * b <= Vect::Val::one(),
+            None         => false
+        }
+    }
+}
+
+impl<Vect : Vector> Shape for Ellipse<Vect>
+where Vect::Val : Float + HPOrd
+{
+    type Val  = Vect::Val;
+    type Vect = Vect::Own;
+    type Own  = Ellipse<Vect::Own>;
+
+    impl_shape!(Ellipse<Vect>);
+}
+
+/// number of steps so that chords of a sweep in radians on an ellipse with maximal radius deviate at most "tol" from the boundary
+///
+/// the sagitta of the unit circle is scaled at most by the maximal radius, so the parameter step is 2 * acos(1 - tol / radius)
+pub(crate) fn steps<Val : Cast<f64>>(radius : Val, tol : Val, sweep : Val, min : usize) -> usize {
+    let radius : f64 = radius.cast();
+    let tol    : f64 = tol.cast();
+    let sweep  : f64 = sweep.cast();
+
+    if radius <= 0f64 || tol.is_nan() || tol <= 0f64 || tol >= radius {
+        return min
+    }
+
+    let step  = 2f64 * (1f64 - tol / radius).acos();
+    let steps = (sweep.abs() / step).ceil();
+
+    match steps < MAX_STEPS as f64 {
+        true  => Ord::max(steps as usize, min),
+        false => MAX_STEPS
+    }
+}
@@ -8,7 +8,8 @@ use crate::geom::d2::prim::vect::{
 
 use crate::num::{
     Zero,
-    One
+    One,
+    Float
 };
 
 use crate::ops::{
@@ -111,6 +112,35 @@ pub trait Segment {
         [self.a(), self.ab()]
     }
 
+    /// closest point on line segment to point
+    fn closest<V : Vector<Val = Self::Val>>(&self, pnt : V) -> <Self::Vect as Vector>::Own
+    where Self::Val : Zero + One + HAdd + HSub + HMul + HDiv + HPOrd
+    {
+        let [a, ab] = self.vects();
+
+        let zero = Self::Val::zero();
+        let one  = Self::Val::one();
+
+        let ab_dot_ab = ab.dot(&ab);
+
+        if ab_dot_ab <= zero {
+            return a
+        }
+
+        let r = pnt.sub(&a).dot(&ab) / ab_dot_ab;
+        let r = r.max(zero).min(one);
+
+        a.add(ab.vmul(r))
+    }
+
+    /// distance of point to line segment
+    fn dist<V : Vector<Val = Self::Val>>(&self, pnt : V) -> Self::Val
+    where Self::Val : Float + HPOrd
+    {
+        let closest = self.closest(&pnt);
+        pnt.sub(closest).len()
+    }
+
     /// optional intersection between to line segments without epsilon zero checks
     ///
     /// a + r * b = c + s * d where r, s in \[0, 1\]
@@ -32,6 +32,8 @@ use crate::rel::{
 
 pub type Vect<Val> = (Val, Val);
 
+// len is the euclidean norm, not a container size
+#[allow(clippy::len_without_is_empty)]
 pub trait Vector {
     /// value type of vector
     type Val : Copy;
@@ -22,7 +22,7 @@ use crate::rel::{
     HPOrd
 };
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Bounds<Vect : Vector> {
     start : Vect,
     end   : Vect 
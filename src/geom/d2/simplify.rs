@@ -0,0 +1,261 @@
+use std::cmp::{
+    Ordering,
+    Reverse
+};
+
+use std::collections::{
+    BinaryHeap
+};
+
+use crate::geom::d2::poly::{
+    Poly
+};
+
+use crate::geom::d2::prim::seg::{
+    PSeg,
+    Segment
+};
+
+use crate::geom::d2::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    Float
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// Ramer-Douglas-Peucker simplification of closed contour with maximal hausdorff distance tol to the original contour
+///
+/// the contour is split at its first vertex and the vertex farthest from it and every span keeps its farthest vertex
+/// until all removed vertices are within tol of the simplified edges, so that both contours are within tol of each other;
+/// with topology = true edges of the simplified contour that intersect other edges get their farthest vertices back
+pub fn rdp<V : Vector>(contour : &[V], tol : V::Val, topology : bool) -> Vec<V::Own>
+where V::Val : Float + HPOrd
+{
+    let len = contour.len();
+
+    if len <= 3 {
+        return contour.iter().map(V::of).collect()
+    }
+
+    let far = (1..len).fold(1, |far, index| {
+        match contour[index].sub(&contour[0]).len() > contour[far].sub(&contour[0]).len() {
+            true  => index,
+            false => far
+        }
+    });
+
+    let mut keep = vec![false; len];
+    keep[0]   = true;
+    keep[far] = true;
+
+    // spans of vertex indices where end index len means vertex 0
+    let mut stack = vec![(0, far), (far, len)];
+
+    while let Some((start, end)) = stack.pop() {
+        if let Some((index, dist)) = farthest(contour, start, end) {
+            if dist > tol {
+                keep[index] = true;
+                stack.push((start, index));
+                stack.push((index, end));
+            }
+        }
+    }
+
+    // closed contours need at least 3 vertices
+    if keep.iter().filter(|keep| **keep).count() < 3 {
+        for (start, end) in [(0, far), (far, len)] {
+            if let Some((index, _)) = farthest(contour, start, end) {
+                keep[index] = true;
+            }
+        }
+    }
+
+    let mut changed = topology;
+
+    while changed {
+        let kept = (0..len).filter(|index| keep[*index]).collect::<Vec<_>>();
+        let spans = (0..kept.len()).map(|index| (kept[index], kept.get(index + 1).copied().unwrap_or(len))).collect::<Vec<_>>();
+
+        changed = false;
+
+        for (index, (start, end)) in spans.iter().enumerate() {
+            let others = spans.iter().enumerate().filter(|(other, _)| *other != index).map(|(_, span)| *span);
+
+            if conflict(contour, *start, *end, others) {
+                if let Some((vertex, _)) = farthest(contour, *start, *end) {
+                    keep[vertex] = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    (0..len).filter(|index| keep[*index]).map(|index| V::of(&contour[index])).collect()
+}
+
+/// Visvalingam-Whyatt simplification of closed contour with maximal hausdorff distance tol to the original contour
+///
+/// vertices are removed in order of the area of the triangle with their neighbours as long as all original vertices between
+/// the neighbours are within tol of the new edge; with topology = true removals whose new edge intersects other edges are skipped
+pub fn visvalingam<V : Vector>(contour : &[V], tol : V::Val, topology : bool) -> Vec<V::Own>
+where V::Val : Float + HPOrd
+{
+    let len = contour.len();
+
+    if len <= 3 {
+        return contour.iter().map(V::of).collect()
+    }
+
+    let mut prev = (0..len).map(|index| (index + len - 1) % len).collect::<Vec<_>>();
+    let mut next = (0..len).map(|index| (index + 1) % len).collect::<Vec<_>>();
+
+    let mut removed = vec![false; len];
+    let mut version = vec![0usize; len];
+    let mut count   = len;
+
+    let area = |prev : usize, index : usize, next : usize| {
+        let a = contour[prev].sub(&contour[index]);
+        let b = contour[next].sub(&contour[index]);
+        let det = a.det(&b);
+
+        match det < V::Val::zero() {
+            false => det,
+            true  => -det
+        }
+    };
+
+    let mut heap = (0..len).map(|index| Reverse(Entry{area : area(prev[index], index, next[index]), index : index, version : 0})).collect::<BinaryHeap<_>>();
+
+    while let Some(Reverse(entry)) = heap.pop() {
+        if count <= 3 {
+            break;
+        }
+
+        let index = entry.index;
+
+        if removed[index] || entry.version != version[index] {
+            continue;
+        }
+
+        let (start, end) = (prev[index], next[index]);
+
+        // span of original vertices replaced by the new edge
+        let span_end = match end <= start {
+            true  => end + len,
+            false => end
+        };
+
+        if farthest(contour, start, span_end).is_some_and(|(_, dist)| dist > tol) {
+            continue;
+        }
+
+        if topology {
+            let mut others = Vec::new();
+            let mut current = end;
+
+            while current != start {
+                others.push((current, next[current]));
+                current = next[current];
+            }
+
+            if conflict(contour, start, end, others.into_iter()) {
+                continue;
+            }
+        }
+
+        removed[index] = true;
+        count -= 1;
+
+        next[start] = end;
+        prev[end]   = start;
+
+        for neighbour in [start, end] {
+            version[neighbour] += 1;
+            heap.push(Reverse(Entry{area : area(prev[neighbour], neighbour, next[neighbour]), index : neighbour, version : version[neighbour]}));
+        }
+    }
+
+    (0..len).filter(|index| !removed[*index]).map(|index| V::of(&contour[index])).collect()
+}
+
+/// vertex between start and end (exclusive, indices modulo contour length) with largest distance to edge from start to end
+fn farthest<V : Vector>(contour : &[V], start : usize, end : usize) -> Option<(usize, V::Val)>
+where V::Val : Float + HPOrd
+{
+    let len  = contour.len();
+    let edge = PSeg::new(V::of(&contour[start % len]), V::of(&contour[end % len]));
+
+    ((start + 1)..end).fold(None, |far, index| {
+        let dist = edge.dist(&contour[index % len]);
+
+        match far {
+            Some((_, max)) if max >= dist => far,
+            _ => Some((index % len, dist))
+        }
+    })
+}
+
+/// checks if edge from start to end intersects edges of others apart from touching adjacent edges at shared vertices
+fn conflict<V : Vector, I : Iterator<Item = (usize, usize)>>(contour : &[V], start : usize, end : usize, others : I) -> bool
+where V::Val : Float + HPOrd
+{
+    let len = contour.len();
+
+    let a = V::of(&contour[start % len]);
+    let b = V::of(&contour[end % len]);
+
+    for (other_start, other_end) in others {
+        let c = V::of(&contour[other_start % len]);
+        let d = V::of(&contour[other_end % len]);
+
+        let shared = match (other_start % len, other_end % len) {
+            (s, _) if s == end % len   => Some((&b, &a, &d)),
+            (_, e) if e == start % len => Some((&a, &b, &c)),
+            _ => None
+        };
+
+        let conflict = match shared {
+            Some((shared, own, other)) => Poly::<V::Own>::collinear_overlap(shared, own, other),
+            None                       => Poly::<V::Own>::segments_intersect(&a, &b, &c, &d)
+        };
+
+        if conflict {
+            return true
+        }
+    }
+
+    false
+}
+
+/// entry of vertex in priority queue that is outdated if its version is behind the version of the vertex
+struct Entry<Val> {
+    area    : Val,
+    index   : usize,
+    version : usize
+}
+
+impl<Val : HPOrd> PartialEq for Entry<Val> {
+    fn eq(&self, other : &Entry<Val>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Val : HPOrd> Eq for Entry<Val> {}
+
+impl<Val : HPOrd> PartialOrd for Entry<Val> {
+    fn partial_cmp(&self, other : &Entry<Val>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Val : HPOrd> Ord for Entry<Val> {
+    fn cmp(&self, other : &Entry<Val>) -> Ordering {
+        self.area.partial_cmp(&other.area).unwrap_or(Ordering::Equal).then(self.index.cmp(&other.index))
+    }
+}
@@ -0,0 +1,300 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::contour;
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// smoothing method of closed contours
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmoothType {
+    /// corner cutting at a quarter and three quarters of every edge per iteration
+    Chaikin,
+    /// laplacian smoothing with alternating shrinking and inflating steps per iteration (taubin)
+    Laplacian,
+    /// centripetal catmull-rom spline through the vertices
+    CatmullRom,
+    /// uniform cubic b-spline with the vertices as control points
+    BSpline
+}
+
+/// smoothing of closed contours where corners with turning angle above the corner angle stay fixed
+#[derive(Clone, Copy, Debug)]
+pub struct Smooth {
+    smooth       : SmoothType,
+    iterations   : usize,
+    samples      : usize,
+    corner_angle : f64
+}
+
+impl Smooth {
+    /// smoothing with 2 iterations, 4 spline samples per edge and corner angle of 60 degrees
+    pub fn new(smooth : SmoothType) -> Smooth {
+        Smooth{smooth : smooth, iterations : 2, samples : 4, corner_angle : std::f64::consts::FRAC_PI_3}
+    }
+
+    /// number of iterations of chaikin and laplacian smoothing
+    pub fn iterations(mut self, iterations : usize) -> Smooth {
+        self.iterations = iterations;
+        self
+    }
+
+    /// number of points per edge of catmull-rom and b-spline smoothing
+    pub fn samples(mut self, samples : usize) -> Smooth {
+        self.samples = Ord::max(samples, 1);
+        self
+    }
+
+    /// minimal turning angle in radians between incoming and outgoing edge of vertices that stay fixed
+    pub fn corner_angle(mut self, corner_angle : f64) -> Smooth {
+        self.corner_angle = corner_angle;
+        self
+    }
+
+    /// smoothed closed contour
+    pub fn contour<V : Vector>(&self, contour : &[V]) -> Vec<Vect<f64>>
+    where V::Val : Cast<f64>
+    {
+        let contour = contour.iter().map(|vertex| (vertex.x().cast(), vertex.y().cast())).collect::<Vec<Vect<f64>>>();
+
+        if contour.len() < 3 {
+            return contour
+        }
+
+        let corners = corners(&contour, self.corner_angle);
+
+        match self.smooth {
+            SmoothType::Chaikin    => self.chaikin(contour, corners),
+            SmoothType::Laplacian  => self.laplacian(contour, &corners),
+            SmoothType::CatmullRom => self.catmull_rom(&contour, &corners),
+            SmoothType::BSpline    => self.b_spline(&contour, &corners)
+        }
+    }
+
+    /// cuts every edge at a quarter and three quarters and keeps corners between their cuts
+    fn chaikin(&self, mut contour : Vec<Vect<f64>>, mut corners : Vec<bool>) -> Vec<Vect<f64>> {
+        for _ in 0..self.iterations {
+            let len = contour.len();
+
+            let mut cut = Vec::with_capacity(2 * len + 1);
+            let mut cut_corners = Vec::with_capacity(2 * len + 1);
+
+            for index in 0..len {
+                let a = contour[index];
+                let b = contour[(index + 1) % len];
+
+                if corners[index] {
+                    cut.push(a);
+                    cut_corners.push(true);
+                }
+
+                cut.push(a.vmul(0.75f64).add(b.vmul(0.25f64)));
+                cut.push(a.vmul(0.25f64).add(b.vmul(0.75f64)));
+                cut_corners.extend([false, false]);
+            }
+
+            contour = cut;
+            corners = cut_corners;
+        }
+
+        contour
+    }
+
+    /// moves vertices towards and away from the mean of their neighbours so that the contour does not shrink
+    fn laplacian(&self, mut contour : Vec<Vect<f64>>, corners : &[bool]) -> Vec<Vect<f64>> {
+        let len = contour.len();
+
+        // factors of taubin smoothing with pass band frequency of about 0.1
+        for _ in 0..self.iterations {
+            for factor in [0.5f64, -0.53f64] {
+                contour = (0..len).map(|index| {
+                    let pnt = contour[index];
+
+                    match corners[index] {
+                        true  => pnt,
+                        false => {
+                            let mean = contour[(index + len - 1) % len].add(contour[(index + 1) % len]).vmul(0.5f64);
+                            pnt.add(mean.sub(pnt).vmul(factor))
+                        }
+                    }
+                }).collect();
+            }
+        }
+
+        contour
+    }
+
+    /// samples spline through every edge with tangents of edges from corners mirrored to keep the corners sharp
+    fn catmull_rom(&self, contour : &[Vect<f64>], corners : &[bool]) -> Vec<Vect<f64>> {
+        let len = contour.len();
+
+        let mut smooth = Vec::with_capacity(len * self.samples);
+
+        for index in 0..len {
+            let next = (index + 1) % len;
+
+            let p1 = contour[index];
+            let p2 = contour[next];
+
+            let p0 = match corners[index] {
+                true  => p1.vmul(2f64).sub(p2),
+                false => contour[(index + len - 1) % len]
+            };
+
+            let p3 = match corners[next] {
+                true  => p2.vmul(2f64).sub(p1),
+                false => contour[(next + 1) % len]
+            };
+
+            // knot intervals of square roots of edge lengths avoid cusps and self-intersections within edges
+            let knot = |a : Vect<f64>, b : Vect<f64>| f64::max(b.sub(a).len().sqrt(), 1e-12f64);
+
+            let t0 = 0f64;
+            let t1 = t0 + knot(p0, p1);
+            let t2 = t1 + knot(p1, p2);
+            let t3 = t2 + knot(p2, p3);
+
+            let lerp = |a : Vect<f64>, b : Vect<f64>, ta : f64, tb : f64, t : f64| a.vmul((tb - t) / (tb - ta)).add(b.vmul((t - ta) / (tb - ta)));
+
+            for sample in 0..self.samples {
+                let t = t1 + (t2 - t1) * sample as f64 / self.samples as f64;
+
+                let a1 = lerp(p0, p1, t0, t1, t);
+                let a2 = lerp(p1, p2, t1, t2, t);
+                let a3 = lerp(p2, p3, t2, t3, t);
+
+                let b1 = lerp(a1, a2, t0, t2, t);
+                let b2 = lerp(a2, a3, t1, t3, t);
+
+                smooth.push(lerp(b1, b2, t1, t2, t));
+            }
+        }
+
+        smooth
+    }
+
+    /// samples spline of control points where corners are tripled so that the spline passes through them
+    fn b_spline(&self, contour : &[Vect<f64>], corners : &[bool]) -> Vec<Vect<f64>> {
+        let control = contour.iter().zip(corners).flat_map(|(pnt, corner)| {
+            let count = match corner {
+                true  => 3,
+                false => 1
+            };
+
+            std::iter::repeat_n(*pnt, count)
+        }).collect::<Vec<_>>();
+
+        let len = control.len();
+
+        let mut smooth = Vec::with_capacity(len * self.samples);
+
+        // span from control point index to the next one where tripled corners are hit exactly at the start of a span
+        for index in 0..len {
+            let p0 = control[(index + len - 1) % len];
+            let p1 = control[index];
+            let p2 = control[(index + 1) % len];
+            let p3 = control[(index + 2) % len];
+
+            for sample in 0..self.samples {
+                let t = sample as f64 / self.samples as f64;
+                let s = 1f64 - t;
+
+                let w0 = s * s * s / 6f64;
+                let w1 = (3f64 * t * t * t - 6f64 * t * t + 4f64) / 6f64;
+                let w2 = (-3f64 * t * t * t + 3f64 * t * t + 3f64 * t + 1f64) / 6f64;
+                let w3 = t * t * t / 6f64;
+
+                smooth.push(p0.vmul(w0).add(p1.vmul(w1)).add(p2.vmul(w2)).add(p3.vmul(w3)));
+            }
+        }
+
+        smooth.dedup();
+        smooth
+    }
+}
+
+/// flags of vertices of closed contour with turning angle in radians between incoming and outgoing edge above angle
+pub fn corners<V : Vector>(contour : &[V], angle : f64) -> Vec<bool>
+where V::Val : Cast<f64>
+{
+    let len = contour.len();
+
+    (0..len).map(|index| {
+        let prev : Vect<f64> = (contour[(index + len - 1) % len].x().cast(), contour[(index + len - 1) % len].y().cast());
+        let pnt  : Vect<f64> = (contour[index].x().cast(), contour[index].y().cast());
+        let next : Vect<f64> = (contour[(index + 1) % len].x().cast(), contour[(index + 1) % len].y().cast());
+
+        let a = pnt.sub(prev);
+        let b = next.sub(pnt);
+
+        // vertices of zero length edges are not corners
+        match a.len() > 0f64 && b.len() > 0f64 {
+            true  => a.det(b).atan2(a.dot(b)).abs() > angle,
+            false => false
+        }
+    }).collect()
+}
+
+/// resample closed contour with uniform arc length spacing of at most about "spacing" between corners
+///
+/// corners with turning angle above corner angle are kept and every part between two corners is resampled on its own
+/// with the spacing adjusted to divide its length evenly; contours without corners start at the first vertex
+pub fn resample<V : Vector>(contour : &[V], spacing : f64, corner_angle : f64) -> Vec<Vect<f64>>
+where V::Val : Cast<f64>
+{
+    let contour = contour.iter().map(|vertex| (vertex.x().cast(), vertex.y().cast())).collect::<Vec<Vect<f64>>>();
+    let len = contour.len();
+
+    if len < 2 || spacing.is_nan() || spacing <= 0f64 {
+        return contour
+    }
+
+    let corners = corners(&contour, corner_angle);
+    let starts = (0..len).filter(|index| corners[*index]).collect::<Vec<_>>();
+
+    if starts.is_empty() {
+        let count = Ord::max((contour::len(&contour) / spacing).round() as usize, 3);
+        return contour::resample(&contour, count)
+    }
+
+    let mut resampled = Vec::new();
+
+    for (index, start) in starts.iter().enumerate() {
+        // part from corner to next corner where end index beyond len wraps around
+        let end = match starts.get(index + 1) {
+            Some(end) => *end,
+            None      => starts[0] + len
+        };
+
+        let part = (*start..=end).map(|index| contour[index % len]).collect::<Vec<_>>();
+        let part_len = part.windows(2).map(|edge| edge[1].sub(edge[0]).len()).sum::<f64>();
+
+        let count = Ord::max((part_len / spacing).round() as usize, 1);
+        let step = part_len / count as f64;
+
+        resampled.push(part[0]);
+
+        // arc length at start of current edge
+        let mut offset = 0f64;
+        let mut sample = 1;
+
+        for edge in part.windows(2) {
+            let edge_len = edge[1].sub(edge[0]).len();
+
+            while sample < count && step * sample as f64 <= offset + edge_len {
+                let r = (step * sample as f64 - offset) / edge_len;
+
+                resampled.push(edge[0].add(edge[1].sub(edge[0]).vmul(r)));
+                sample += 1;
+            }
+
+            offset += edge_len;
+        }
+    }
+
+    resampled
+}
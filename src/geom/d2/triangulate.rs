@@ -0,0 +1,771 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+    VecDeque
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d2::hull::{
+    self,
+    orient
+};
+
+use crate::geom::d2::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::mesh::ind::{
+    IndSeg,
+    IndTri
+};
+
+/// missing neighbour of triangle
+const NONE : usize = usize::MAX;
+
+/// triangulation of polygon with holes by ear clipping
+///
+/// the first contour is the outer contour and all further contours are holes in any orientation;
+/// holes are bridged to the outer contour and the triangles are counterclockwise with indices
+/// into the concatenated contours
+pub fn ear_clipping<V : Vector>(contours : &[&[V]]) -> Vec<IndTri>
+where V::Val : Cast<f64>
+{
+    let pnts = contours.iter().flat_map(|contour| contour.iter()).map(|pnt| (pnt.x().cast(), pnt.y().cast())).collect::<Vec<Vect<f64>>>();
+
+    let mut offset = 0;
+    let mut rings  = Vec::new();
+
+    for contour in contours {
+        rings.push((offset..offset + contour.len()).collect::<Vec<_>>());
+        offset += contour.len();
+    }
+
+    let Some(outer) = rings.first() else {
+        return Vec::new()
+    };
+
+    if outer.len() < 3 {
+        return Vec::new()
+    }
+
+    // outer contour counterclockwise and holes clockwise
+    let mut poly = oriented(&pnts, outer.clone(), true);
+
+    let mut holes = rings[1..].iter().filter(|hole| hole.len() >= 3).map(|hole| oriented(&pnts, hole.clone(), false)).collect::<Vec<_>>();
+
+    holes.sort_by(|a, b| {
+        let max = |hole : &Vec<usize>| hole.iter().map(|index| pnts[*index].x()).fold(f64::NEG_INFINITY, f64::max);
+        max(b).total_cmp(&max(a))
+    });
+
+    for hole in holes {
+        if let Some(bridged) = bridge(&pnts, &poly, &hole) {
+            poly = bridged;
+        }
+    }
+
+    clip(&pnts, poly)
+}
+
+/// constrained delaunay triangulation of points with required segments as edges
+///
+/// duplicate points are merged into their first occurrence and segments that cross already inserted segments are dropped;
+/// without interior the triangles cover the convex hull of the points;
+/// with interior only triangles enclosed by an odd number of segments are kept (e.g. inside closed contours and outside of holes);
+/// the triangles are counterclockwise with indices into the points
+pub fn constrained_delaunay<V : Vector>(pnts : &[V], segments : &[IndSeg], interior : bool) -> Vec<IndTri>
+where V::Val : Cast<f64>
+{
+    let pnts = pnts.iter().map(|pnt| (pnt.x().cast(), pnt.y().cast())).collect::<Vec<Vect<f64>>>();
+
+    if pnts.len() < 3 {
+        return Vec::new()
+    }
+
+    let mut cdt = Cdt::new(&pnts);
+    let mut map = vec![NONE; pnts.len()];
+
+    for index in order(&pnts) {
+        map[index] = cdt.insert(index);
+    }
+
+    for segment in segments {
+        let (a, b) = (map[segment.a()], map[segment.b()]);
+
+        if a != b {
+            cdt.constrain(a, b);
+        }
+    }
+
+    // the finite super triangle can take the place of convex hull edges, so that they are inserted as well to cover the hull
+    if !interior {
+        let vertices = pnts.iter().zip(&map).map(|(pnt, vertex)| ((pnt.x().to_bits(), pnt.y().to_bits()), *vertex)).collect::<HashMap<_, _>>();
+        let hull = hull::monotone_chain(&pnts).into_iter().map(|pnt| vertices[&(pnt.x().to_bits(), pnt.y().to_bits())]).collect::<Vec<_>>();
+
+        for (index, a) in hull.iter().enumerate() {
+            cdt.constrain(*a, hull[(index + 1) % hull.len()]);
+        }
+    }
+
+    cdt.triangles(interior)
+}
+
+/// ring of indices in counterclockwise (ccw) or clockwise orientation
+fn oriented(pnts : &[Vect<f64>], mut ring : Vec<usize>, ccw : bool) -> Vec<usize> {
+    let area = (0..ring.len()).map(|index| {
+        let a = pnts[ring[index]];
+        let b = pnts[ring[(index + 1) % ring.len()]];
+
+        a.x() * b.y() - b.x() * a.y()
+    }).sum::<f64>();
+
+    if (area > 0f64) != ccw {
+        ring.reverse();
+    }
+
+    ring
+}
+
+/// polygon with hole connected through a bridge from the rightmost hole vertex to a visible polygon vertex (eberly)
+fn bridge(pnts : &[Vect<f64>], poly : &[usize], hole : &[usize]) -> Option<Vec<usize>> {
+    let start = (0..hole.len()).max_by(|a, b| pnts[hole[*a]].x().total_cmp(&pnts[hole[*b]].x()))?;
+    let m = pnts[hole[start]];
+
+    // nearest edge hit by ray from m towards positive x
+    let mut hit : Option<(f64, usize)> = None;
+
+    for index in 0..poly.len() {
+        let p = pnts[poly[index]];
+        let q = pnts[poly[(index + 1) % poly.len()]];
+
+        if (p.y() > m.y()) == (q.y() > m.y()) {
+            continue;
+        }
+
+        let x = p.x() + (m.y() - p.y()) / (q.y() - p.y()) * (q.x() - p.x());
+
+        if x >= m.x() && hit.is_none_or(|(best, _)| x < best) {
+            hit = Some((x, index));
+        }
+    }
+
+    let (x, edge) = hit?;
+    let i = (x, m.y());
+
+    let candidate = match pnts[poly[edge]].x() > pnts[poly[(edge + 1) % poly.len()]].x() {
+        true  => poly[edge],
+        false => poly[(edge + 1) % poly.len()]
+    };
+
+    let p = pnts[candidate];
+
+    // reflex vertices inside triangle m i p may block the view to p
+    let mut vertex = candidate;
+    let mut best   = (f64::NEG_INFINITY, f64::INFINITY);
+
+    for index in 0..poly.len() {
+        let prev = pnts[poly[(index + poly.len() - 1) % poly.len()]];
+        let curr = pnts[poly[index]];
+        let next = pnts[poly[(index + 1) % poly.len()]];
+
+        if poly[index] == candidate || orient(prev, curr, next) > 0f64 || !inside(m, i, p, curr) || curr == m {
+            continue;
+        }
+
+        let diff = curr.sub(m);
+        let cos  = diff.x() / diff.len();
+
+        if cos > best.0 || (cos == best.0 && diff.len() < best.1) {
+            best   = (cos, diff.len());
+            vertex = poly[index];
+        }
+    }
+
+    // bridge at the occurrence of the vertex whose wedge contains m
+    let occurrences = (0..poly.len()).filter(|index| poly[*index] == vertex).collect::<Vec<_>>();
+
+    let position = occurrences.iter().copied().find(|index| {
+        let prev = pnts[poly[(index + poly.len() - 1) % poly.len()]];
+        let next = pnts[poly[(index + 1) % poly.len()]];
+
+        wedge(prev, pnts[vertex], next, m)
+    }).or(occurrences.first().copied())?;
+
+    let mut bridged = poly[..=position].to_vec();
+
+    bridged.extend((0..=hole.len()).map(|index| hole[(start + index) % hole.len()]));
+    bridged.extend_from_slice(&poly[position..]);
+
+    Some(bridged)
+}
+
+/// triangles of counterclockwise polygon by clipping ears
+fn clip(pnts : &[Vect<f64>], poly : Vec<usize>) -> Vec<IndTri> {
+    let len = poly.len();
+
+    let mut prev = (0..len).map(|index| (index + len - 1) % len).collect::<Vec<_>>();
+    let mut next = (0..len).map(|index| (index + 1) % len).collect::<Vec<_>>();
+
+    let mut tris  = Vec::with_capacity(len.saturating_sub(2));
+    let mut count = len;
+    let mut curr  = 0;
+    let mut fails = 0;
+
+    let pnt = |index : usize| pnts[poly[index]];
+
+    while count > 3 {
+        let (a, b, c) = (prev[curr], curr, next[curr]);
+
+        let ear = orient(pnt(a), pnt(b), pnt(c)) > 0f64 && {
+            let mut other = next[c];
+            let mut empty = true;
+
+            while other != a {
+                let p = pnt(other);
+
+                // bridge duplicates of the corners do not block the ear
+                if p != pnt(a) && p != pnt(b) && p != pnt(c) && inside(pnt(a), pnt(b), pnt(c), p) {
+                    empty = false;
+                    break;
+                }
+
+                other = next[other];
+            }
+
+            empty
+        };
+
+        // without ears degenerate vertices are dropped first and then any convex vertex is clipped
+        let forced = fails >= count && match fails >= 2 * count {
+            false => orient(pnt(a), pnt(b), pnt(c)) == 0f64,
+            true  => true
+        };
+
+        if ear || forced {
+            if orient(pnt(a), pnt(b), pnt(c)) > 0f64 {
+                tris.push(IndTri::new(poly[a], poly[b], poly[c]));
+            }
+
+            next[a] = c;
+            prev[c] = a;
+
+            count -= 1;
+            fails  = 0;
+            curr   = c;
+        } else {
+            fails += 1;
+            curr   = c;
+        }
+    }
+
+    let (a, b, c) = (prev[curr], curr, next[curr]);
+
+    if orient(pnt(a), pnt(b), pnt(c)) > 0f64 {
+        tris.push(IndTri::new(poly[a], poly[b], poly[c]));
+    }
+
+    tris
+}
+
+/// insertion order of points along a snake curve through vertical strips, so that point location walks stay short
+fn order(pnts : &[Vect<f64>]) -> Vec<usize> {
+    let (min, max) = pnts.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), pnt| (f64::min(min, pnt.x()), f64::max(max, pnt.x())));
+
+    let strips = ((pnts.len() as f64 / 2f64).sqrt().ceil() as usize).max(1);
+    let width  = f64::max(max - min, f64::MIN_POSITIVE);
+
+    let strip = |pnt : Vect<f64>| Ord::min(((pnt.x() - min) / width * strips as f64) as usize, strips - 1);
+
+    let mut order = (0..pnts.len()).collect::<Vec<_>>();
+
+    order.sort_by(|a, b| {
+        let (sa, sb) = (strip(pnts[*a]), strip(pnts[*b]));
+
+        sa.cmp(&sb).then_with(|| match sa % 2 == 0 {
+            true  => pnts[*a].y().total_cmp(&pnts[*b].y()),
+            false => pnts[*b].y().total_cmp(&pnts[*a].y())
+        })
+    });
+
+    order
+}
+
+/// positive if d lies inside circumcircle of counterclockwise triangle a b c
+fn incircle(a : Vect<f64>, b : Vect<f64>, c : Vect<f64>, d : Vect<f64>) -> f64 {
+    let (ad, bd, cd) = (a.sub(d), b.sub(d), c.sub(d));
+
+    let (al, bl, cl) = (ad.dot(ad), bd.dot(bd), cd.dot(cd));
+
+    ad.x() * (bd.y() * cl - bl * cd.y()) - ad.y() * (bd.x() * cl - bl * cd.x()) + al * (bd.x() * cd.y() - bd.y() * cd.x())
+}
+
+/// check if p lies in triangle a b c of any orientation (boundary included)
+fn inside(a : Vect<f64>, b : Vect<f64>, c : Vect<f64>, p : Vect<f64>) -> bool {
+    let orients = [orient(a, b, p), orient(b, c, p), orient(c, a, p)];
+
+    orients.iter().all(|orient| *orient >= 0f64) || orients.iter().all(|orient| *orient <= 0f64)
+}
+
+/// check if direction from vertex p to m lies strictly within the interior wedge of polygon vertex p between prev and next
+fn wedge(prev : Vect<f64>, p : Vect<f64>, next : Vect<f64>, m : Vect<f64>) -> bool {
+    match orient(prev, p, next) >= 0f64 {
+        true  => orient(prev, p, m) > 0f64 && orient(p, next, m) > 0f64,
+        false => orient(prev, p, m) > 0f64 || orient(p, next, m) > 0f64
+    }
+}
+
+/// triangulation with neighbours across the edge opposite of every triangle vertex and a super triangle around all points
+struct Cdt {
+    pnts        : Vec<Vect<f64>>,
+    tris        : Vec<[usize; 3]>,
+    nbrs        : Vec<[usize; 3]>,
+    vtri        : Vec<usize>,
+    constrained : HashSet<(usize, usize)>,
+    last        : usize
+}
+
+impl Cdt {
+    fn new(pnts : &[Vect<f64>]) -> Cdt {
+        let (min, max) = pnts.iter().fold(((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)), |(min, max), pnt| {
+            ((f64::min(min.0, pnt.x()), f64::min(min.1, pnt.y())), (f64::max(max.0, pnt.x()), f64::max(max.1, pnt.y())))
+        });
+
+        let center = min.add(max).vdiv(2f64);
+        let size   = f64::max(f64::max(max.0 - min.0, max.1 - min.1), 1f64) * 100f64;
+
+        let len = pnts.len();
+
+        let mut all = pnts.to_vec();
+
+        all.push((center.x() - 2f64 * size, center.y() - size));
+        all.push((center.x() + 2f64 * size, center.y() - size));
+        all.push((center.x(), center.y() + 2f64 * size));
+
+        let mut vtri = vec![NONE; len + 3];
+
+        vtri[len]     = 0;
+        vtri[len + 1] = 0;
+        vtri[len + 2] = 0;
+
+        Cdt{pnts : all, tris : vec![[len, len + 1, len + 2]], nbrs : vec![[NONE; 3]], vtri : vtri, constrained : HashSet::new(), last : 0}
+    }
+
+    /// number of real points without super triangle
+    fn real(&self) -> usize {
+        self.pnts.len() - 3
+    }
+
+    fn set(&mut self, tri : usize, vertices : [usize; 3], nbrs : [usize; 3]) {
+        if tri == self.tris.len() {
+            self.tris.push(vertices);
+            self.nbrs.push(nbrs);
+        } else {
+            self.tris[tri] = vertices;
+            self.nbrs[tri] = nbrs;
+        }
+
+        for vertex in vertices {
+            self.vtri[vertex] = tri;
+        }
+    }
+
+    /// replaces neighbour old of triangle by new
+    fn relink(&mut self, tri : usize, old : usize, new : usize) {
+        if tri == NONE {
+            return
+        }
+
+        if let Some(index) = (0..3).find(|index| self.nbrs[tri][*index] == old) {
+            self.nbrs[tri][index] = new;
+        }
+    }
+
+    fn pnt(&self, vertex : usize) -> Vect<f64> {
+        self.pnts[vertex]
+    }
+
+    /// inserts point and returns its vertex or the vertex of an equal point
+    fn insert(&mut self, vertex : usize) -> usize {
+        let p = self.pnt(vertex);
+
+        let mut tri   = self.last;
+        let mut steps = 0;
+
+        // visibility walk towards the point
+        let (tri, zeros) = loop {
+            let [a, b, c] = self.tris[tri];
+
+            if let Some(equal) = [a, b, c].into_iter().find(|vertex| self.pnt(*vertex) == p) {
+                return equal
+            }
+
+            let orients = [orient(self.pnt(b), self.pnt(c), p), orient(self.pnt(c), self.pnt(a), p), orient(self.pnt(a), self.pnt(b), p)];
+
+            // rotating start edge avoids cycles on degenerate inputs
+            let step = (0..3).map(|offset| (offset + steps) % 3).find(|index| orients[*index] < 0f64 && self.nbrs[tri][*index] != NONE);
+
+            match step {
+                Some(index) if steps < 4 * self.tris.len() + 16 => {
+                    tri = self.nbrs[tri][index];
+                    steps += 1;
+                },
+                _ => break (tri, (0..3).filter(|index| orients[*index] == 0f64).collect::<Vec<_>>())
+            }
+        };
+
+        match zeros[..] {
+            [edge] if self.nbrs[tri][edge] != NONE => self.split_edge(tri, edge, vertex),
+            _ => self.split(tri, vertex)
+        }
+
+        vertex
+    }
+
+    /// splits triangle into three triangles around vertex p
+    fn split(&mut self, t : usize, p : usize) {
+        let [a, b, c] = self.tris[t];
+        let [na, nb, nc] = self.nbrs[t];
+
+        let t1 = self.tris.len();
+        let t2 = t1 + 1;
+
+        self.set(t, [a, b, p], [t1, t2, nc]);
+        self.set(t1, [b, c, p], [t2, t, na]);
+        self.set(t2, [c, a, p], [t, t1, nb]);
+
+        self.relink(na, t, t1);
+        self.relink(nb, t, t2);
+
+        self.last = t;
+
+        self.legalize(vec![(t, 2), (t1, 2), (t2, 2)]);
+    }
+
+    /// splits edge opposite of vertex i of triangle and the neighbour across into four triangles around vertex p
+    fn split_edge(&mut self, t : usize, i : usize, p : usize) {
+        let (a, b, c) = (self.tris[t][i], self.tris[t][(i + 1) % 3], self.tris[t][(i + 2) % 3]);
+        let (nt_b, nt_c) = (self.nbrs[t][(i + 1) % 3], self.nbrs[t][(i + 2) % 3]);
+
+        let u = self.nbrs[t][i];
+        let j = (0..3).find(|index| self.tris[u][*index] != b && self.tris[u][*index] != c).unwrap();
+
+        let d = self.tris[u][j];
+        let (nu_c, nu_b) = (self.nbrs[u][(j + 1) % 3], self.nbrs[u][(j + 2) % 3]);
+
+        let t1 = self.tris.len();
+        let t3 = t1 + 1;
+
+        self.set(t, [a, b, p], [t3, t1, nt_c]);
+        self.set(t1, [a, p, c], [u, nt_b, t]);
+        self.set(u, [d, c, p], [t1, t3, nu_b]);
+        self.set(t3, [d, p, b], [t, nu_c, u]);
+
+        self.relink(nt_b, t, t1);
+        self.relink(nu_c, u, t3);
+
+        // split segments stay constrained
+        if self.constrained.remove(&key(b, c)) {
+            self.constrained.insert(key(b, p));
+            self.constrained.insert(key(p, c));
+        }
+
+        self.last = t;
+
+        self.legalize(vec![(t, 2), (t1, 1), (u, 2), (t3, 1)]);
+    }
+
+    /// flips edges opposite of the new vertex until they are locally delaunay
+    fn legalize(&mut self, mut stack : Vec<(usize, usize)>) {
+        while let Some((t, i)) = stack.pop() {
+            let n = self.nbrs[t][i];
+
+            if n == NONE {
+                continue;
+            }
+
+            let (p, b, c) = (self.tris[t][i], self.tris[t][(i + 1) % 3], self.tris[t][(i + 2) % 3]);
+
+            if self.constrained.contains(&key(b, c)) {
+                continue;
+            }
+
+            let d = self.opposite(n, b, c);
+
+            if incircle(self.pnt(p), self.pnt(b), self.pnt(c), self.pnt(d)) > 0f64 {
+                let (t, n) = self.flip(t, i);
+
+                stack.push((t, 0));
+                stack.push((n, 0));
+            }
+        }
+    }
+
+    /// vertex of triangle that is not on edge a b
+    fn opposite(&self, tri : usize, a : usize, b : usize) -> usize {
+        self.tris[tri].into_iter().find(|vertex| *vertex != a && *vertex != b).unwrap()
+    }
+
+    /// flips edge opposite of vertex i of triangle, so that both triangles start with that vertex
+    fn flip(&mut self, t : usize, i : usize) -> (usize, usize) {
+        let (p, b, c) = (self.tris[t][i], self.tris[t][(i + 1) % 3], self.tris[t][(i + 2) % 3]);
+        let (t_b, t_c) = (self.nbrs[t][(i + 1) % 3], self.nbrs[t][(i + 2) % 3]);
+
+        let n = self.nbrs[t][i];
+        let j = (0..3).find(|index| self.tris[n][*index] != b && self.tris[n][*index] != c).unwrap();
+
+        let d = self.tris[n][j];
+        let (n_c, n_b) = (self.nbrs[n][(j + 1) % 3], self.nbrs[n][(j + 2) % 3]);
+
+        self.set(t, [p, b, d], [n_c, n, t_c]);
+        self.set(n, [p, d, c], [n_b, t_b, t]);
+
+        self.relink(n_c, n, t);
+        self.relink(t_b, t, n);
+
+        self.last = t;
+
+        (t, n)
+    }
+
+    /// triangle with edge a b and index of its vertex opposite of the edge
+    fn find_edge(&self, a : usize, b : usize) -> Option<(usize, usize)> {
+        let start = self.vtri[a];
+        let mut tri = start;
+
+        loop {
+            let i = (0..3).find(|index| self.tris[tri][*index] == a)?;
+
+            if let Some(k) = (0..3).find(|index| self.tris[tri][*index] == b) {
+                return Some((tri, 3 - i - k))
+            }
+
+            tri = self.nbrs[tri][(i + 1) % 3];
+
+            if tri == NONE || tri == start {
+                return None
+            }
+        }
+    }
+
+    /// inserts segment between vertices a and b as edge
+    fn constrain(&mut self, mut a : usize, b : usize) {
+        while a != b {
+            if self.find_edge(a, b).is_some() {
+                self.constrained.insert(key(a, b));
+                return
+            }
+
+            let Some((end, crossing)) = self.crossing(a, b) else {
+                return
+            };
+
+            if crossing.iter().any(|(u, w)| self.constrained.contains(&key(*u, *w))) {
+                return
+            }
+
+            self.flip_out(a, end, crossing);
+            self.constrained.insert(key(a, end));
+
+            a = end;
+        }
+    }
+
+    /// edges crossing the segment from a towards b up to b or the first vertex on the segment
+    fn crossing(&self, a : usize, b : usize) -> Option<(usize, Vec<(usize, usize)>)> {
+        let (pa, pb) = (self.pnt(a), self.pnt(b));
+
+        let on = |vertex : usize| {
+            let p = self.pnt(vertex);
+            orient(pa, pb, p) == 0f64 && p.sub(pa).dot(pb.sub(pa)) > 0f64
+        };
+
+        // triangle around a whose wedge contains the segment
+        let start = self.vtri[a];
+        let mut tri = start;
+
+        let (mut tri, mut left, mut right) = loop {
+            let i = (0..3).find(|index| self.tris[tri][*index] == a)?;
+            let (u, w) = (self.tris[tri][(i + 1) % 3], self.tris[tri][(i + 2) % 3]);
+
+            if on(u) {
+                return Some((u, Vec::new()))
+            }
+
+            if on(w) {
+                return Some((w, Vec::new()))
+            }
+
+            if orient(pa, self.pnt(u), pb) > 0f64 && orient(pa, self.pnt(w), pb) < 0f64 {
+                break (tri, w, u);
+            }
+
+            tri = self.nbrs[tri][(i + 1) % 3];
+
+            if tri == NONE || tri == start {
+                return None
+            }
+        };
+
+        let mut crossing = vec![(left, right)];
+
+        loop {
+            let i = (0..3).find(|index| self.tris[tri][*index] != left && self.tris[tri][*index] != right)?;
+            let n = self.nbrs[tri][i];
+
+            if n == NONE {
+                return None
+            }
+
+            let x = self.opposite(n, left, right);
+
+            if x == b || orient(pa, pb, self.pnt(x)) == 0f64 {
+                return Some((x, crossing))
+            }
+
+            match orient(pa, pb, self.pnt(x)) > 0f64 {
+                true  => left  = x,
+                false => right = x
+            }
+
+            crossing.push((left, right));
+            tri = n;
+        }
+    }
+
+    /// flips crossing edges out of the segment from a to b and restores delaunay of the new edges (sloan)
+    fn flip_out(&mut self, a : usize, b : usize, crossing : Vec<(usize, usize)>) {
+        let (pa, pb) = (self.pnt(a), self.pnt(b));
+
+        let mut queue = crossing.into_iter().collect::<VecDeque<_>>();
+        let mut new   = Vec::new();
+
+        let mut steps = 0;
+        let limit = 16 * (queue.len() + 1) * (queue.len() + 1);
+
+        while let Some((u, w)) = queue.pop_front() {
+            steps += 1;
+
+            if steps > limit {
+                break;
+            }
+
+            let Some((t, i)) = self.find_edge(u, w) else {
+                continue
+            };
+
+            let p = self.tris[t][i];
+            let d = self.opposite(self.nbrs[t][i], u, w);
+
+            // only edges of strictly convex quadrilaterals can be flipped
+            if orient(self.pnt(p), self.pnt(d), self.pnt(u)) * orient(self.pnt(p), self.pnt(d), self.pnt(w)) >= 0f64 {
+                queue.push_back((u, w));
+                continue;
+            }
+
+            self.flip(t, i);
+
+            let crosses = p != a && p != b && d != a && d != b && orient(pa, pb, self.pnt(p)) * orient(pa, pb, self.pnt(d)) < 0f64;
+
+            match crosses {
+                true  => queue.push_back((p, d)),
+                false => new.push((p, d))
+            }
+        }
+
+        // new edges other than the segment are flipped until they are locally delaunay
+        let mut changed = true;
+        let mut rounds  = 0;
+
+        while changed && rounds < new.len() + 1 {
+            changed = false;
+            rounds += 1;
+
+            for edge in new.iter_mut() {
+                let (u, w) = *edge;
+
+                if key(u, w) == key(a, b) || self.constrained.contains(&key(u, w)) {
+                    continue;
+                }
+
+                let Some((t, i)) = self.find_edge(u, w) else {
+                    continue
+                };
+
+                let n = self.nbrs[t][i];
+
+                if n == NONE {
+                    continue;
+                }
+
+                let p = self.tris[t][i];
+                let (b, c) = (self.tris[t][(i + 1) % 3], self.tris[t][(i + 2) % 3]);
+                let d = self.opposite(n, b, c);
+
+                if incircle(self.pnt(p), self.pnt(b), self.pnt(c), self.pnt(d)) > 0f64 {
+                    self.flip(t, i);
+
+                    *edge   = (p, d);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    /// triangles without super triangle vertices, only with odd number of enclosing segments if interior
+    fn triangles(&self, interior : bool) -> Vec<IndTri> {
+        let real = self.real();
+
+        let keep = match interior {
+            false => vec![true; self.tris.len()],
+            true  => {
+                // 0-1 breadth first search of segment crossings from the super triangle
+                let mut depth = vec![usize::MAX; self.tris.len()];
+                let mut queue = VecDeque::new();
+
+                for (tri, vertices) in self.tris.iter().enumerate() {
+                    if vertices.iter().any(|vertex| *vertex >= real) {
+                        depth[tri] = 0;
+                        queue.push_back(tri);
+                    }
+                }
+
+                while let Some(tri) = queue.pop_front() {
+                    for i in 0..3 {
+                        let n = self.nbrs[tri][i];
+
+                        if n == NONE {
+                            continue;
+                        }
+
+                        let (b, c) = (self.tris[tri][(i + 1) % 3], self.tris[tri][(i + 2) % 3]);
+                        let cost = usize::from(self.constrained.contains(&key(b, c)));
+
+                        if depth[tri] + cost < depth[n] {
+                            depth[n] = depth[tri] + cost;
+
+                            match cost {
+                                0 => queue.push_front(n),
+                                _ => queue.push_back(n)
+                            }
+                        }
+                    }
+                }
+
+                depth.into_iter().map(|depth| depth % 2 == 1).collect()
+            }
+        };
+
+        self.tris.iter().zip(keep).filter(|(vertices, keep)| *keep && vertices.iter().all(|vertex| *vertex < real)).map(|(vertices, _)| {
+            IndTri::new(vertices[0], vertices[1], vertices[2])
+        }).collect()
+    }
+}
+
+/// undirected edge key
+fn key(a : usize, b : usize) -> (usize, usize) {
+    (Ord::min(a, b), Ord::max(a, b))
+}
@@ -1,3 +1,12 @@
+/// module for axis analysis of 3D stem meshes
+pub mod analysis;
+
+/// module for convex hulls of 3D point sets
+pub mod hull;
+
+/// module for principal component analysis of 3D point sets
+pub mod pca;
+
 /// module for 3D primitives
 pub mod prim;
 
@@ -0,0 +1,396 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::mesh::ind::{
+    IndTriMesh
+};
+
+use crate::geom::d3::pca::{
+    Pca
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// number of line fits through cross-sections perpendicular to the previous fit
+const REFINE_ITERATIONS : usize = 3;
+
+/// 3D line of point and unit direction
+#[derive(Clone, Copy, Debug)]
+pub struct Axis {
+    pnt : Vect<f64>,
+    dir : Vect<f64>
+}
+
+/// orthonormal right-handed coordinate frame of origin and x, y and z axes in model coordinates
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    origin : Vect<f64>,
+    axes   : [Vect<f64>; 3]
+}
+
+/// estimation of the longitudinal axis and neck axis of stem meshes
+///
+/// the longitudinal axis is the first principal axis of the surface, optionally refined by a line fit
+/// through the centroids of cross-sections; it points towards the proximal end with the neck
+#[derive(Clone, Copy, Debug)]
+pub struct AxisAnalysis {
+    refine   : bool,
+    sections : usize,
+    trim     : f64,
+    neck     : f64
+}
+
+impl Axis {
+    /// create new Axis of point and unit direction without any checks
+    pub fn new_unchecked(pnt : Vect<f64>, dir : Vect<f64>) -> Axis {
+        Axis{pnt : pnt, dir : dir}
+    }
+
+    /// point on axis
+    pub fn pnt(&self) -> Vect<f64> {
+        self.pnt
+    }
+
+    /// unit direction of axis
+    pub fn dir(&self) -> Vect<f64> {
+        self.dir
+    }
+
+    /// signed position of projection of point onto axis
+    pub fn along<V : Vector>(&self, pnt : V) -> f64
+    where V::Val : Cast<f64>
+    {
+        to_f64(pnt).sub(self.pnt).dot(self.dir)
+    }
+
+    /// distance of point to axis
+    pub fn dist<V : Vector>(&self, pnt : V) -> f64
+    where V::Val : Cast<f64>
+    {
+        to_f64(pnt).sub(self.pnt).cross(self.dir).len()
+    }
+}
+
+impl Frame {
+    /// create new Frame of origin and orthonormal right-handed axes without any checks
+    pub fn new_unchecked(origin : Vect<f64>, axes : [Vect<f64>; 3]) -> Frame {
+        Frame{origin : origin, axes : axes}
+    }
+
+    /// origin of frame in model coordinates
+    pub fn origin(&self) -> Vect<f64> {
+        self.origin
+    }
+
+    /// x, y and z axes of frame in model coordinates
+    pub fn axes(&self) -> [Vect<f64>; 3] {
+        self.axes
+    }
+
+    /// frame coordinates of point in model coordinates
+    pub fn apply<V : Vector>(&self, pnt : V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        let diff = to_f64(pnt).sub(self.origin);
+
+        (diff.dot(self.axes[0]), diff.dot(self.axes[1]), diff.dot(self.axes[2]))
+    }
+
+    /// model coordinates of point in frame coordinates
+    pub fn revert<V : Vector>(&self, pnt : V) -> Vect<f64>
+    where V::Val : Cast<f64>
+    {
+        let pnt = to_f64(pnt);
+
+        self.origin.add(self.axes[0].vmul(pnt.x())).add(self.axes[1].vmul(pnt.y())).add(self.axes[2].vmul(pnt.z()))
+    }
+}
+
+impl Default for AxisAnalysis {
+    fn default() -> AxisAnalysis {
+        AxisAnalysis::new()
+    }
+}
+
+impl AxisAnalysis {
+    /// analysis with refinement through 20 cross-sections, 10% trimmed at both ends, and neck in the proximal 30%
+    pub fn new() -> AxisAnalysis {
+        AxisAnalysis{refine : true, sections : 20, trim : 0.1, neck : 0.3}
+    }
+
+    /// refine principal axis with line fit through cross-section centroids
+    pub fn refine(mut self, refine : bool) -> AxisAnalysis {
+        self.refine = refine;
+        self
+    }
+
+    /// number of cross-sections for refinement
+    pub fn sections(mut self, sections : usize) -> AxisAnalysis {
+        self.sections = Ord::max(sections, 2);
+        self
+    }
+
+    /// fraction of length at both ends without cross-sections
+    pub fn trim(mut self, trim : f64) -> AxisAnalysis {
+        self.trim = trim.clamp(0f64, 0.45);
+        self
+    }
+
+    /// fraction of length at the proximal end that contains the neck
+    pub fn neck(mut self, neck : f64) -> AxisAnalysis {
+        self.neck = neck.clamp(0.01, 1f64);
+        self
+    }
+
+    /// longitudinal axis of mesh pointing towards the proximal end (None for meshes without area)
+    ///
+    /// the proximal end is the end whose surface lies farther from the axis on average;
+    /// cross-sections for refinement lie between the trimmed distal end and the neck
+    pub fn long_axis<V : Vector>(&self, mesh : &IndTriMesh<V>) -> Option<Axis>
+    where V::Val : Cast<f64>
+    {
+        let tris = triangles(mesh);
+
+        let pca = surface_pca(&tris)?;
+        let axis = Axis::new_unchecked(pca.mean(), pca.axes()[0]);
+
+        let (min, max) = range(&tris, &axis);
+        let len = max - min;
+
+        let spread = |lo : f64, hi : f64| {
+            let (sum, area) = tris.iter().filter(|tri| (lo..=hi).contains(&axis.along(centroid(tri)))).fold((0f64, 0f64), |(sum, area), tri| {
+                (sum + axis.dist(centroid(tri)) * tri_area(tri), area + tri_area(tri))
+            });
+
+            match area > 0f64 {
+                true  => sum / area,
+                false => 0f64
+            }
+        };
+
+        let mut axis = match spread(min, min + self.neck * len) > spread(max - self.neck * len, max) {
+            true  => Axis::new_unchecked(axis.pnt, axis.dir.vmul(-1f64)),
+            false => axis
+        };
+
+        if !self.refine {
+            return Some(axis)
+        }
+
+        // sections of the previous fit are less oblique to the stem
+        for _ in 0..REFINE_ITERATIONS {
+            let centroids = self.centroids(&tris, &axis);
+
+            if centroids.len() < 2 {
+                break;
+            }
+
+            let fit = Pca::points(&centroids)?;
+
+            let dir = match fit.axes()[0].dot(axis.dir) < 0f64 {
+                true  => fit.axes()[0].vmul(-1f64),
+                false => fit.axes()[0]
+            };
+
+            axis = Axis::new_unchecked(fit.mean(), dir);
+        }
+
+        Some(axis)
+    }
+
+    /// neck axis as first principal axis of the surface at the proximal end of the longitudinal axis
+    /// outside of the radius of the stem below the neck (None if it has no area)
+    ///
+    /// the neck axis points away from the longitudinal axis towards the proximal end
+    pub fn neck_axis<V : Vector>(&self, mesh : &IndTriMesh<V>, long : &Axis) -> Option<Axis>
+    where V::Val : Cast<f64>
+    {
+        let tris = triangles(mesh);
+
+        let (min, max) = range(&tris, long);
+        let start = max - self.neck * (max - min);
+
+        // radius of stem in a band of the trim length below the neck
+        let radius = tris.iter().flatten().filter(|pnt| (start - self.trim * (max - min)..start).contains(&long.along(*pnt))).fold(0f64, |radius, pnt| {
+            f64::max(radius, long.dist(pnt))
+        });
+
+        let proximal = tris.into_iter().filter(|tri| long.along(centroid(tri)) >= start).collect::<Vec<_>>();
+        let outside  = proximal.iter().filter(|tri| long.dist(centroid(tri)) > radius).copied().collect::<Vec<_>>();
+
+        let pca = surface_pca(&outside).or_else(|| surface_pca(&proximal))?;
+        let dir = pca.axes()[0];
+
+        // neck points upwards and away from the longitudinal axis
+        let away = pca.mean().sub(long.pnt).sub(long.dir.vmul(long.along(pca.mean())));
+
+        let away = match away.len() > 0f64 {
+            true  => away.norm(),
+            false => away
+        };
+
+        match dir.dot(long.dir) + dir.dot(away) < 0f64 {
+            true  => Some(Axis::new_unchecked(pca.mean(), dir.vmul(-1f64))),
+            false => Some(Axis::new_unchecked(pca.mean(), dir))
+        }
+    }
+
+    /// canonical frame of mesh with z along the longitudinal axis towards the proximal end and the neck in the xz plane
+    /// pointing towards positive x, so that rotate_y and rotate_z of frame coordinates act on anatomical angles
+    ///
+    /// the origin is the point of the longitudinal axis; without distinct neck the second principal axis is x
+    pub fn frame<V : Vector>(&self, mesh : &IndTriMesh<V>) -> Option<Frame>
+    where V::Val : Cast<f64>
+    {
+        let long = self.long_axis(mesh)?;
+        let z = long.dir;
+
+        let neck = self.neck_axis(mesh, &long).map(|neck| neck.dir.sub(z.vmul(neck.dir.dot(z))));
+
+        let x = match neck {
+            Some(x) if x.len() > 1e-6 => x.norm(),
+            _ => {
+                let pca = surface_pca(&triangles(mesh))?;
+                let x = pca.axes()[1];
+
+                x.sub(z.vmul(x.dot(z))).norm()
+            }
+        };
+
+        Some(Frame::new_unchecked(long.pnt, [x, z.cross(x), z]))
+    }
+
+    /// area centroids of cross-sections perpendicular to axis between trimmed distal end and neck
+    fn centroids(&self, tris : &[[Vect<f64>; 3]], axis : &Axis) -> Vec<Vect<f64>> {
+        let (min, max) = range(tris, axis);
+
+        let lo = min + self.trim * (max - min);
+        let hi = max - f64::max(self.trim, self.neck) * (max - min);
+
+        // in-plane axes u and v with u, v and axis right-handed
+        let u = match axis.dir.x().abs() < 0.9 {
+            true  => (1f64, 0f64, 0f64).cross(axis.dir).norm(),
+            false => (0f64, 1f64, 0f64).cross(axis.dir).norm()
+        };
+
+        let v = axis.dir.cross(u);
+
+        (0..self.sections).filter_map(|index| {
+            let pos = lo + (hi - lo) * (index as f64 + 0.5) / self.sections as f64;
+
+            let (mut area, mut cu, mut cv) = (0f64, 0f64, 0f64);
+
+            for tri in tris {
+                let Some((a, b)) = section(tri, axis, pos) else {
+                    continue
+                };
+
+                // segments of the boundary are summed with green's theorem in plane coordinates
+                let (au, av) = (a.sub(axis.pnt).dot(u), a.sub(axis.pnt).dot(v));
+                let (bu, bv) = (b.sub(axis.pnt).dot(u), b.sub(axis.pnt).dot(v));
+
+                let cross = au * bv - bu * av;
+
+                area += cross / 2f64;
+                cu   += (au + bu) * cross / 6f64;
+                cv   += (av + bv) * cross / 6f64;
+            }
+
+            match area.abs() > f64::EPSILON {
+                true  => Some(axis.pnt.add(axis.dir.vmul(pos)).add(u.vmul(cu / area)).add(v.vmul(cv / area))),
+                false => None
+            }
+        }).collect()
+    }
+}
+
+/// area-weighted principal components of triangle surfaces (None without area)
+pub fn surface_pca(tris : &[[Vect<f64>; 3]]) -> Option<Pca> {
+    let total = tris.iter().map(tri_area).sum::<f64>();
+
+    if total <= 0f64 {
+        return None
+    }
+
+    let mean = tris.iter().fold((0f64, 0f64, 0f64), |sum, tri| sum.add(centroid(tri).vmul(tri_area(tri)))).vdiv(total);
+
+    // second moments of uniform density over each triangle relative to the mean
+    let mut covariance = [[0f64; 3]; 3];
+
+    for tri in tris {
+        let area = tri_area(tri);
+
+        let [a, b, c] = tri.map(|pnt| pnt.sub(mean)).map(|pnt| [pnt.x(), pnt.y(), pnt.z()]);
+        let s = [a[0] + b[0] + c[0], a[1] + b[1] + c[1], a[2] + b[2] + c[2]];
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let moment = a[row] * a[col] + b[row] * b[col] + c[row] * c[col] + s[row] * s[col];
+                covariance[row][col] += area * moment / 12f64 / total;
+            }
+        }
+    }
+
+    Some(Pca::new(mean, covariance))
+}
+
+/// triangles of mesh in f64 coordinates
+pub fn triangles<V : Vector>(mesh : &IndTriMesh<V>) -> Vec<[Vect<f64>; 3]>
+where V::Val : Cast<f64>
+{
+    mesh.triangles().iter().map(|tri| mesh.triangle(tri).map(to_f64)).collect()
+}
+
+fn to_f64<V : Vector>(pnt : V) -> Vect<f64>
+where V::Val : Cast<f64>
+{
+    (pnt.x().cast(), pnt.y().cast(), pnt.z().cast())
+}
+
+fn centroid(tri : &[Vect<f64>; 3]) -> Vect<f64> {
+    tri[0].add(tri[1]).add(tri[2]).vdiv(3f64)
+}
+
+fn tri_area(tri : &[Vect<f64>; 3]) -> f64 {
+    tri[1].sub(tri[0]).cross(tri[2].sub(tri[0])).len() / 2f64
+}
+
+/// smallest and largest position of triangle points along axis
+fn range(tris : &[[Vect<f64>; 3]], axis : &Axis) -> (f64, f64) {
+    tris.iter().flatten().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), pnt| {
+        let pos = axis.along(pnt);
+        (f64::min(min, pos), f64::max(max, pos))
+    })
+}
+
+/// intersection segment of triangle with plane perpendicular to axis at position,
+/// oriented counterclockwise around the axis for outward triangle normals
+fn section(tri : &[Vect<f64>; 3], axis : &Axis, pos : f64) -> Option<(Vect<f64>, Vect<f64>)> {
+    let dists = tri.map(|pnt| axis.along(pnt) - pos);
+
+    let mut pnts = Vec::with_capacity(2);
+
+    for index in 0..3 {
+        let next = (index + 1) % 3;
+
+        if (dists[index] < 0f64) != (dists[next] < 0f64) {
+            let t = dists[index] / (dists[index] - dists[next]);
+            pnts.push(tri[index].add(tri[next].sub(tri[index]).vmul(t)));
+        }
+    }
+
+    let [a, b] = pnts[..] else {
+        return None
+    };
+
+    let normal = tri[1].sub(tri[0]).cross(tri[2].sub(tri[0]));
+
+    match b.sub(a).dot(axis.dir.cross(normal)) < 0f64 {
+        true  => Some((b, a)),
+        false => Some((a, b))
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::{
+    HashMap
+};
+
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::mesh::ind::{
+    IndTriMesh
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::geom::mesh::ind::{
+    IndTri
+};
+
+/// distances to faces below this fraction of the bounding box diagonal count as inside
+const TOLERANCE : f64 = 1e-10;
+
+/// reason why points have no 3D convex hull
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HullError {
+    /// there are no points
+    Empty,
+    /// all points are equal
+    Coincident,
+    /// all points lie on a line
+    Collinear,
+    /// all points lie in a plane
+    Coplanar
+}
+
+/// face of hull under construction with outward unit normal and points above it
+struct Face {
+    indices : [usize; 3],
+    normal  : Vect<f64>,
+    offset  : f64,
+    outside : Vec<usize>,
+    alive   : bool
+}
+
+impl Face {
+    fn new(pnts : &[Vect<f64>], indices : [usize; 3]) -> Face {
+        let [a, b, c] = indices.map(|index| pnts[index]);
+
+        let normal = b.sub(a).cross(c.sub(a));
+        let len = normal.len();
+
+        let normal = match len > 0f64 {
+            true  => normal.vdiv(len),
+            false => normal
+        };
+
+        Face{indices : indices, normal : normal, offset : normal.dot(a), outside : Vec::new(), alive : true}
+    }
+
+    /// signed distance of point above face
+    fn dist(&self, pnt : Vect<f64>) -> f64 {
+        self.normal.dot(pnt) - self.offset
+    }
+
+    /// directed edges ab, bc and ca
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.indices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+/// convex hull of points with quickhull algorithm as triangle mesh with outward normals (counterclockwise seen from outside)
+///
+/// the mesh only has the hull vertices; points closer than TOLERANCE times the bounding box diagonal to faces are treated as inside,
+/// so that coplanar points on faces are dropped and degenerate point sets give an explicit error
+pub fn quickhull<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Result<IndTriMesh<V::Own>, HullError>
+where V::Val : Cast<f64>
+{
+    let input = pnts.into_iter().map(|pnt| V::of(&pnt)).collect::<Vec<_>>();
+    let pnts  = input.iter().map(|pnt| (pnt.x().cast(), pnt.y().cast(), pnt.z().cast())).collect::<Vec<Vect<f64>>>();
+
+    if pnts.is_empty() {
+        return Err(HullError::Empty)
+    }
+
+    // tolerance of distances relative to the bounding box diagonal
+    let min = pnts.iter().fold(pnts[0], |min, pnt| (min.0.min(pnt.x()), min.1.min(pnt.y()), min.2.min(pnt.z())));
+    let max = pnts.iter().fold(pnts[0], |max, pnt| (max.0.max(pnt.x()), max.1.max(pnt.y()), max.2.max(pnt.z())));
+    let eps = TOLERANCE * max.sub(min).len();
+
+    let faces = hull(&pnts, eps)?;
+
+    // points inside the final hull may be chosen as eye before the farther points and remain as vertices on hull faces or edges,
+    // so that the hull is built again from the extreme vertices only
+    let faces = match extreme(&pnts, &faces, eps) {
+        Some(extreme) => hull(&extreme.iter().map(|index| pnts[*index]).collect::<Vec<_>>(), eps)?
+            .into_iter()
+            .map(|face| face.map(|index| extreme[index]))
+            .collect::<Vec<_>>(),
+        None => faces
+    };
+
+    // only hull vertices are kept
+    let mut indices = vec![None; pnts.len()];
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+
+    for face in faces {
+        let [a, b, c] = face.map(|index| {
+            *indices[index].get_or_insert_with(|| {
+                vertices.push(V::of(&input[index]));
+                vertices.len() - 1
+            })
+        });
+
+        triangles.push(IndTri::new(a, b, c));
+    }
+
+    Ok(IndTriMesh::new_unchecked(vertices, triangles))
+}
+
+/// vertex indices of outward faces of hull of points with quickhull algorithm
+fn hull(pnts : &[Vect<f64>], eps : f64) -> Result<Vec<[usize; 3]>, HullError> {
+    let [a, b, c, d] = simplex(pnts, eps)?;
+
+    let mut faces = Vec::new();
+
+    let centroid = pnts[a].add(pnts[b]).add(pnts[c]).add(pnts[d]).vdiv(4f64);
+
+    for indices in [[a, b, c], [a, d, b], [a, c, d], [b, d, c]] {
+        let face = Face::new(pnts, indices);
+
+        // orient faces away from the simplex centroid
+        match face.dist(centroid) > 0f64 {
+            true  => faces.push(Face::new(pnts, [indices[0], indices[2], indices[1]])),
+            false => faces.push(face)
+        }
+    }
+
+    let mut edges = HashMap::new();
+
+    for (index, face) in faces.iter().enumerate() {
+        for edge in face.edges() {
+            edges.insert(edge, index);
+        }
+    }
+
+    for (index, pnt) in pnts.iter().enumerate() {
+        if [a, b, c, d].contains(&index) {
+            continue;
+        }
+
+        if let Some(face) = faces.iter_mut().find(|face| face.dist(*pnt) > eps) {
+            face.outside.push(index);
+        }
+    }
+
+    let mut pending = (0..faces.len()).collect::<Vec<_>>();
+
+    while let Some(current) = pending.pop() {
+        if !faces[current].alive || faces[current].outside.is_empty() {
+            continue;
+        }
+
+        let eye = faces[current].outside.iter().copied().fold(None, |far : Option<usize>, index| {
+            match far {
+                Some(far) if faces[current].dist(pnts[far]) >= faces[current].dist(pnts[index]) => Some(far),
+                _ => Some(index)
+            }
+        }).unwrap();
+
+        // faces visible from the eye point and edges between visible and hidden faces
+        let mut visible = vec![current];
+        let mut horizon = Vec::new();
+
+        faces[current].alive = false;
+
+        let mut stack = vec![current];
+
+        while let Some(face) = stack.pop() {
+            for (from, to) in faces[face].edges() {
+                let neighbour = edges[&(to, from)];
+
+                if !faces[neighbour].alive {
+                    continue;
+                }
+
+                match faces[neighbour].dist(pnts[eye]) > eps {
+                    true  => {
+                        faces[neighbour].alive = false;
+                        visible.push(neighbour);
+                        stack.push(neighbour);
+                    },
+                    false => horizon.push((from, to))
+                }
+            }
+        }
+
+        let orphans = visible.iter().flat_map(|face| std::mem::take(&mut faces[*face].outside)).filter(|index| *index != eye).collect::<Vec<_>>();
+
+        for face in &visible {
+            for edge in faces[*face].edges() {
+                if edges.get(&edge) == Some(face) {
+                    edges.remove(&edge);
+                }
+            }
+        }
+
+        let first = faces.len();
+
+        for (from, to) in horizon {
+            let face = Face::new(pnts, [from, to, eye]);
+
+            for edge in face.edges() {
+                edges.insert(edge, faces.len());
+            }
+
+            faces.push(face);
+        }
+
+        for index in orphans {
+            if let Some(face) = faces[first..].iter_mut().find(|face| face.dist(pnts[index]) > eps) {
+                face.outside.push(index);
+            }
+        }
+
+        pending.extend(first..faces.len());
+    }
+
+    Ok(faces.iter().filter(|face| face.alive).map(|face| face.indices).collect())
+}
+
+/// sorted hull vertices whose incident faces do not all lie in one or two planes (on a face or an edge of the hull)
+///
+/// none if all hull vertices are extreme
+fn extreme(pnts : &[Vect<f64>], faces : &[[usize; 3]], eps : f64) -> Option<Vec<usize>> {
+    let mut incident : HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (index, face) in faces.iter().enumerate() {
+        for vertex in face {
+            incident.entry(*vertex).or_default().push(index);
+        }
+    }
+
+    let area = |face : &usize| {
+        let [a, b, c] = faces[*face].map(|index| pnts[index]);
+        b.sub(a).cross(c.sub(a)).len()
+    };
+
+    let on = |plane : &Face, face : &usize| faces[*face].iter().all(|index| plane.dist(pnts[*index]).abs() <= eps);
+
+    // planes of the largest faces have the most accurate normals
+    let largest = |incident : &[&usize]| incident.iter().max_by(|p, q| area(p).total_cmp(&area(q))).map(|face| Face::new(pnts, faces[**face]));
+
+    let count = incident.len();
+
+    let mut extreme = incident.into_iter().filter(|(_, incident)| {
+        let incident = incident.iter().collect::<Vec<_>>();
+
+        let first = match largest(&incident) {
+            Some(first) => first,
+            None        => return false
+        };
+
+        let rest = incident.into_iter().filter(|face| !on(&first, face)).collect::<Vec<_>>();
+
+        let second = match largest(&rest) {
+            Some(second) => second,
+            None         => return false
+        };
+
+        rest.iter().any(|face| !on(&second, face))
+    }).map(|(vertex, _)| vertex).collect::<Vec<_>>();
+
+    extreme.sort();
+
+    match extreme.len() < count {
+        true  => Some(extreme),
+        false => None
+    }
+}
+
+/// indices of non-degenerate tetrahedron of points with largest extents
+fn simplex(pnts : &[Vect<f64>], eps : f64) -> Result<[usize; 4], HullError> {
+    let farthest = |dist : &dyn Fn(Vect<f64>) -> f64| {
+        (0..pnts.len()).fold((0, f64::NEG_INFINITY), |(far, max), index| {
+            let dist = dist(pnts[index]);
+
+            match dist > max {
+                true  => (index, dist),
+                false => (far, max)
+            }
+        })
+    };
+
+    // pair of extreme points along an axis with largest distance
+    let extremes : [fn(Vect<f64>) -> f64; 3] = [
+        |pnt : Vect<f64>| pnt.x(),
+        |pnt : Vect<f64>| pnt.y(),
+        |pnt : Vect<f64>| pnt.z()
+    ];
+
+    let (a, b) = extremes.iter().map(|coord| {
+        let (min, _) = farthest(&|pnt| -coord(pnt));
+        let (max, _) = farthest(&|pnt| coord(pnt));
+
+        (min, max)
+    }).fold((0, 0), |(a, b), (min, max)| {
+        match pnts[max].sub(pnts[min]).len() > pnts[b].sub(pnts[a]).len() {
+            true  => (min, max),
+            false => (a, b)
+        }
+    });
+
+    if pnts[b].sub(pnts[a]).len() <= eps {
+        return Err(HullError::Coincident)
+    }
+
+    let dir = pnts[b].sub(pnts[a]).norm();
+
+    let (c, dist) = farthest(&|pnt| pnt.sub(pnts[a]).cross(dir).len());
+
+    if dist <= eps {
+        return Err(HullError::Collinear)
+    }
+
+    let normal = dir.cross(pnts[c].sub(pnts[a])).norm();
+
+    let (d, dist) = farthest(&|pnt| pnt.sub(pnts[a]).dot(normal).abs());
+
+    if dist <= eps {
+        return Err(HullError::Coplanar)
+    }
+
+    Ok([a, b, c, d])
+}
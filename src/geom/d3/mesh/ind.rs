@@ -1,7 +1,16 @@
+use std::collections::{
+    HashMap,
+    HashSet
+};
+
 use std::vec::{
     Vec 
 };
 
+use crate::conv::{
+    Cast
+};
+
 use crate::file::stl::{
     Stl 
 };
@@ -10,17 +19,43 @@ use crate::geom::{
     d2
 };
 
+use crate::geom::d3::analysis::{
+    Frame
+};
+
+use crate::geom::d3::hull::{
+    self,
+    HullError
+};
+
+use crate::geom::d3::prim::obox::{
+    OBox
+};
+
 use crate::geom::d3::prim::vect::{
     Vect,
     Vector 
 };
 
 use crate::geom::mesh::ind::{
-    IndSeg 
+    IndSeg,
+    IndTri
 };
 
 use crate::num::{
-    Float
+    Float,
+    Zero,
+    One,
+    Two
+};
+
+use crate::ops::{
+    HSub,
+    HMul
+};
+
+use crate::rel::{
+    HPOrd
 };
 
 pub struct IndSegMesh<Vect : Vector> {
@@ -28,6 +63,11 @@ pub struct IndSegMesh<Vect : Vector> {
     segments : Vec<IndSeg>
 }
 
+pub struct IndTriMesh<Vect : Vector> {
+    vertices  : Vec<Vect>,
+    triangles : Vec<IndTri>
+}
+
 impl<Vect : Vector> IndSegMesh<Vect> {
     pub fn new_unchecked(vertices : Vec<Vect>, segments : Vec<IndSeg>) -> IndSegMesh<Vect> {
         IndSegMesh{vertices : vertices, segments : segments}
@@ -103,11 +143,183 @@ impl<Vect : Vector> IndSegMesh<Vect> {
         IndSegMesh::new_unchecked(vertices, segments)
     }
 
+    /// mesh in coordinates of frame (see Frame::apply)
+    pub fn transform(&self, frame : &Frame) -> IndSegMesh<(f64, f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        let vertices = self.vertices().iter().map(|vertex| frame.apply(vertex)).collect();
+        let segments = self.segments().clone();
+
+        IndSegMesh::new_unchecked(vertices, segments)
+    }
+
     pub fn proj_2d<V : d2::prim::Vector, Func : Fn(&Vect) -> V>(&self, proj : Func) -> d2::mesh::ind::IndSegMesh<V> {
         let vertices = self.vertices.iter().map(|vertex| proj(vertex)).collect();
         let segments = self.segments.clone();
 
         d2::mesh::ind::IndSegMesh::new_unchecked(vertices, segments)
     }
+
+    /// contour of mesh rotated by inclination around y axis and anteversion around z axis and projected onto xz plane
+    pub fn template_contour(&self, inclination : Vect::Val, anteversion : Vect::Val, max : usize) -> Option<Vec<d2::prim::Vect<Vect::Val>>>
+    where Vect::Val : Float + HPOrd + std::fmt::Debug
+    {
+        let mesh_3d = self.rotate_y(inclination).rotate_z(anteversion);
+        let mesh_2d = mesh_3d.proj_2d(|vertex| (vertex.x(), vertex.z()));
+
+        mesh_2d.rrcontour(max)
+    }
+}
+
+impl<Vect : Vector> IndTriMesh<Vect> {
+    pub fn new_unchecked(vertices : Vec<Vect>, triangles : Vec<IndTri>) -> IndTriMesh<Vect> {
+        IndTriMesh{vertices : vertices, triangles : triangles}
+    }
+
+    pub fn vertices(&self) -> &Vec<Vect> {
+        &self.vertices 
+    }
+
+    pub fn triangles(&self) -> &Vec<IndTri> {
+        &self.triangles
+    }
+
+    /// vertices of triangle
+    pub fn triangle(&self, tri : &IndTri) -> [Vect::Own; 3] {
+        tri.indices().map(|index| Vect::of(&self.vertices[index]))
+    }
+
+    /// normal of triangle with length of twice its area pointing to the side from which its vertices are counterclockwise
+    pub fn area_normal(&self, tri : &IndTri) -> Vect::Own 
+    where Vect::Val : HSub + HMul
+    {
+        let [a, b, c] = self.triangle(tri);
+        b.sub(&a).cross(c.sub(&a))
+    }
+
+    /// area weighted unit normals of vertices
+    pub fn vertex_normals(&self) -> Vec<Vect::Own> 
+    where Vect::Val : Float + HPOrd
+    {
+        let zero = Vect::Val::zero();
+        let mut normals = (0..self.vertices.len()).map(|_| Vect::of((zero, zero, zero))).collect::<Vec<_>>();
+
+        for tri in &self.triangles {
+            let normal = self.area_normal(tri);
+
+            for index in tri.indices() {
+                normals[index] = normals[index].add(&normal);
+            }
+        }
+
+        normals.into_iter()
+            .map(|normal| match normal.len() > zero {
+                false => normal,
+                true  => normal.norm()
+            })
+            .collect()
+    }
+
+    /// surface area as sum of triangle areas
+    pub fn area(&self) -> Vect::Val
+    where Vect::Val : Float
+    {
+        let sum = self.triangles.iter().fold(Vect::Val::zero(), |sum, tri| sum + self.area_normal(tri).len());
+
+        sum / Vect::Val::two()
+    }
+
+    /// signed enclosed volume of closed mesh with divergence theorem (positive if normals point outwards)
+    pub fn volume(&self) -> Vect::Val
+    where Vect::Val : Float
+    {
+        let sum = self.triangles.iter().fold(Vect::Val::zero(), |sum, tri| {
+            let [a, b, c] = self.triangle(tri);
+            sum + a.dot(b.cross(c))
+        });
+
+        let three = Vect::Val::two() + Vect::Val::one();
+
+        sum / (three * Vect::Val::two())
+    }
+
+    /// convex hull of vertices with outward normals (see hull::quickhull)
+    pub fn convex_hull(&self) -> Result<IndTriMesh<Vect::Own>, HullError>
+    where Vect::Val : Cast<f64>
+    {
+        hull::quickhull(&self.vertices)
+    }
+
+    /// oriented box of vertices with axes along the principal components (see OBox::pca)
+    pub fn oriented_box(&self) -> Option<OBox<(f64, f64, f64)>>
+    where Vect::Val : Cast<f64>
+    {
+        OBox::pca(&self.vertices)
+    }
+
+    /// segment mesh of the unique triangle edges
+    pub fn ind_seg_mesh(&self) -> IndSegMesh<Vect::Own> {
+        let mut unique   = HashSet::new();
+        let mut segments = Vec::new();
+
+        for tri in &self.triangles {
+            for mut segment in tri.segments() {
+                segment.sort();
+
+                if unique.insert(segment.clone()) {
+                    segments.push(segment);
+                }
+            }
+        }
+
+        let vertices = self.vertices.iter().map(Vect::of).collect();
+
+        IndSegMesh::new_unchecked(vertices, segments)
+    }
+
+    pub fn rotate_z(&self, angle : Vect::Val) -> IndTriMesh<Vect::Own> 
+    where Vect::Val : Float
+    {   
+        let vertices = self.vertices().iter().map(|vertex| vertex.rotate_z(angle)).collect();
+        IndTriMesh::new_unchecked(vertices, self.triangles.clone())
+    }
+
+    pub fn rotate_y(&self, angle : Vect::Val) -> IndTriMesh<Vect::Own> 
+    where Vect::Val : Float 
+    {
+        let vertices = self.vertices().iter().map(|vertex| vertex.rotate_y(angle)).collect();
+        IndTriMesh::new_unchecked(vertices, self.triangles.clone())
+    }
+
+    /// mesh in coordinates of frame (see Frame::apply)
+    pub fn transform(&self, frame : &Frame) -> IndTriMesh<(f64, f64, f64)>
+    where Vect::Val : Cast<f64>
+    {
+        let vertices = self.vertices().iter().map(|vertex| frame.apply(vertex)).collect();
+        IndTriMesh::new_unchecked(vertices, self.triangles.clone())
+    }
 }
 
+impl IndTriMesh<Vect<f32>> {
+    /// triangle mesh of stl with merged identical vertices
+    pub fn from_stl(stl : &Stl) -> IndTriMesh<Vect<f32>> {
+        let mut vertices  = Vec::new();
+        let mut indices   = HashMap::new();
+        let mut triangles = Vec::new();
+
+        for triangle in stl.triangles() {
+            let [a, b, c] = triangle.vertices().map(|vertex| {
+                let key = (vertex.x().to_bits(), vertex.y().to_bits(), vertex.z().to_bits());
+
+                *indices.entry(key).or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() - 1
+                })
+            });
+
+            triangles.push(IndTri::new(a, b, c));
+        }
+
+        IndTriMesh::new_unchecked(vertices, triangles)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+/// maximal number of jacobi sweeps of eigen decomposition
+const MAX_SWEEPS : usize = 50;
+
+/// principal components of 3D data as mean and eigen decomposition of covariance matrix
+pub struct Pca {
+    mean   : Vect<f64>,
+    values : [f64; 3],
+    axes   : [Vect<f64>; 3]
+}
+
+impl Pca {
+    // General methods
+
+    /// create new Pca of mean and symmetric covariance matrix
+    pub fn new(mean : Vect<f64>, covariance : [[f64; 3]; 3]) -> Pca {
+        let (values, axes) = eigen(covariance);
+
+        Pca{mean : mean, values : values, axes : axes}
+    }
+
+    /// principal components of points (None if there are no points)
+    pub fn points<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Option<Pca>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| (pnt.x().cast(), pnt.y().cast(), pnt.z().cast())).collect::<Vec<Vect<f64>>>();
+
+        if pnts.is_empty() {
+            return None
+        }
+
+        let mean = pnts.iter().fold((0f64, 0f64, 0f64), |sum, pnt| sum.add(pnt)).vdiv(pnts.len() as f64);
+
+        let mut covariance = [[0f64; 3]; 3];
+
+        for pnt in &pnts {
+            let diff = pnt.sub(mean);
+            let diff = [diff.x(), diff.y(), diff.z()];
+
+            for row in 0..3 {
+                for col in 0..3 {
+                    covariance[row][col] += diff[row] * diff[col] / pnts.len() as f64;
+                }
+            }
+        }
+
+        Some(Pca::new(mean, covariance))
+    }
+
+    /// mean of data
+    pub fn mean(&self) -> Vect<f64> {
+        self.mean
+    }
+
+    /// variances along axes in descending order
+    pub fn values(&self) -> [f64; 3] {
+        self.values
+    }
+
+    /// orthonormal right-handed principal axes in order of descending variance
+    pub fn axes(&self) -> [Vect<f64>; 3] {
+        self.axes
+    }
+}
+
+/// eigen values in descending order and orthonormal right-handed eigen vectors of symmetric matrix with jacobi algorithm
+pub fn eigen(matrix : [[f64; 3]; 3]) -> ([f64; 3], [Vect<f64>; 3]) {
+    let mut a = matrix;
+    let mut v = [[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]];
+
+    let norm = a.iter().flatten().map(|val| val * val).sum::<f64>().sqrt();
+
+    for _ in 0..MAX_SWEEPS {
+        let off = (a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2]).sqrt();
+
+        if off <= f64::EPSILON * norm {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0f64 {
+                continue;
+            }
+
+            // rotation angle that zeroes a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2f64 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+            let c = 1f64 / (t * t + 1f64).sqrt();
+            let s = t * c;
+
+            for row in a.iter_mut() {
+                let (akp, akq) = (row[p], row[q]);
+                row[p] = c * akp - s * akq;
+                row[q] = s * akp + c * akq;
+            }
+
+            let (row_p, row_q) = (a[p], a[q]);
+
+            a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+            a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+
+            for row in v.iter_mut() {
+                let (vkp, vkq) = (row[p], row[q]);
+                row[p] = c * vkp - s * vkq;
+                row[q] = s * vkp + c * vkq;
+            }
+        }
+    }
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|i, j| a[*j][*j].total_cmp(&a[*i][*i]));
+
+    let values = order.map(|i| a[i][i]);
+    let axes   = order.map(|i| (v[0][i], v[1][i], v[2][i]));
+
+    // third axis is flipped to make axes right-handed
+    let third = axes[0].cross(axes[1]);
+
+    (values, [axes[0], axes[1], third])
+}
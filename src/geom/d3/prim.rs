@@ -1,4 +1,12 @@
+/// module for 3D oriented box primitive
+pub mod obox;
+
+/// module for 3D sphere primitive
+pub mod sphere;
+
 /// module for 3D vector primitive
 pub mod vect;
 
-pub use vect::*;
\ No newline at end of file
+pub use obox::*;
+pub use sphere::*;
+pub use vect::*;
@@ -0,0 +1,133 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::d3::pca::{
+    Pca
+};
+
+use crate::geom::d3::prim::vect::{
+    Vect,
+    Vector
+};
+
+use crate::num::{
+    Zero,
+    Two
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HNeg
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// 3D oriented box defined by "center", 3 orthonormal "axes" and 3 "half" extents along the axes
+pub struct OBox<V : Vector> {
+    center : V,
+    axes   : [V; 3],
+    half   : [V::Val; 3]
+}
+
+impl<Vect : Vector> OBox<Vect> {
+    // General methods
+
+    /// create new OBox of center, orthonormal axes and half extents without any checks
+    pub fn new_unchecked(center : Vect, axes : [Vect; 3], half : [Vect::Val; 3]) -> OBox<Vect> {
+        OBox{center : center, axes : axes, half : half}
+    }
+
+    // Box methods
+
+    /// center of box
+    pub fn center(&self) -> Vect::Own {
+        Vect::of(&self.center)
+    }
+
+    /// orthonormal axes of box
+    pub fn axes(&self) -> [Vect::Own; 3] {
+        [Vect::of(&self.axes[0]), Vect::of(&self.axes[1]), Vect::of(&self.axes[2])]
+    }
+
+    /// half extents of box along axes
+    pub fn half(&self) -> [Vect::Val; 3] {
+        self.half
+    }
+
+    /// extents of box along axes
+    pub fn size(&self) -> [Vect::Val; 3]
+    where Vect::Val : Two + HMul
+    {
+        self.half.map(|half| half * Vect::Val::two())
+    }
+
+    /// volume of box
+    pub fn volume(&self) -> Vect::Val
+    where Vect::Val : Two + HMul
+    {
+        let [x, y, z] = self.size();
+        x * y * z
+    }
+
+    /// corners of box with bit i of corner index set for positive side of axis i
+    pub fn corners(&self) -> [Vect::Own; 8]
+    where Vect::Val : HAdd + HMul + HNeg
+    {
+        std::array::from_fn(|index| {
+            (0..3).fold(self.center(), |corner, axis| {
+                let half = match index & (1 << axis) != 0 {
+                    true  =>  self.half[axis],
+                    false => -self.half[axis]
+                };
+
+                corner.add(self.axes[axis].vmul(half))
+            })
+        })
+    }
+
+    /// check if box contains point
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool
+    where Vect::Val : HAdd + HSub + HMul + HNeg + HPOrd
+    {
+        let diff = pnt.sub(&self.center);
+
+        (0..3).all(|axis| {
+            let dist = diff.dot(&self.axes[axis]);
+            -self.half[axis] <= dist && dist <= self.half[axis]
+        })
+    }
+}
+
+impl OBox<Vect<f64>> {
+    /// oriented box of points with axes along the principal components in order of descending variance (None if there are no points)
+    ///
+    /// the box is centered on the extents of the points along the axes, so that the first axis is usually the long axis
+    pub fn pca<V : Vector, I : IntoIterator<Item = V>>(pnts : I) -> Option<OBox<Vect<f64>>>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| (pnt.x().cast(), pnt.y().cast(), pnt.z().cast())).collect::<Vec<Vect<f64>>>();
+
+        let pca  = Pca::points(&pnts)?;
+        let axes = pca.axes();
+
+        let mut center = pca.mean();
+        let mut half   = [f64::zero(); 3];
+
+        for axis in 0..3 {
+            let (min, max) = pnts.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), pnt| {
+                let dist = pnt.sub(pca.mean()).dot(axes[axis]);
+                (f64::min(min, dist), f64::max(max, dist))
+            });
+
+            center = center.add(axes[axis].vmul((min + max) / 2f64));
+            half[axis] = (max - min) / 2f64;
+        }
+
+        Some(OBox::new_unchecked(center, axes, half))
+    }
+}
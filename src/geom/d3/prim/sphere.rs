@@ -0,0 +1,123 @@
+use crate::conv::{
+    Cast
+};
+
+use crate::geom::fit::{
+    self,
+    Fit,
+    FitType,
+    Ransac
+};
+
+use crate::geom::d3::prim::vect::{
+    Vector
+};
+
+use crate::num::{
+    One,
+    Two,
+    Float
+};
+
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// 3D sphere defined by "center" and "radius"
+pub struct Sphere<V : Vector> {
+    center : V,
+    radius : V::Val
+}
+
+impl<Vect : Vector> Sphere<Vect> {
+    // General methods
+
+    /// create new Sphere of "center" and "radius"
+    pub fn new(center : Vect, radius : Vect::Val) -> Sphere<Vect> {
+        Sphere{center : center, radius : radius}
+    }
+
+    pub fn center(&self) -> Vect::Own {
+        Vect::of(&self.center)
+    }
+
+    pub fn radius(&self) -> Vect::Val {
+        self.radius
+    }
+
+    pub fn area(&self) -> Vect::Val
+    where Vect::Val : Float
+    {
+        let two = Vect::Val::two();
+
+        two * two * Vect::Val::pi() * self.radius * self.radius
+    }
+
+    pub fn volume(&self) -> Vect::Val
+    where Vect::Val : Float
+    {
+        self.area() * self.radius / (Vect::Val::two() + Vect::Val::one())
+    }
+
+    // Shape methods
+
+    /// add vector to center (translation)
+    pub fn add<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Sphere<Vect::Own>
+    where Vect::Val : HAdd
+    {
+        Sphere::new(self.center.add(&vect), self.radius)
+    }
+
+    /// sub vector from center (translation)
+    pub fn sub<V : Vector<Val = Vect::Val>>(&self, vect : V) -> Sphere<Vect::Own>
+    where Vect::Val : HSub
+    {
+        Sphere::new(self.center.sub(&vect), self.radius)
+    }
+
+    /// check if sphere contains point (boundary included)
+    pub fn contains<V : Vector<Val = Vect::Val>>(&self, pnt : V) -> bool
+    where Vect::Val : HAdd + HSub + HMul + HPOrd
+    {
+        let diff = pnt.sub(&self.center);
+
+        diff.dot(&diff) <= self.radius * self.radius
+    }
+}
+
+impl Sphere<(f64, f64, f64)> {
+    // Fit methods
+
+    /// sphere fitted to points with residual statistics (None for less than 4 points or coplanar points)
+    pub fn fit<V : Vector, I : IntoIterator<Item = V>>(pnts : I, fit : FitType) -> Option<Fit<Sphere<(f64, f64, f64)>>>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| [pnt.x().cast(), pnt.y().cast(), pnt.z().cast()]).collect::<Vec<[f64; 3]>>();
+
+        let (center, radius) = fit::sphere(&pnts, fit)?;
+
+        let inliers = vec![true; pnts.len()];
+        let stats   = fit::stats(&pnts, &center, radius, &inliers);
+
+        Some(Fit::new_unchecked(Sphere::new((center[0], center[1], center[2]), radius), stats, inliers))
+    }
+
+    /// sphere fitted to the consensus set of noisy points with residual statistics of inliers (see Ransac)
+    pub fn ransac<V : Vector, I : IntoIterator<Item = V>>(pnts : I, ransac : &Ransac) -> Option<Fit<Sphere<(f64, f64, f64)>>>
+    where V::Val : Cast<f64>
+    {
+        let pnts = pnts.into_iter().map(|pnt| [pnt.x().cast(), pnt.y().cast(), pnt.z().cast()]).collect::<Vec<[f64; 3]>>();
+
+        let (center, radius, inliers) = fit::ransac(&pnts, ransac)?;
+
+        let stats = fit::stats(&pnts, &center, radius, &inliers);
+
+        Some(Fit::new_unchecked(Sphere::new((center[0], center[1], center[2]), radius), stats, inliers))
+    }
+}
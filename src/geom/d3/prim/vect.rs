@@ -6,12 +6,21 @@ use crate::num::{
     Float 
 };
 
+use crate::ops::{
+    HAdd,
+    HSub,
+    HMul,
+    HDiv
+};
+
 use crate::rel::{
     HPEq 
 };
 
 pub type Vect<Val> = (Val, Val, Val);
 
+// len is the euclidean norm, not a container size
+#[allow(clippy::len_without_is_empty)]
 pub trait Vector {
     /// value type of vector
     type Val : Copy;
@@ -37,6 +46,66 @@ pub trait Vector {
         self.x() == other.x() && self.y() == other.y() && self.z() == other.z()
     }
 
+    /// length of float vector
+    fn len(&self) -> Self::Val 
+    where Self::Val : Float
+    {
+        (self.x() * self.x() + self.y() * self.y() + self.z() * self.z()).sqrt()
+    }
+
+    /// add two vectors componentwise
+    fn add<V : Vector<Val = Self::Val>>(&self, other : V) -> Self::Own
+    where Self::Val : HAdd 
+    {
+        Self::of((self.x() + other.x(), self.y() + other.y(), self.z() + other.z()))
+    }
+
+    /// sub two vectors componentwise
+    fn sub<V : Vector<Val = Self::Val>>(&self, other : V) -> Self::Own
+    where Self::Val : HSub 
+    {
+        Self::of((self.x() - other.x(), self.y() - other.y(), self.z() - other.z()))
+    }
+
+    /// mul value to vector componentwise
+    fn vmul(&self, val : Self::Val) -> Self::Own 
+    where Self::Val : HMul 
+    {
+        Self::of((self.x() * val, self.y() * val, self.z() * val))
+    }
+
+    /// div vector by value componentwise
+    fn vdiv(&self, val : Self::Val) -> Self::Own 
+    where Self::Val : HDiv 
+    {
+        Self::of((self.x() / val, self.y() / val, self.z() / val))
+    }
+
+    /// dot product of two vectors
+    fn dot<V : Vector<Val = Self::Val>>(&self, other : V) -> Self::Val
+    where Self::Val : HAdd + HMul 
+    {
+        self.x() * other.x() + self.y() * other.y() + self.z() * other.z()
+    }
+
+    /// cross product of two vectors
+    fn cross<V : Vector<Val = Self::Val>>(&self, other : V) -> Self::Own
+    where Self::Val : HSub + HMul 
+    {
+        let x = self.y() * other.z() - self.z() * other.y();
+        let y = self.z() * other.x() - self.x() * other.z();
+        let z = self.x() * other.y() - self.y() * other.x();
+
+        Self::of((x, y, z))
+    }
+
+    /// vector with same direction and length 1
+    fn norm(&self) -> Self::Own 
+    where Self::Val : Float
+    {
+        self.vdiv(self.len())
+    }
+
     fn rotate_y(&self, angle : Self::Val) -> Self::Own 
     where Self::Val : Float
     {
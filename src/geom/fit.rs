@@ -0,0 +1,424 @@
+use rand::{
+    RngExt,
+    SeedableRng
+};
+
+use rand::rngs::{
+    StdRng
+};
+
+use crate::num::{
+    Float
+};
+
+use crate::rel::{
+    HPOrd
+};
+
+/// maximal number of levenberg-marquardt iterations of geometric fits
+const MAX_ITERATIONS : usize = 100;
+
+/// maximal number of refits to the inliers of ransac
+const REFIT_ITERATIONS : usize = 5;
+
+/// pivots of linear systems up to this fraction of the largest matrix value are treated as singular
+const SINGULAR : f64 = 1e-12;
+
+/// least squares method of circle and sphere fits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FitType {
+    /// linear least squares of the algebraic distance |p - c|² - r² (kasa)
+    Algebraic,
+    /// nonlinear least squares of the euclidean distance |p - c| - r with levenberg-marquardt starting at the algebraic fit
+    Geometric
+}
+
+/// statistics of the euclidean residuals |p - c| - r of the points used by a fit
+#[derive(Clone, Copy, Debug)]
+pub struct FitStats {
+    count : usize,
+    rms   : f64,
+    mean  : f64,
+    max   : f64
+}
+
+/// fitted shape with residual statistics and points used by the fit
+#[derive(Clone, Debug)]
+pub struct Fit<S> {
+    shape   : S,
+    stats   : FitStats,
+    inliers : Vec<bool>
+}
+
+/// random sample consensus of fits to minimal samples of noisy points
+///
+/// the shape of the largest consensus set within the threshold distance is refitted to its inliers
+#[derive(Clone, Copy, Debug)]
+pub struct Ransac {
+    fit        : FitType,
+    iterations : usize,
+    threshold  : f64,
+    seed       : u64
+}
+
+impl FitStats {
+    /// number of points used by the fit
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// root mean square of residuals
+    pub fn rms(&self) -> f64 {
+        self.rms
+    }
+
+    /// mean of absolute residuals
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// largest absolute residual
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+}
+
+impl<S> Fit<S> {
+    /// create new Fit of shape, residual statistics and inliers without any checks
+    pub fn new_unchecked(shape : S, stats : FitStats, inliers : Vec<bool>) -> Fit<S> {
+        Fit{shape : shape, stats : stats, inliers : inliers}
+    }
+
+    /// fitted shape
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    /// residual statistics of inliers
+    pub fn stats(&self) -> FitStats {
+        self.stats
+    }
+
+    /// points used by the fit in order of the input points
+    pub fn inliers(&self) -> &Vec<bool> {
+        &self.inliers
+    }
+
+    /// fitted shape without statistics
+    pub fn into_shape(self) -> S {
+        self.shape
+    }
+}
+
+impl Ransac {
+    /// consensus with geometric refit, 1000 iterations and seed 0 for maximal distance threshold of inliers
+    pub fn new(threshold : f64) -> Ransac {
+        Ransac{fit : FitType::Geometric, iterations : 1000, threshold : threshold, seed : 0}
+    }
+
+    /// least squares method of the refit to the inliers
+    pub fn fit(mut self, fit : FitType) -> Ransac {
+        self.fit = fit;
+        self
+    }
+
+    /// number of minimal samples
+    pub fn iterations(mut self, iterations : usize) -> Ransac {
+        self.iterations = Ord::max(iterations, 1);
+        self
+    }
+
+    /// seed of the random samples, so that equal inputs give equal fits
+    pub fn seed(mut self, seed : u64) -> Ransac {
+        self.seed = seed;
+        self
+    }
+}
+
+/// center and radius of circle (N = 2) or sphere (N = 3) fitted to points (None for less than N + 1 points or degenerate points)
+pub(crate) fn sphere<const N : usize>(pnts : &[[f64; N]], fit : FitType) -> Option<([f64; N], f64)> {
+    let (center, radius) = algebraic(pnts)?;
+
+    match fit {
+        FitType::Algebraic => Some((center, radius)),
+        FitType::Geometric => Some(geometric(pnts, center, radius))
+    }
+}
+
+/// fit of circle (N = 2) or sphere (N = 3) to the consensus set of points (None if no minimal sample has a fit)
+pub(crate) fn ransac<const N : usize>(pnts : &[[f64; N]], ransac : &Ransac) -> Option<([f64; N], f64, Vec<bool>)> {
+    if pnts.len() <= N {
+        return None
+    }
+
+    let mut rng = StdRng::seed_from_u64(ransac.seed);
+    let mut best : Option<(usize, f64, [f64; N], f64)> = None;
+
+    for _ in 0..ransac.iterations {
+        let mut sample = Vec::with_capacity(N + 1);
+
+        while sample.len() <= N {
+            let index = rng.random_range(0..pnts.len());
+
+            if !sample.contains(&index) {
+                sample.push(index);
+            }
+        }
+
+        let sample = sample.into_iter().map(|index| pnts[index]).collect::<Vec<_>>();
+
+        let Some((center, radius)) = algebraic(&sample) else {
+            continue
+        };
+
+        // consensus sets of equal size are compared by their residuals
+        let (count, sum) = pnts.iter().map(|pnt| residual(pnt, &center, radius).abs()).filter(|dist| *dist <= ransac.threshold).fold((0, 0f64), |(count, sum), dist| {
+            (count + 1, sum + dist)
+        });
+
+        let better = match best {
+            Some((best_count, best_sum, _, _)) => count > best_count || (count == best_count && sum < best_sum),
+            None => true
+        };
+
+        if better {
+            best = Some((count, sum, center, radius));
+        }
+    }
+
+    let (_, _, mut center, mut radius) = best?;
+
+    let mut inliers = inliers(pnts, &center, radius, ransac.threshold);
+
+    // inliers of the refit are refitted until they are stable
+    for _ in 0..REFIT_ITERATIONS {
+        let consensus = pnts.iter().zip(&inliers).filter(|(_, inlier)| **inlier).map(|(pnt, _)| *pnt).collect::<Vec<_>>();
+
+        let Some((refit_center, refit_radius)) = sphere(&consensus, ransac.fit) else {
+            break;
+        };
+
+        let refit = self::inliers(pnts, &refit_center, refit_radius, ransac.threshold);
+
+        if refit.iter().filter(|inlier| **inlier).count() <= N {
+            break;
+        }
+
+        let stable = refit == inliers;
+
+        center  = refit_center;
+        radius  = refit_radius;
+        inliers = refit;
+
+        if stable {
+            break;
+        }
+    }
+
+    Some((center, radius, inliers))
+}
+
+/// residual statistics of inliers
+pub(crate) fn stats<const N : usize>(pnts : &[[f64; N]], center : &[f64; N], radius : f64, inliers : &[bool]) -> FitStats {
+    let residuals = pnts.iter().zip(inliers).filter(|(_, inlier)| **inlier).map(|(pnt, _)| residual(pnt, center, radius).abs()).collect::<Vec<_>>();
+
+    let count = residuals.len();
+
+    match count {
+        0 => FitStats{count : 0, rms : 0f64, mean : 0f64, max : 0f64},
+        _ => {
+            let rms  = (residuals.iter().map(|res| res * res).sum::<f64>() / count as f64).sqrt();
+            let mean = residuals.iter().sum::<f64>() / count as f64;
+            let max  = residuals.iter().copied().fold(0f64, f64::max);
+
+            FitStats{count : count, rms : rms, mean : mean, max : max}
+        }
+    }
+}
+
+/// points with euclidean residual within threshold
+fn inliers<const N : usize>(pnts : &[[f64; N]], center : &[f64; N], radius : f64, threshold : f64) -> Vec<bool> {
+    pnts.iter().map(|pnt| residual(pnt, center, radius).abs() <= threshold).collect()
+}
+
+/// euclidean residual |p - c| - r
+fn residual<const N : usize>(pnt : &[f64; N], center : &[f64; N], radius : f64) -> f64 {
+    (0..N).map(|axis| (pnt[axis] - center[axis]).powi(2)).sum::<f64>().sqrt() - radius
+}
+
+/// linear least squares of |q|² = 2 c·q + d for points q relative to their mean with r² = d + |c|²
+fn algebraic<const N : usize>(pnts : &[[f64; N]]) -> Option<([f64; N], f64)> {
+    if pnts.len() <= N {
+        return None
+    }
+
+    let mean : [f64; N] = std::array::from_fn(|axis| pnts.iter().map(|pnt| pnt[axis]).sum::<f64>() / pnts.len() as f64);
+
+    let mut matrix = vec![vec![0f64; N + 1]; N + 1];
+    let mut rhs    = vec![0f64; N + 1];
+
+    for pnt in pnts {
+        let mut row = (0..N).map(|axis| 2f64 * (pnt[axis] - mean[axis])).collect::<Vec<_>>();
+        row.push(1f64);
+
+        let sqr = (0..N).map(|axis| (pnt[axis] - mean[axis]).powi(2)).sum::<f64>();
+
+        for i in 0..=N {
+            for j in 0..=N {
+                matrix[i][j] += row[i] * row[j];
+            }
+
+            rhs[i] += row[i] * sqr;
+        }
+    }
+
+    let solution = solve(matrix, rhs.into_iter().map(|val| [val]).collect(), SINGULAR)?.into_iter().map(|[val]| val).collect::<Vec<_>>();
+
+    let center : [f64; N] = std::array::from_fn(|axis| solution[axis] + mean[axis]);
+    let sqr = solution[N] + (0..N).map(|axis| solution[axis] * solution[axis]).sum::<f64>();
+
+    match sqr > 0f64 && sqr.is_finite() {
+        true  => Some((center, sqr.sqrt())),
+        false => None
+    }
+}
+
+/// levenberg-marquardt minimization of the squared euclidean residuals starting at center and radius
+fn geometric<const N : usize>(pnts : &[[f64; N]], center : [f64; N], radius : f64) -> ([f64; N], f64) {
+    let cost = |center : &[f64; N], radius : f64| pnts.iter().map(|pnt| residual(pnt, center, radius).powi(2)).sum::<f64>();
+
+    let mut params = center.to_vec();
+    params.push(radius);
+
+    let mut current = cost(&center, radius);
+    let mut lambda  = 1e-3;
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut normal   = vec![vec![0f64; N + 1]; N + 1];
+        let mut gradient = vec![0f64; N + 1];
+
+        for pnt in pnts {
+            let diff = (0..N).map(|axis| pnt[axis] - params[axis]).collect::<Vec<_>>();
+            let dist = diff.iter().map(|val| val * val).sum::<f64>().sqrt();
+
+            // points at the center have no direction
+            let mut jacobian = match dist > 0f64 {
+                true  => diff.iter().map(|val| -val / dist).collect::<Vec<_>>(),
+                false => vec![0f64; N]
+            };
+
+            jacobian.push(-1f64);
+
+            let res = dist - params[N];
+
+            for i in 0..=N {
+                for j in 0..=N {
+                    normal[i][j] += jacobian[i] * jacobian[j];
+                }
+
+                gradient[i] -= jacobian[i] * res;
+            }
+        }
+
+        let mut accepted = false;
+
+        while lambda < 1e12 {
+            let mut damped = normal.clone();
+
+            for i in 0..=N {
+                damped[i][i] += lambda * f64::max(normal[i][i], f64::EPSILON);
+            }
+
+            let Some(step) = solve(damped, gradient.iter().map(|val| [*val]).collect(), SINGULAR).map(|step| step.into_iter().map(|[val]| val).collect::<Vec<_>>()) else {
+                lambda *= 10f64;
+                continue
+            };
+
+            let next = params.iter().zip(&step).map(|(param, step)| param + step).collect::<Vec<_>>();
+            let next_center : [f64; N] = std::array::from_fn(|axis| next[axis]);
+            let next_cost = cost(&next_center, next[N]);
+
+            if next_cost <= current {
+                let size = step.iter().map(|val| val * val).sum::<f64>().sqrt();
+                let norm = params.iter().map(|val| val * val).sum::<f64>().sqrt();
+
+                params  = next;
+                current = next_cost;
+                lambda  = f64::max(lambda / 10f64, 1e-12);
+
+                accepted = size > 1e-12 * (1f64 + norm);
+                break;
+            }
+
+            lambda *= 10f64;
+        }
+
+        if !accepted {
+            break;
+        }
+    }
+
+    (std::array::from_fn(|axis| params[axis]), params[N].abs())
+}
+
+/// solution of linear system with M right hand sides per row by gaussian elimination with partial pivoting
+///
+/// None if the system is singular with a pivot of at most "tol" times the largest absolute matrix value
+pub(crate) fn solve<Val : Float + HPOrd, const M : usize>(mut matrix : Vec<Vec<Val>>, mut rhs : Vec<[Val; M]>, tol : Val) -> Option<Vec<[Val; M]>> {
+    let len  = rhs.len();
+    let zero = Val::zero();
+
+    let abs = |val : Val| match val < zero {
+        false => val,
+        true  => -val
+    };
+
+    let scale = matrix.iter().flatten().fold(zero, |max, val| match abs(*val) > max {
+        true  => abs(*val),
+        false => max
+    });
+
+    for col in 0..len {
+        let pivot = (col..len).fold(col, |pivot, row| {
+            match abs(matrix[row][col]) > abs(matrix[pivot][col]) {
+                true  => row,
+                false => pivot
+            }
+        });
+
+        if abs(matrix[pivot][col]) <= tol * scale || matrix[pivot][col] == zero {
+            return None
+        }
+
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot = &upper[col];
+
+        for (offset, current) in lower.iter_mut().enumerate() {
+            let row    = col + 1 + offset;
+            let factor = current[col] / pivot[col];
+
+            for (val, sub) in current[col..].iter_mut().zip(&pivot[col..]) {
+                *val = *val - factor * *sub;
+            }
+
+            rhs[row] = std::array::from_fn(|index| rhs[row][index] - factor * rhs[col][index]);
+        }
+    }
+
+    let mut solution = vec![[zero; M]; len];
+
+    for row in (0..len).rev() {
+        let mut sum = rhs[row];
+
+        for col in (row + 1)..len {
+            sum = std::array::from_fn(|index| sum[index] - matrix[row][col] * solution[col][index]);
+        }
+
+        solution[row] = sum.map(|val| val / matrix[row][row]);
+    }
+
+    Some(solution)
+}
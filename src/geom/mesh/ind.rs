@@ -57,4 +57,43 @@ impl IndSeg {
     pub fn contains_index(&self, index : usize) -> bool {
         self.a() == index || self.b() == index
     }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IndTri {
+    a : usize,
+    b : usize,
+    c : usize
+}
+
+impl IndTri {
+    pub fn new(index_a : usize, index_b : usize, index_c : usize) -> IndTri {
+        IndTri{a : index_a, b : index_b, c : index_c}
+    }
+
+    pub fn a(&self) -> usize {
+        self.a 
+    }
+
+    pub fn b(&self) -> usize {
+        self.b
+    }
+
+    pub fn c(&self) -> usize {
+        self.c
+    }
+
+    pub fn indices(&self) -> [usize; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    /// edges ab, bc and ca
+    pub fn segments(&self) -> [IndSeg; 3] {
+        [IndSeg::new(self.a, self.b), IndSeg::new(self.b, self.c), IndSeg::new(self.c, self.a)]
+    }
+
+    /// triangle with reversed orientation
+    pub fn flip(&self) -> IndTri {
+        IndTri::new(self.a, self.c, self.b)
+    }
 }
\ No newline at end of file
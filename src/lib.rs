@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+/// module for precomputed contour atlases
+pub mod atlas;
+
 /// module for conversions
 pub mod conv;
 
@@ -48,30 +51,22 @@ pub unsafe extern "C" fn rust_test(byte : u8, nat : usize, nat32 : u32) {
     println!("rust byte={byte} nat={nat} nat32={nat32}");
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn rust_generateTStielDaten2D(
+unsafe fn read_ind_seg_mesh_3d(
     vertex_values      : *mut f64, 
     vertex_values_len  : usize,
     semgent_values     : *mut usize,
-    segment_values_len : usize,
-    point_values       : *mut f64,
-    point_values_len   : usize,
-    inklination        : f64,
-    anteversion        : f64,
-    stiel_daten        : *mut StielDaten2D
-    ) -> bool
-{   
-    if vertex_values_len % 3 != 0 || segment_values_len % 2 != 0 || point_values_len % 3 != 0 {
-        return false;
+    segment_values_len : usize
+    ) -> Option<crate::geom::d3::mesh::ind::IndSegMesh<(f64, f64, f64)>>
+{
+    if vertex_values_len % 3 != 0 || segment_values_len % 2 != 0 {
+        return None;
     }
 
     let vertices_len = vertex_values_len  / 3;
     let segments_len = segment_values_len / 2;
-    let points_len   = point_values_len   / 3;
 
     let mut vertices = Vec::with_capacity(vertices_len);
     let mut segments = Vec::with_capacity(segments_len);
-    let mut points   = Vec::with_capacity(points_len);
 
     for i in 0..vertices_len {
         let offset = i as isize * 3;
@@ -94,26 +89,11 @@ pub unsafe extern "C" fn rust_generateTStielDaten2D(
         segments.push(segment);
     }
 
-    for i in 0..points_len {
-        let offset = i as isize * 3;
-
-        let x = *point_values.offset(offset + 0);
-        let y = *point_values.offset(offset + 1);
-        let z = *point_values.offset(offset + 2);
-
-        let point = (x, y, z);
-        points.push(point);
-    }
-
     let indexed_mesh_3d = crate::geom::d3::mesh::ind::IndSegMesh::new_unchecked(vertices, segments);
-    let indexed_mesh_3d = indexed_mesh_3d.rotate_y(inklination).rotate_z(anteversion);
-
-    let indexed_mesh_2d = indexed_mesh_3d.proj_2d(|vertex| (vertex.x(), vertex.z()));
-
-    let Some(contour) = indexed_mesh_2d.rrcontour(10_000) else {
-        return false
-    };
+    Some(indexed_mesh_3d)
+}
 
+unsafe fn write_stiel_daten_2d<V : Vector2<Val = f64>>(contour : &[V], stiel_daten : *mut StielDaten2D) {
     let contour_len = contour.len() * 2;
 
     let contour_values = libc::malloc(std::mem::size_of::<f64>() * contour_len) as *mut f64;
@@ -121,7 +101,7 @@ pub unsafe extern "C" fn rust_generateTStielDaten2D(
 
     for i in 0..contour.len() {
         let offset = i as isize * 2;
-        let vertex = contour[i];
+        let vertex = &contour[i];
 
         *contour_values.offset(offset + 0) = vertex.x();
         *contour_values.offset(offset + 1) = vertex.y();
@@ -133,10 +113,167 @@ pub unsafe extern "C" fn rust_generateTStielDaten2D(
         point_values:       points_values, 
         point_values_len:   0     
     };
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rust_generateTStielDaten2D(
+    vertex_values      : *mut f64, 
+    vertex_values_len  : usize,
+    semgent_values     : *mut usize,
+    segment_values_len : usize,
+    point_values       : *mut f64,
+    point_values_len   : usize,
+    inklination        : f64,
+    anteversion        : f64,
+    stiel_daten        : *mut StielDaten2D
+    ) -> bool
+{   
+    if point_values_len % 3 != 0 {
+        return false;
+    }
+
+    let points_len = point_values_len / 3;
+    let mut points = Vec::with_capacity(points_len);
+
+    for i in 0..points_len {
+        let offset = i as isize * 3;
+
+        let x = *point_values.offset(offset + 0);
+        let y = *point_values.offset(offset + 1);
+        let z = *point_values.offset(offset + 2);
+
+        let point = (x, y, z);
+        points.push(point);
+    }
+
+    let Some(indexed_mesh_3d) = read_ind_seg_mesh_3d(vertex_values, vertex_values_len, semgent_values, segment_values_len) else {
+        return false
+    };
+
+    let Some(contour) = indexed_mesh_3d.template_contour(inklination, anteversion, 10_000) else {
+        return false
+    };
+
+    write_stiel_daten_2d(&contour, stiel_daten);
 
     true 
 }
 
+/// generate contour atlas over inclination and anteversion grid and write it to binary file
+///
+/// # Safety
+///
+/// `vertex_values`, `semgent_values` and `path_chars` must be valid for their given lengths,
+/// none of them may be null
+#[no_mangle]
+pub unsafe extern "C" fn rust_generateContourAtlas(
+    vertex_values      : *mut f64, 
+    vertex_values_len  : usize,
+    semgent_values     : *mut usize,
+    segment_values_len : usize,
+    inklination_start  : f64,
+    inklination_end    : f64,
+    inklination_count  : usize,
+    anteversion_start  : f64,
+    anteversion_end    : f64,
+    anteversion_count  : usize,
+    samples            : usize,
+    path_chars         : *mut u8,
+    path_chars_len     : usize
+    ) -> bool
+{
+    let path_slice = std::slice::from_raw_parts_mut(path_chars, path_chars_len);
+
+    let Ok(path) = std::str::from_utf8(path_slice) else {
+        return false;
+    };
+
+    let Some(indexed_mesh_3d) = read_ind_seg_mesh_3d(vertex_values, vertex_values_len, semgent_values, segment_values_len) else {
+        return false
+    };
+
+    let inklination = crate::atlas::AngleGrid::new(inklination_start, inklination_end, inklination_count);
+    let anteversion = crate::atlas::AngleGrid::new(anteversion_start, anteversion_end, anteversion_count);
+
+    let atlas = crate::atlas::Atlas::generate(&indexed_mesh_3d, inklination, anteversion, samples, 10_000);
+
+    atlas.write_binary(path).is_ok()
+}
+
+/// load atlas from binary file once and return opaque handle for queries (null on failure)
+///
+/// # Safety
+///
+/// `path_chars` must be valid for `path_chars_len` bytes and may not be null
+#[no_mangle]
+pub unsafe extern "C" fn rust_loadContourAtlas(
+    path_chars     : *mut u8,
+    path_chars_len : usize
+    ) -> *mut crate::atlas::Atlas
+{
+    let path_slice = std::slice::from_raw_parts_mut(path_chars, path_chars_len);
+
+    let Ok(path) = std::str::from_utf8(path_slice) else {
+        return std::ptr::null_mut();
+    };
+
+    let Ok(atlas) = crate::atlas::Atlas::read_binary(path) else {
+        return std::ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(atlas))
+}
+
+/// query contour of atlas handle returned by rust_loadContourAtlas
+///
+/// # Safety
+///
+/// `atlas` must be null or a live handle from rust_loadContourAtlas, `error` must be null or
+/// valid for writes and `stiel_daten` must be valid for writes and may not be null
+#[no_mangle]
+pub unsafe extern "C" fn rust_atlasTStielDaten2D(
+    atlas       : *const crate::atlas::Atlas,
+    inklination : f64,
+    anteversion : f64,
+    blend       : bool,
+    error       : *mut f64,
+    stiel_daten : *mut StielDaten2D
+    ) -> bool
+{
+    let Some(atlas) = atlas.as_ref() else {
+        return false;
+    };
+
+    let query = match blend {
+        false => crate::atlas::AtlasQuery::Nearest,
+        true  => crate::atlas::AtlasQuery::Blend
+    };
+
+    let Some(contour) = atlas.contour(inklination, anteversion, query) else {
+        return false
+    };
+
+    if let Some(error) = error.as_mut() {
+        *error = contour.error();
+    }
+
+    write_stiel_daten_2d(contour.vertices(), stiel_daten);
+
+    true
+}
+
+/// free atlas handle returned by rust_loadContourAtlas (null is ignored)
+///
+/// # Safety
+///
+/// `atlas` must be null or a handle from rust_loadContourAtlas that was not freed before
+#[no_mangle]
+pub unsafe extern "C" fn rust_freeContourAtlas(atlas : *mut crate::atlas::Atlas) {
+    if !atlas.is_null() {
+        drop(Box::from_raw(atlas));
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn rust_loadFromBinaryStl(
     vertex_values      : *mut *mut f64, 
@@ -2,12 +2,44 @@ use image::*;
 
 use svg;
 
+use crate::draw::drr::*;
+use crate::draw::overlay::*;
 use crate::draw::rast::*;
+use crate::draw::render::*;
 use crate::draw::vect::*;
+use crate::draw::viewport::*;
 
+use crate::file::dxf::*;
 use crate::file::stl::*;
+use crate::file::svg::*;
 
-use crate::geom::d2::prim::PRect;
+use crate::geom::d2::clip::*;
+
+use crate::geom::d2::offset::{
+    JoinType,
+    Offset
+};
+
+use crate::geom::fit::{
+    FitType,
+    Ransac
+};
+
+use crate::geom::d2::poly::Poly;
+
+use crate::geom::d2::prim::{
+    Arc,
+    Bezier,
+    BSpline,
+    Circle,
+    Ellipse,
+    PRect
+};
+
+use crate::geom::d2::smooth::{
+    Smooth,
+    SmoothType
+};
 
 use crate::geom::d2::prim::seg::{
     PSeg,
@@ -23,8 +55,17 @@ use crate::geom::d3::prim::vect::{
     Vector as Vector3
 };
 
+use crate::geom::d3::prim::{
+    Sphere
+};
+
+use crate::geom::d3::analysis::{
+    AxisAnalysis
+};
+
 use crate::geom::d3::mesh::ind::{
-    IndSegMesh
+    IndSegMesh,
+    IndTriMesh
 };
 
 pub fn seg_intsec() {
@@ -48,9 +89,10 @@ pub fn fill_rect_shape() {
     let rect = PRect::new_unchecked(a, b, c, d);
 
     let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::pixels(2000, 2000);
     let color = |_ : Vect2<f32>| Rgb::from([255u8, 255u8, 255u8]);
 
-    fill_shape_float(&mut image, rect, color);
+    fill_shape_float(&mut image, &viewport, rect, color);
 
     let save_path = r#"fill_rect_shape.bmp"#;
     let _ = image.save(save_path);
@@ -68,13 +110,14 @@ pub fn draw_line_segs() {
     let da = PSeg::new(d, a);
 
     let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::pixels(2000, 2000);
     let color = |_ : Vect2<f32>| Rgb::from([255u8, 255u8, 255u8]);
     let width = 7f32;
 
-    draw_seg_float(&mut image, ab, color, width);
-    draw_seg_float(&mut image, bc, color, width);
-    draw_seg_float(&mut image, cd, color, width);
-    draw_seg_float(&mut image, da, color, width);
+    draw_seg_float(&mut image, &viewport, ab, color, width);
+    draw_seg_float(&mut image, &viewport, bc, color, width);
+    draw_seg_float(&mut image, &viewport, cd, color, width);
+    draw_seg_float(&mut image, &viewport, da, color, width);
 
     let save_path = r#"draw_line_segs.bmp"#;
     let _ = image.save(save_path);
@@ -86,20 +129,12 @@ pub fn stl_to_ind_seg_mesh() -> std::io::Result<()> {
 
     let mesh = IndSegMesh::from_stl(&stl);
 
-    let mut mesh = mesh.proj_2d(|vertex| (vertex.x(), vertex.z()));
+    let mesh = mesh.proj_2d(|vertex| (vertex.x(), vertex.z()));
 
     let mut image = RgbImage::new(5000, 5000);
+    let viewport = Viewport::fit(mesh.bounds(), 5000, 5000, 0f64);
     let color = |_ : Vect2<f32>| Rgb::from([255u8, 255u8, 255u8]);
-    let width = 3f32;
-
-    let bounds = mesh.bounds();
-    let im_bounds = image.dimensions().map::<(f32, f32), _>(|val| val as f32);
-
-    let scales = im_bounds.div(bounds.size());
-    let scale = f32::min(scales.x(),scales.y());
-
-    mesh.add_mut(bounds.start().neg());
-    mesh.vmul_mut(scale);
+    let width = 3f32 / viewport.scale() as f32;
 
     for segment in mesh.segments() {
         let a = mesh.vertex(segment.a());
@@ -107,7 +142,7 @@ pub fn stl_to_ind_seg_mesh() -> std::io::Result<()> {
 
         let pseg = PSeg::new(a, b);
 
-        draw_seg_float(&mut image, pseg, color, width);
+        draw_seg_float(&mut image, &viewport, pseg, color, width);
     }
 
     let save_path = r#"stl_to_ind_seg_mesh.bmp"#;
@@ -122,22 +157,14 @@ pub fn ing_seg_mesh_contour() -> std::io::Result<()> {
 
     let mesh = IndSegMesh::from_stl(&stl);
 
-    let mut mesh = mesh.proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let mesh = mesh.proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
 
     let mut image = RgbImage::new(4000, 4000);
+    let viewport = Viewport::fit(mesh.bounds(), 4000, 4000, 0f64);
     let white = |_ : Vect2<f32>| Rgb::from([255u8, 255u8, 255u8]);
     let red = |_ : Vect2<f32>| Rgb::from([255u8, 0u8, 0u8]);
 
-    let width = 2f32;
-
-    let bounds = mesh.bounds();
-    let im_bounds = image.dimensions().map::<(f64, f64), _>(|val| val as f64);
-
-    let scales = im_bounds.div(bounds.size());
-    let scale = f64::min(scales.x(),scales.y());
-
-    mesh.add_mut(bounds.start().neg());
-    mesh.vmul_mut(scale);
+    let width = 2f32 / viewport.scale() as f32;
 
     for segment in mesh.segments() {
         let a = mesh.vertex(segment.a()).map::<(f32, f32), _>(|val| val as f32);
@@ -145,7 +172,7 @@ pub fn ing_seg_mesh_contour() -> std::io::Result<()> {
 
         let pseg = PSeg::new(a, b);
 
-        draw_seg_float(&mut image, pseg, white, width);
+        draw_seg_float(&mut image, &viewport, pseg, white, width);
     }
 
     let contour = mesh.contour().unwrap();
@@ -156,7 +183,7 @@ pub fn ing_seg_mesh_contour() -> std::io::Result<()> {
 
         let pseg = PSeg::new(a, b);
 
-        draw_seg_float(&mut image, pseg, red, width);
+        draw_seg_float(&mut image, &viewport, pseg, red, width);
     }
 
     let save_path = r#"ing_seg_mesh_contour.bmp"#;
@@ -171,23 +198,14 @@ pub fn ing_seg_mesh_rrcontour() -> std::io::Result<()> {
 
     let mesh = IndSegMesh::from_stl(&stl);
 
-    let mut mesh = mesh.proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64)).deduplicate();
+    let mesh = mesh.proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64)).deduplicate();
 
     let white = |_ : Vect2<f32>| Rgb::from([255u8, 255u8, 255u8]);
     let red = |_ : Vect2<f32>| Rgb::from([255u8, 0u8, 0u8]);
     let green = |_ : Vect2<f32>| Rgb::from([0u8, 255u8, 0u8]);
 
-    let width = 2f32;
-
-    let bounds = mesh.bounds();
-    let im_bounds = (3800, 3800).map::<(f64, f64), _>(|val| val as f64);
-
-    let scales = im_bounds.div(bounds.size());
-    let scale = f64::min(scales.x(),scales.y());
-
-    mesh.add_mut(bounds.start().neg());
-    mesh.vmul_mut(scale);
-    mesh.add_mut((100f64, 100f64));
+    let viewport = Viewport::fit(mesh.bounds(), 4000, 4000, 100f64);
+    let width = 2f32 / viewport.scale() as f32;
 
     let mut count = 0;
 
@@ -217,7 +235,7 @@ pub fn ing_seg_mesh_rrcontour() -> std::io::Result<()> {
     
             let pseg = PSeg::new(a, b);
     
-            draw_seg_float(&mut image, pseg, white, width);
+            draw_seg_float(&mut image, &viewport, pseg, white, width);
         }
 
         let a = (&contour[index]).map::<(f32, f32), _>(|val| val as f32);
@@ -225,9 +243,7 @@ pub fn ing_seg_mesh_rrcontour() -> std::io::Result<()> {
 
         let ab = PSeg::new(a, b);
 
-        draw_seg_float(&mut image, ab, red, width + 2f32);
-
-        horizontal_flip(&mut image);
+        draw_seg_float(&mut image, &viewport, ab, red, 2f32 * width);
 
         let save_path = format!("rrcontour_examples\\{}.bmp", index);
         let _ = image.save(save_path);
@@ -395,32 +411,14 @@ pub fn mesh_2d_contour() -> std::io::Result<()> {
     }
 
     let segments = mesh.segments().iter().map(|iseg| mesh.point_segment(iseg));
-    let mesh_path = draw_segments_to_path(segments)
-        .set("stroke", "black")
-        .set("stroke-width", "0.2");
 
-    let mut contour_data = svg::node::element::path::Data::new();
-
-    for index in 0..contour.len() {
-        let a = contour[index];
-        let b = contour[(index + 1) % contour.len()];
-
-        contour_data = contour_data
-            .move_to((a.x(), a.y()))
-            .line_to((b.x(), b.y()));
-    }
-
-    let contour_path = svg::node::element::Path::new()
-        .set("stroke", "red")
-        .set("stroke-width", "0.5")
-        .set("d", contour_data);
-
-    let svg = svg::Document::new()
-        .add(mesh_path)
-        .add(contour_path);
+    let scene = Scene::new()
+        .margin(5f64)
+        .layer(Group::layer("mesh").segments(segments, Style::stroke("black", 0.2)))
+        .layer(Group::layer("contour").contour(&contour, Style::stroke("red", 0.5)));
 
     let svg_path = format!("debug\\svg\\test.svg");
-    svg::save(svg_path, &svg).unwrap();
+    scene.save(svg_path).unwrap();
 
     Ok(())
 }
@@ -458,33 +456,518 @@ pub fn template_mesh_2d_contour() -> std::io::Result<()> {
         println!("elapsed: {:?}", elapsed);
 
         let segments = mesh.segments().iter().map(|iseg| mesh.point_segment(iseg));
-        let mesh_path = draw_segments_to_path(segments)
-            .set("stroke", "black")
-            .set("stroke-width", "0.2");
 
-        let mut contour_data = svg::node::element::path::Data::new();
+        let scene = Scene::new()
+            .layer(Group::layer("mesh").segments(segments, Style::stroke("black", 0.2)))
+            .layer(Group::layer("contour").contour(&contour, Style::stroke("red", 1.5)));
 
-        for index in 0..contour.len() {
-            let a = contour[index];
-            let b = contour[(index + 1) % contour.len()];
+        let svg_path = format!("debug\\svg\\{}.svg", index);
+        scene.save(svg_path).unwrap();
+    }
 
-            contour_data = contour_data
-                .move_to((a.x(), a.y()))
-                .line_to((b.x(), b.y()));
-        }
+    Ok(())
+}
 
-        let contour_path = svg::node::element::Path::new()
-            .set("stroke", "red")
-            .set("stroke-width", "1.5")
-            .set("d", contour_data);
+pub fn scene_to_svg() -> std::io::Result<()> {
+    let outer = vec![(0f64, 0f64), (60f64, 0f64), (60f64, 40f64), (0f64, 40f64)];
+    let hole  = vec![(20f64, 10f64), (40f64, 10f64), (40f64, 30f64), (20f64, 30f64)];
 
-        let svg = svg::Document::new()
-            .add(mesh_path)
-            .add(contour_path);
+    let label_style = Style::new().fill_color("black").font_size(4f64);
 
-        let svg_path = format!("debug\\svg\\{}.svg", index);
-        svg::save(svg_path, &svg).unwrap();
+    let shapes = Group::layer("shapes")
+        .poly_with_holes(&outer, &[&hole], Style::fill("lightgray").stroke_color("black").stroke_width(0.3))
+        .polyline(&[(0f64, 50f64), (30f64, 60f64), (60f64, 50f64)], Style::stroke("blue", 0.5));
+
+    let labels = Group::layer("labels")
+        .text((30f64, -6f64), "60 mm x 40 mm", Anchor::Middle, label_style);
+
+    let scene = Scene::new()
+        .unit(Unit::Mm)
+        .margin(10f64)
+        .layer(shapes)
+        .layer(labels);
+
+    scene.save("scene_to_svg.svg")
+}
+
+pub fn annotated_template_to_svg() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\177-204_26_ml.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let inclination   = 45f64.to_radians();
+    let anteversion   = 10f64.to_radians();
+    let magnification = 1.15f64;
+
+    let mesh = IndSegMesh::from_stl(&stl);
+    let mesh = IndSegMesh::new_unchecked(
+        mesh.vertices().iter().map(|vertex| (vertex.x() as f64, vertex.y() as f64, vertex.z() as f64)).collect(), 
+        mesh.segments().clone()
+    );
+
+    let contour = mesh.template_contour(inclination, anteversion, 10_000).unwrap();
+
+    let bounds = Poly::new(contour.clone()).bounds();
+    let start  = bounds.start();
+    let end    = bounds.end();
+
+    let style = Style::stroke("black", 0.25);
+    let block = TitleBlock::template(&stl.name(), inclination, anteversion, magnification);
+
+    let annotations = Group::layer("annotations")
+        .scale_bar((start.x(), start.y() - 20f64), 50f64, 5, magnification, style.clone(), 3f64)
+        .ruler((end.x() + 10f64, start.y()), (end.x() + 10f64, end.y()), 1f64, 10, style.clone(), 3f64)
+        .angle((start.x(), end.y()), (1f64, 0f64), (inclination.cos(), -inclination.sin()), 15f64, style.clone(), 3f64)
+        .title_block((end.x() + 25f64, end.y()), 70f64, &block, style, 3f64);
+
+    let scene = Scene::new()
+        .unit(Unit::Mm)
+        .scale(magnification)
+        .margin(10f64)
+        .layer(Group::layer("contour").contour(&contour, Style::stroke("red", 0.5)))
+        .layer(annotations);
+
+    scene.save("annotated_template_to_svg.svg")
+}
+
+pub fn contour_to_dxf() -> std::io::Result<()> {
+    let outer = vec![(0f64, 0f64), (60f64, 0f64), (60f64, 40f64), (0f64, 40f64)];
+    let hole  = vec![(20f64, 10f64), (40f64, 10f64), (40f64, 30f64), (20f64, 30f64)];
+
+    let dxf = Dxf::new()
+        .layer("cut", 1)
+        .layer("engrave", 5)
+        .contour("cut", &outer)
+        .contour("cut", &hole)
+        .segments("engrave", vec![PSeg::new((0f64, 50f64), (60f64, 50f64))])
+        .text("engrave", (0f64, 55f64), 4f64, "60 mm x 40 mm");
+
+    dxf.write("contour_to_dxf.dxf")?;
+
+    let dxf = Dxf::read("contour_to_dxf.dxf")?;
+
+    println!("{} polygons on layer cut", dxf.filter_layer("cut").polys().len());
+
+    Ok(())
+}
+
+pub fn fill_poly_with_holes_scanline() {
+    let outer = vec![(200f64, 200f64), (1800f64, 300f64), (1000f64, 1800f64)];
+    let hole  = vec![(800f64, 600f64), (1200f64, 600f64), (1000f64, 1000f64)];
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::pixels(2000, 2000);
+    let color = |_ : Vect2<f64>| Rgb::from([255u8, 255u8, 255u8]);
+
+    fill_poly_with_holes_float(&mut image, &viewport, &outer, &[&hole], FillRule::EvenOdd, color);
+
+    let save_path = r#"fill_poly_with_holes_scanline.bmp"#;
+    let _ = image.save(save_path);
+}
+
+pub fn draw_polyline_anti_aliased() {
+    let vertices = vec![(200f64, 1800f64), (600f64, 200f64), (1000f64, 1800f64), (1400f64, 300f64), (1800f64, 1600f64)];
+
+    let mut image = RgbaImage::new(2000, 2000);
+    let viewport = Viewport::pixels(2000, 2000);
+    let stroke = Stroke::new(40f64).cap(Cap::Round).join(Join::Round);
+
+    draw_polyline_aa(&mut image, &viewport, &vertices, false, stroke, Rgba::from([255u8, 0u8, 0u8, 160u8]));
+
+    let save_path = r#"draw_polyline_anti_aliased.png"#;
+    let _ = image.save(save_path);
+}
+
+pub fn template_overlay() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\177-204_26_ml.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl);
+    let mesh = IndSegMesh::new_unchecked(
+        mesh.vertices().iter().map(|vertex| (vertex.x() as f64, vertex.y() as f64, vertex.z() as f64)).collect(), 
+        mesh.segments().clone()
+    );
+
+    let contour = mesh.template_contour(45f64.to_radians(), 10f64.to_radians(), 10_000).unwrap();
+
+    // 0.143 mm per pixel at 1.15 magnification
+    let placement = Placement::new(0.143f64 / 1.15f64, (1200f64, 800f64)).rotation(5f64.to_radians());
+
+    let style  = OverlayStyle::new(Rgb::from([255u8, 0u8, 0u8]), 3f64).opacity(0.8f64).stroke(Stroke::new(3f64).join(Join::Round));
+    let marker = OverlayStyle::new(Rgb::from([0u8, 255u8, 0u8]), 2f64);
+
+    let radiograph_path = r#"C:\OneDrive\Code\Bachelor\radiograph.png"#;
+
+    Overlay::open(radiograph_path)?
+        .contour(&contour, true, &placement, &style)
+        .landmark(&(0f64, 0f64), Marker::Cross, 30f64, &placement, &marker)
+        .save("template_overlay.png")
+}
+
+pub fn render_stl_thumbnail() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\177-204_26_ml.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl)
+        .rotate_y(45f32.to_radians())
+        .rotate_z(10f32.to_radians());
+
+    let camera = Camera::fit(mesh.vertices(), (0f32, 1f32, 0f32), (0f32, 0f32, 1f32), 1f64, 0.05f64).unwrap();
+
+    Render::new(512, 512, camera, (-1f32, -1f32, 1f32), Rgb::from([40u8, 40u8, 40u8]))
+        .mesh(&mesh, Rgb::from([210u8, 210u8, 220u8]), Shading::Gouraud)
+        .wireframe(&mesh.ind_seg_mesh(), Rgb::from([0u8, 0u8, 0u8]))
+        .save("render_stl_thumbnail.png")
+}
+
+pub fn stl_to_drr() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\177-204_26_ml.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl)
+        .rotate_y(45f32.to_radians())
+        .rotate_z(10f32.to_radians());
+
+    let drr = Drr::new(1024, 1024, 0.2f64).attenuation(0.03f64);
+
+    match drr.render(&mesh).save("stl_to_drr.png") {
+        Ok(())   => Ok(()),
+        Err(err) => Err(std::io::Error::other(err.to_string()))
+    }
+}
+
+pub fn contour_measurements() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl);
+
+    let mesh = mesh.proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let contour = mesh.contour().unwrap();
+    let poly = Poly::new(contour);
+
+    println!("area = {}; perimeter = {};", poly.area(), poly.perimeter());
+    println!("orientation = {:?}; simple = {};", poly.orientation(), poly.is_simple());
+    println!("centroid = {:?}; moments = {:?};", poly.centroid(), poly.moments());
+    println!("contains centroid = {:?};", poly.centroid().map(|centroid| poly.contains_non_zero(centroid)));
+
+    Ok(())
+}
+
+pub fn fill_hip_primitives() {
+    let head = Circle::new((0f64, 0f64), 24f64);
+    let cup  = Arc::circular((0f64, 0f64), 28f64, 200f64.to_radians(), 180f64.to_radians());
+    let neck = Ellipse::axes((18f64, -22f64), 30f64, 14f64, -50f64.to_radians());
+
+    let bounds = neck.poly(0.1f64).bounds();
+
+    let mut image = RgbImage::new(1000, 1000);
+    let viewport = Viewport::fit(bounds, 1000, 1000, 250f64);
+
+    fill_shape_float(&mut image, &viewport, &neck, |_ : Vect2<f64>| Rgb::from([120u8, 120u8, 120u8]));
+    fill_shape_float(&mut image, &viewport, &cup, |_ : Vect2<f64>| Rgb::from([0u8, 120u8, 255u8]));
+    fill_shape_float(&mut image, &viewport, &head, |_ : Vect2<f64>| Rgb::from([255u8, 255u8, 255u8]));
+
+    let save_path = r#"fill_hip_primitives.bmp"#;
+    let _ = image.save(save_path);
+}
+
+pub fn stem_canal_overlap() -> std::io::Result<()> {
+    let stem_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let canal_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-204_26.stl"#;
+
+    let stem = IndSegMesh::from_stl(&Stl::read_binary(stem_path)?).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let canal = IndSegMesh::from_stl(&Stl::read_binary(canal_path)?).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let stem = Poly::new(stem.contour().unwrap());
+    let canal = Poly::new(canal.contour().unwrap());
+
+    let inside = stem.boolean(&canal, BoolOp::Intersection);
+    let outside = stem.boolean(&canal, BoolOp::Difference);
+
+    println!("stem area = {}; inside canal = {}; outside canal = {};", stem.area(), inside.area(), outside.area());
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::fit(canal.bounds(), 2000, 2000, 20f64);
+
+    fill_contours_float(&mut image, &viewport, &inside.contours(), FillRule::EvenOdd, |_| Rgb::from([0u8, 200u8, 0u8]));
+    fill_contours_float(&mut image, &viewport, &outside.contours(), FillRule::EvenOdd, |_| Rgb::from([220u8, 0u8, 0u8]));
+
+    let save_path = r#"stem_canal_overlap.bmp"#;
+    let _ = image.save(save_path);
+
+    Ok(())
+}
+
+pub fn cement_mantle_outline() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let stem = Poly::new(mesh.contour().unwrap());
+
+    let mantle = stem.offset(2f64, &Offset::new(JoinType::Round).tolerance(0.01f64));
+    let undersize = stem.offset(-0.5f64, &Offset::new(JoinType::Miter).miter_limit(2f64));
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::fit(mantle.polys()[0].outer().bounds(), 2000, 2000, 20f64);
+
+    fill_contours_float(&mut image, &viewport, &mantle.contours(), FillRule::EvenOdd, |_| Rgb::from([160u8, 160u8, 160u8]));
+    fill_contours_float(&mut image, &viewport, &[stem.vertices().as_slice()], FillRule::EvenOdd, |_| Rgb::from([255u8, 255u8, 255u8]));
+    fill_contours_float(&mut image, &viewport, &undersize.contours(), FillRule::EvenOdd, |_| Rgb::from([0u8, 120u8, 255u8]));
+
+    let save_path = r#"cement_mantle_outline.bmp"#;
+    let _ = image.save(save_path);
+
+    Ok(())
+}
+
+pub fn simplify_contour() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let poly = Poly::new(mesh.contour().unwrap());
+
+    for tol in [0.01f64, 0.1f64, 0.5f64] {
+        let rdp = poly.rdp(tol, true);
+        let visvalingam = poly.visvalingam(tol, true);
+
+        println!("tol = {}; vertices = {}; rdp = {}; visvalingam = {};", tol, poly.vertices().len(), rdp.vertices().len(), visvalingam.vertices().len());
+    }
+
+    Ok(())
+}
+
+pub fn smooth_contour() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let poly = Poly::new(mesh.contour().unwrap()).resample(0.5f64, 45f64.to_radians());
+
+    for smooth in [SmoothType::Chaikin, SmoothType::Laplacian, SmoothType::CatmullRom, SmoothType::BSpline] {
+        let smoothed = poly.smooth(&Smooth::new(smooth).corner_angle(45f64.to_radians()));
+
+        println!("{:?}: vertices = {}; area = {}; smoothed vertices = {}; smoothed area = {};", smooth, poly.vertices().len(), poly.area(), smoothed.vertices().len(), smoothed.area());
+    }
+
+    Ok(())
+}
+
+pub fn fit_stem_curves() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let contour = mesh.contour().unwrap();
+
+    let spline = BSpline::<Vect2<f64>>::fit_closed(&contour, 40, 3).unwrap();
+    let bezier = Bezier::<Vect2<f64>>::fit(&contour[..(contour.len() / 4)], 3).unwrap();
+
+    println!("spline bounds = {:?}; spline vertices = {};", spline.bounds(), spline.poly(0.01f64).vertices().len());
+    println!("bezier pnts = {:?}; bezier bounds = {:?};", bezier.pnts(), bezier.bounds());
+
+    Ok(())
+}
+
+pub fn stem_convex_hull() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let hull = mesh.convex_hull();
+    let stem = Poly::new(mesh.contour().unwrap());
+
+    println!("hull vertices = {}; hull area = {}; stem area = {};", hull.vertices().len(), hull.area(), stem.area());
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::fit(hull.bounds(), 2000, 2000, 20f64);
+
+    fill_contours_float(&mut image, &viewport, &[hull.vertices().as_slice()], FillRule::EvenOdd, |_| Rgb::from([160u8, 160u8, 160u8]));
+    fill_contours_float(&mut image, &viewport, &[stem.vertices().as_slice()], FillRule::EvenOdd, |_| Rgb::from([255u8, 255u8, 255u8]));
+
+    let save_path = r#"stem_convex_hull.bmp"#;
+    let _ = image.save(save_path);
+
+    Ok(())
+}
+
+pub fn stem_hull_measurements() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl);
+
+    match mesh.convex_hull() {
+        Ok(hull) => {
+            println!("stem volume = {}; stem area = {};", mesh.volume(), mesh.area());
+            println!("hull vertices = {}; hull volume = {}; hull area = {};", hull.vertices().len(), hull.volume(), hull.area());
+        },
+        Err(err) => println!("no hull: {:?}", err)
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn stem_axis_and_width() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl);
+
+    if let Some(obox) = mesh.oriented_box() {
+        println!("long axis = {:?}; size = {:?};", obox.axes()[0], obox.size());
+    }
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+    let stem = Poly::new(mesh.contour().unwrap());
+
+    let (Some(area), Some(width)) = (stem.min_area_rect(), stem.min_width_rect()) else {
+        return Ok(())
+    };
+
+    println!("min area rect = {:?}; min width = {};", area.pnts(), width.ad().len());
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::fit(area.bounds(), 2000, 2000, 20f64);
+
+    fill_contours_float(&mut image, &viewport, &[area.pnts().as_slice()], FillRule::EvenOdd, |_| Rgb::from([160u8, 160u8, 160u8]));
+    fill_contours_float(&mut image, &viewport, &[stem.vertices().as_slice()], FillRule::EvenOdd, |_| Rgb::from([255u8, 255u8, 255u8]));
+
+    let save_path = r#"stem_axis_and_width.bmp"#;
+    let _ = image.save(save_path);
+
+    Ok(())
+}
+
+pub fn stem_canonical_frame() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl);
+    let analysis = AxisAnalysis::new();
+
+    let Some(long) = analysis.long_axis(&mesh) else {
+        return Ok(())
+    };
+
+    println!("long axis = {:?} {:?};", long.pnt(), long.dir());
+
+    if let Some(neck) = analysis.neck_axis(&mesh, &long) {
+        println!("neck axis = {:?} {:?}; neck angle = {};", neck.pnt(), neck.dir(), neck.dir().dot(long.dir()).acos().to_degrees());
+    }
+
+    let Some(frame) = analysis.frame(&mesh) else {
+        return Ok(())
+    };
+
+    let canonical = IndSegMesh::from_stl(&stl).transform(&frame);
+    let contour = canonical.template_contour(10f64.to_radians(), 15f64.to_radians(), 10_000).unwrap();
+
+    let poly = Poly::new(contour);
+
+    let mut image = RgbImage::new(2000, 2000);
+    let viewport = Viewport::fit(poly.bounds(), 2000, 2000, 20f64);
+
+    fill_contours_float(&mut image, &viewport, &[poly.vertices().as_slice()], FillRule::EvenOdd, |_| Rgb::from([255u8, 255u8, 255u8]));
+
+    let save_path = r#"stem_canonical_frame.bmp"#;
+    let _ = image.save(save_path);
+
+    Ok(())
+}
+
+pub fn stem_taper_fits() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndTriMesh::from_stl(&stl);
+    let analysis = AxisAnalysis::new();
+
+    let Some(neck) = analysis.long_axis(&mesh).and_then(|long| analysis.neck_axis(&mesh, &long)) else {
+        return Ok(())
+    };
+
+    // vertices at the end of the taper
+    let max = mesh.vertices().iter().map(|vertex| neck.along(vertex)).fold(f64::NEG_INFINITY, f64::max);
+    let taper = mesh.vertices().iter().filter(|vertex| neck.along(*vertex) > max - 5f64).collect::<Vec<_>>();
+
+    if let Some(fit) = Sphere::ransac(&taper, &Ransac::new(0.05)) {
+        println!("sphere center = {:?}; radius = {}; stats = {:?};", fit.shape().center(), fit.shape().radius(), fit.stats());
+    }
+
+    let clicked = [(10f64, 0f64), (7.1f64, 7.1f64), (0f64, 10f64), (-7f64, 7.2f64), (-10f64, 0.1f64)];
+
+    for fit in [FitType::Algebraic, FitType::Geometric] {
+        if let Some(fit) = Circle::fit(clicked, fit) {
+            println!("circle center = {:?}; radius = {}; rms = {};", fit.shape().center(), fit.shape().radius(), fit.stats().rms());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn stem_triangulation() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let stem = Poly::new(mesh.contour().unwrap());
+
+    let ears = stem.triangulate();
+    let delaunay = mesh.triangulate(true);
+
+    println!("ear triangles = {}; ear area = {}; delaunay triangles = {}; delaunay area = {}; stem area = {};", ears.triangles().len(), ears.area(), delaunay.triangles().len(), delaunay.area(), stem.area());
+
+    Ok(())
+}
+
+pub fn stem_crossing_edges() -> std::io::Result<()> {
+    let stl_path = r#"C:\OneDrive\Code\Bachelor\models\stl\LINK SP-CL 3D\177-202_26.stl"#;
+    let stl = Stl::read_binary(stl_path)?;
+
+    let mesh = IndSegMesh::from_stl(&stl).proj_2d(|vertex| (vertex.x() as f64, vertex.z() as f64));
+
+    let crossings = mesh.intersections();
+    let planar = mesh.split_intersections();
+
+    println!("segments = {}; intersections = {}; planar vertices = {}; planar segments = {};", mesh.segments().len(), crossings.len(), planar.vertices().len(), planar.segments().len());
+
+    Ok(())
+}
+
+pub fn svg_path_import() {
+    let paths = [
+        r#"<svg><path d="M0 0 L10 0 L10 10 Z"/></svg>"#,
+        r#"<svg><path d="m0 0 10 0 0 10 h-5 q-5 0 -5 -5 z"/></svg>"#,
+        r#"<svg><path d="M0 0 C 5 5 10 5 15 0 S 25 -5 30 0"/></svg>"#,
+        r#"<svg><path d="M0 0 A 5 5 0 0 1 10 0 Z"/></svg>"#,
+        r#"<svg><path d="M0 0 L10 0 L10 10 Z 5 5"/></svg>"#,
+        r#"<svg><path d="M0 0 L10 0 # 5"/></svg>"#
+    ];
+
+    for path in paths {
+        match Svg::parse(path, 0.1f64) {
+            Ok(svg) => println!("paths = {}; vertices = {:?};", svg.paths().len(), svg.paths().iter().map(|path| path.vertices().len()).collect::<Vec<_>>()),
+            Err(err) => println!("error = {};", err)
+        }
+    }
+}
+
+pub fn delaunay_hull_coverage() {
+    // scattered points in a thin strip with hull edges that a finite super triangle flips away
+    let vertices = (0..50).map(|index| {
+        ((index * 7919 % 1000) as f64, (index * 104729 % 997) as f64 / 997f64)
+    }).collect::<Vec<Vect2<f64>>>();
+
+    let mesh = crate::geom::d2::mesh::ind::IndSegMesh::new_unchecked(vertices, Vec::new());
+
+    let delaunay = mesh.triangulate(false);
+    let hull = mesh.convex_hull();
+
+    println!("delaunay triangles = {}; delaunay area = {}; hull area = {};", delaunay.triangles().len(), delaunay.area(), hull.area());
+}